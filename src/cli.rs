@@ -1,9 +1,14 @@
-use clap::Parser;
+use crate::export::ExportFormat;
+use clap::{Args, Parser, Subcommand};
 
 #[derive(Parser, Debug)]
 #[command(name = "textdraw")]
 #[command(about = "An interactive terminal ASCII diagram editor", long_about = None)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// File to open (or render with --render flag)
     #[arg(value_name = "FILE")]
     pub file: Option<String>,
@@ -11,4 +16,54 @@ pub struct Cli {
     /// Render the file to the terminal without entering TUI mode
     #[arg(short, long)]
     pub render: bool,
+
+    /// Render only these layers (comma-separated names) instead of the visible ones
+    #[arg(long, value_delimiter = ',', value_name = "NAMES", requires = "render")]
+    pub layers: Vec<String>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Export a diagram to another format
+    Export(ExportArgs),
+    /// Import plain-text ASCII art as an editable diagram
+    Import(ImportArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct ExportArgs {
+    /// Diagram file to export
+    #[arg(value_name = "FILE")]
+    pub file: String,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = ExportFormat::Svg)]
+    pub format: ExportFormat,
+
+    /// Write to this file instead of stdout
+    #[arg(short, long, value_name = "OUTPUT")]
+    pub output: Option<String>,
+
+    /// Font size in pixels
+    #[arg(long, default_value_t = 14)]
+    pub font_size: u16,
+
+    /// Padding around the diagram in pixels
+    #[arg(long, default_value_t = 16)]
+    pub padding: u16,
+
+    /// Export only these layers (comma-separated names) instead of the visible ones
+    #[arg(long, value_delimiter = ',', value_name = "NAMES")]
+    pub layers: Vec<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct ImportArgs {
+    /// Text file containing ASCII art
+    #[arg(value_name = "FILE")]
+    pub file: String,
+
+    /// Diagram file to write (defaults to FILE with a .textdraw extension)
+    #[arg(short, long, value_name = "OUTPUT")]
+    pub output: Option<String>,
 }
@@ -1,11 +1,13 @@
 use crate::components::Component;
-use crate::components::table_editor;
+use crate::components::{label_editor, table_editor, vertex_editor};
+use crate::events::MouseEventKind;
 use crate::events::{ActionType, EventHandler, EventResult, KeyEvent, MouseEvent};
+use crate::keymap::{Action, Context};
 use crate::state::AppState;
 use crate::tools::Tool;
-use crate::types::{Panel, RenderMap, SelectionMode};
+use crate::types::{Panel, RenderMap, SelectionMode, ZOrder};
 use crate::ui::CURSOR_BLOCK;
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyModifiers, MouseButton};
 use ratatui::{
     Frame,
     style::{Color, Style},
@@ -14,11 +16,29 @@ use ratatui::{
 };
 use std::collections::HashMap;
 
-pub struct CanvasComponent;
+/// Canvas cells scrolled per mouse wheel notch
+const SCROLL_STEP: i32 = 3;
+/// Canvas cells panned per Shift+arrow press
+const PAN_STEP: i32 = 4;
+
+/// Drawn over the corners and edge midpoints of a selected rectangle or table
+const RESIZE_HANDLE: char = '■';
+
+/// Where a middle-drag pan started
+struct PanAnchor {
+    column: i32,
+    row: i32,
+    viewport_x: i32,
+    viewport_y: i32,
+}
+
+pub struct CanvasComponent {
+    pan_anchor: Option<PanAnchor>,
+}
 
 impl CanvasComponent {
     pub fn new() -> Self {
-        Self
+        Self { pan_anchor: None }
     }
 
     /// Generate a map of (x, y) -> (char, color) for welcome text
@@ -90,6 +110,25 @@ impl EventHandler for CanvasComponent {
         if state.is_editing_table() {
             return table_editor::handle_edit_table_key(state, key_event);
         }
+        if state.is_editing_label() {
+            return label_editor::handle_edit_label_key(state, key_event);
+        }
+        if state.is_editing_vertices() {
+            return vertex_editor::handle_edit_vertices_key(state, key_event);
+        }
+
+        // Pan the viewport (Shift+arrows by default)
+        let pan = match state.keymap.resolve(Context::Canvas, key_event) {
+            Some(Action::PanUp) => Some((0, -PAN_STEP)),
+            Some(Action::PanDown) => Some((0, PAN_STEP)),
+            Some(Action::PanLeft) => Some((-PAN_STEP, 0)),
+            Some(Action::PanRight) => Some((PAN_STEP, 0)),
+            _ => None,
+        };
+        if let Some((dx, dy)) = pan {
+            state.pan_viewport(dx, dy);
+            return EventResult::Consumed;
+        }
 
         // Forward to active tool first
         if let Some(tool) = state.tool.active_tool_mut() {
@@ -111,31 +150,65 @@ impl EventHandler for CanvasComponent {
 
         // Handle canvas-level selection operations (move/delete)
         if state.is_select_tool() && state.is_in_selection_mode() {
-            match key_event.code {
-                KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right => {
-                    let (dx, dy) = match key_event.code {
-                        KeyCode::Up => (0, -1),
-                        KeyCode::Down => (0, 1),
-                        KeyCode::Left => (-1, 0),
-                        KeyCode::Right => (1, 0),
-                        _ => unreachable!(),
-                    };
-                    state.move_selected_elements(dx, dy);
+            let delta = match state.keymap.resolve(Context::Selection, key_event) {
+                Some(Action::Up) => Some((0, -1)),
+                Some(Action::Down) => Some((0, 1)),
+                Some(Action::Left) => Some((-1, 0)),
+                Some(Action::Right) => Some((1, 0)),
+                Some(Action::Delete) => {
+                    state.delete_selected_elements();
                     return EventResult::Consumed;
                 }
-                KeyCode::Delete | KeyCode::Backspace => {
-                    state.delete_selected_elements();
+                Some(Action::Raise) => {
+                    state.reorder_selected(ZOrder::Raise);
                     return EventResult::Consumed;
                 }
-                KeyCode::Enter | KeyCode::Char('e') => {
-                    // Enter Edit Table mode if a table is selected
-                    return table_editor::try_enter_edit_table_mode(state);
+                Some(Action::Lower) => {
+                    state.reorder_selected(ZOrder::Lower);
+                    return EventResult::Consumed;
                 }
-                _ => {}
+                Some(Action::BringToFront) => {
+                    state.reorder_selected(ZOrder::Front);
+                    return EventResult::Consumed;
+                }
+                Some(Action::SendToBack) => {
+                    state.reorder_selected(ZOrder::Back);
+                    return EventResult::Consumed;
+                }
+                Some(Action::Group) => {
+                    state.group_selected();
+                    return EventResult::Consumed;
+                }
+                Some(Action::Ungroup) => {
+                    state.ungroup_selected();
+                    return EventResult::Consumed;
+                }
+                Some(Action::Edit) => {
+                    // Enter a selected group, or edit the cells of a selected table, the
+                    // label of a selected rectangle or the bends of a selected line or arrow
+                    if state.enter_selected_group() {
+                        return EventResult::Consumed;
+                    }
+                    let result = table_editor::try_enter_edit_table_mode(state);
+                    if !matches!(result, EventResult::Ignored) {
+                        return result;
+                    }
+                    let result = label_editor::try_enter_edit_label_mode(state);
+                    if !matches!(result, EventResult::Ignored) {
+                        return result;
+                    }
+                    return vertex_editor::try_enter_edit_vertices_mode(state);
+                }
+                _ => None,
+            };
+            if let Some((dx, dy)) = delta {
+                state.move_selected_elements(dx, dy);
+                return EventResult::Consumed;
             }
+            return EventResult::Ignored;
         }
 
-        EventResult::Ignored
+        self.handle_cursor_key(state, key_event)
     }
 
     fn handle_mouse_down(&mut self, state: &mut AppState, mouse_event: &MouseEvent) -> EventResult {
@@ -155,6 +228,17 @@ impl EventHandler for CanvasComponent {
             return EventResult::Ignored;
         }
 
+        // Middle button starts panning instead of drawing or selecting
+        if mouse_event.kind == MouseEventKind::Down(MouseButton::Middle) {
+            self.pan_anchor = Some(PanAnchor {
+                column: mouse_event.column,
+                row: mouse_event.row,
+                viewport_x: state.viewport_x,
+                viewport_y: state.viewport_y,
+            });
+            return EventResult::Consumed;
+        }
+
         // Mark user action (hides welcome screen)
         state.mark_user_action();
 
@@ -169,6 +253,15 @@ impl EventHandler for CanvasComponent {
 
         // Forward to select tool or active drawing tool
         if state.is_select_tool() {
+            if state.is_editing_vertices()
+                && vertex_editor::handle_edit_vertices_mouse_down(
+                    state,
+                    canvas_event.column,
+                    canvas_event.row,
+                )
+            {
+                return EventResult::Consumed;
+            }
             self.handle_selection_mouse_down(
                 state,
                 canvas_event.column,
@@ -193,6 +286,12 @@ impl EventHandler for CanvasComponent {
     }
 
     fn handle_mouse_up(&mut self, state: &mut AppState, mouse_event: &MouseEvent) -> EventResult {
+        if mouse_event.kind == MouseEventKind::Up(MouseButton::Middle)
+            && self.pan_anchor.take().is_some()
+        {
+            return EventResult::Consumed;
+        }
+
         if !should_handle_event(state, mouse_event) {
             return EventResult::Ignored;
         }
@@ -208,6 +307,9 @@ impl EventHandler for CanvasComponent {
 
         // Handle selection
         if state.is_select_tool() {
+            if vertex_editor::release_vertex(state) {
+                return EventResult::Consumed;
+            }
             match state.selection_state.mode {
                 SelectionMode::Selecting => {
                     state.finish_selection(canvas_event.column, canvas_event.row);
@@ -215,6 +317,9 @@ impl EventHandler for CanvasComponent {
                 SelectionMode::Moving => {
                     state.finish_move_selection();
                 }
+                SelectionMode::Resizing => {
+                    state.finish_resize();
+                }
                 _ => {}
             }
             return EventResult::Consumed;
@@ -263,6 +368,14 @@ impl EventHandler for CanvasComponent {
     }
 
     fn handle_mouse_drag(&mut self, state: &mut AppState, mouse_event: &MouseEvent) -> EventResult {
+        // Middle-drag: keep the canvas point under the mouse where it was grabbed
+        if let Some(anchor) = &self.pan_anchor {
+            let target_x = anchor.viewport_x - (mouse_event.column - anchor.column);
+            let target_y = anchor.viewport_y - (mouse_event.row - anchor.row);
+            state.pan_viewport(target_x - state.viewport_x, target_y - state.viewport_y);
+            return EventResult::Consumed;
+        }
+
         if !should_handle_event(state, mouse_event) {
             return EventResult::Ignored;
         }
@@ -280,11 +393,16 @@ impl EventHandler for CanvasComponent {
 
         // Handle selection
         if state.is_select_tool() {
+            if vertex_editor::drag_vertex(state, canvas_event.column, canvas_event.row) {
+                return EventResult::Consumed;
+            }
             if state.is_in_selection_mode() {
                 if state.selection_state.mode == SelectionMode::Selecting {
                     state.update_selection(canvas_event.column, canvas_event.row);
                 } else if state.selection_state.mode == SelectionMode::Moving {
                     state.update_move_selection(canvas_event.column, canvas_event.row);
+                } else if state.selection_state.mode == SelectionMode::Resizing {
+                    state.update_resize(canvas_event.column, canvas_event.row);
                 }
             }
             return EventResult::Consumed;
@@ -297,34 +415,138 @@ impl EventHandler for CanvasComponent {
 
         EventResult::Ignored
     }
+
+    fn handle_mouse_scroll(
+        &mut self,
+        state: &mut AppState,
+        mouse_event: &MouseEvent,
+    ) -> EventResult {
+        if !state.is_inside_canvas(mouse_event.column, mouse_event.row) {
+            return EventResult::Ignored;
+        }
+
+        // Wheel scrolls vertically, Shift+wheel (or a horizontal wheel) scrolls horizontally
+        let (dx, dy) = match (mouse_event.kind, mouse_event.is_shift()) {
+            (MouseEventKind::ScrollUp, false) => (0, -SCROLL_STEP),
+            (MouseEventKind::ScrollDown, false) => (0, SCROLL_STEP),
+            (MouseEventKind::ScrollUp, true) | (MouseEventKind::ScrollLeft, _) => (-SCROLL_STEP, 0),
+            (MouseEventKind::ScrollDown, true) | (MouseEventKind::ScrollRight, _) => {
+                (SCROLL_STEP, 0)
+            }
+            _ => return EventResult::Ignored,
+        };
+        state.pan_viewport(dx, dy);
+
+        // The canvas point under the mouse changed - refresh any drawing preview
+        let moved = MouseEvent {
+            kind: MouseEventKind::Moved,
+            ..*mouse_event
+        };
+        self.handle_mouse_moved(state, &moved)
+    }
 }
 
 impl CanvasComponent {
     /// Convert screen coordinates to canvas coordinates
-    fn to_canvas_coords(&self, state: &AppState, column: u16, row: u16) -> Option<(u16, u16)> {
+    fn to_canvas_coords(&self, state: &AppState, column: i32, row: i32) -> Option<(i32, i32)> {
         let canvas_area = state.layout.canvas;
         // First check if click is within the canvas area at all
-        if column < canvas_area.x
-            || column >= canvas_area.x + canvas_area.width
-            || row < canvas_area.y
-            || row >= canvas_area.y + canvas_area.height
-        {
+        if !state.is_inside_canvas(column, row) {
             return None;
         }
 
-        let canvas_x = column.saturating_sub(canvas_area.x + 1);
-        let canvas_y = row.saturating_sub(canvas_area.y + 1);
+        let screen_x = column - (canvas_area.x as i32 + 1);
+        let screen_y = row - (canvas_area.y as i32 + 1);
 
-        // Check if within canvas bounds (excluding borders)
-        if canvas_x < canvas_area.width.saturating_sub(2)
-            && canvas_y < canvas_area.height.saturating_sub(2)
+        // Check if within canvas bounds (excluding borders), then apply the viewport offset
+        if (0..canvas_area.width as i32 - 2).contains(&screen_x)
+            && (0..canvas_area.height as i32 - 2).contains(&screen_y)
         {
-            return Some((canvas_x, canvas_y));
+            return Some((screen_x + state.viewport_x, screen_y + state.viewport_y));
         }
 
         None
     }
 
+    /// Keyboard drawing: move the canvas cursor and click at it, so everything the mouse
+    /// does can be done without one (e.g. over SSH)
+    fn handle_cursor_key(&mut self, state: &mut AppState, key_event: &KeyEvent) -> EventResult {
+        if state.active_panel != Panel::Canvas {
+            return EventResult::Ignored;
+        }
+        let Some(action) = state.keymap.resolve(Context::Canvas, key_event) else {
+            return EventResult::Ignored;
+        };
+        // Keys with a global meaning too (`l` for the Line tool, Space for the tools
+        // modal) only act on the canvas while drawing
+        if !state.is_drawing() && state.keymap.resolve(Context::Global, key_event).is_some() {
+            return EventResult::Ignored;
+        }
+
+        let (dx, dy) = match action {
+            Action::Up => (0, -1),
+            Action::Down => (0, 1),
+            Action::Left => (-1, 0),
+            Action::Right => (1, 0),
+            Action::Anchor => return self.click_at_cursor(state, KeyModifiers::NONE),
+            // Same as a Shift+click: lines and arrows get a bend, other tools finish
+            Action::Bend if state.is_drawing() => {
+                return self.click_at_cursor(state, KeyModifiers::SHIFT);
+            }
+            _ => return EventResult::Ignored,
+        };
+
+        state.mark_user_action();
+        state.move_cursor(dx, dy);
+        if state.is_drawing() {
+            let moved = self.cursor_event(state, MouseEventKind::Moved, KeyModifiers::NONE);
+            if let Some(tool) = state.tool.active_tool_mut() {
+                tool.handle_mouse_moved(&mut state.canvas, &moved);
+            }
+        }
+        EventResult::Consumed
+    }
+
+    /// Click at the canvas cursor: select the element under it with the Select tool,
+    /// or anchor/commit a point with a drawing tool
+    fn click_at_cursor(&mut self, state: &mut AppState, modifiers: KeyModifiers) -> EventResult {
+        state.mark_user_action();
+        let click = self.cursor_event(state, MouseEventKind::Down(MouseButton::Left), modifiers);
+
+        if state.is_select_tool() {
+            state.toggle_selection_at(click.column, click.row);
+            return EventResult::Consumed;
+        }
+
+        let Some(tool) = state.tool.active_tool_mut() else {
+            return EventResult::Ignored;
+        };
+        match tool.handle_mouse_down(&mut state.canvas, &click) {
+            EventResult::Action(ActionType::FinishedDrawing) => {
+                if !state.tool.tool_locked {
+                    state.select_tool(Tool::Select);
+                }
+                EventResult::Consumed
+            }
+            result => result,
+        }
+    }
+
+    /// Canvas-space mouse event at the cursor position
+    fn cursor_event(
+        &self,
+        state: &AppState,
+        kind: MouseEventKind,
+        modifiers: KeyModifiers,
+    ) -> MouseEvent {
+        MouseEvent {
+            column: state.cursor_x,
+            row: state.cursor_y,
+            kind,
+            modifiers,
+        }
+    }
+
     /// Convert a screen-space mouse event to canvas-space
     /// Returns None if the event is outside the canvas bounds
     fn to_canvas_event(&self, state: &AppState, mouse_event: &MouseEvent) -> Option<MouseEvent> {
@@ -337,13 +559,19 @@ impl CanvasComponent {
     fn handle_selection_mouse_down(
         &self,
         state: &mut AppState,
-        canvas_x: u16,
-        canvas_y: u16,
+        canvas_x: i32,
+        canvas_y: i32,
         shift_pressed: bool,
     ) {
         // Shift+Click: toggle selection at this position (additive selection)
         if shift_pressed {
-            state.toggle_selection_at(canvas_x as i32, canvas_y as i32);
+            state.toggle_selection_at(canvas_x, canvas_y);
+            return;
+        }
+
+        // Grabbing a resize handle of the selected rectangle or table
+        if let Some(handle) = state.resize_handle_at(canvas_x, canvas_y) {
+            state.start_resize(handle, canvas_x, canvas_y);
             return;
         }
 
@@ -367,13 +595,10 @@ impl CanvasComponent {
     }
 
     /// Check if click is inside any selected element
-    fn is_clicking_selected_element(&self, state: &AppState, canvas_x: u16, canvas_y: u16) -> bool {
-        let px = canvas_x as i32;
-        let py = canvas_y as i32;
-
+    fn is_clicking_selected_element(&self, state: &AppState, canvas_x: i32, canvas_y: i32) -> bool {
         for element_id in state.get_selected_element_ids() {
             if let Some(element) = state.canvas.get_element(*element_id) {
-                if element.point_in_bounds(px, py) {
+                if element.point_in_bounds(canvas_x, canvas_y) {
                     return true;
                 }
             }
@@ -406,11 +631,15 @@ impl Component for CanvasComponent {
         let selected_ids = state.get_selected_element_ids();
         let move_offset = state.get_move_offset();
 
+        // Resize handles (or the element at its new size while dragging one)
+        let resize_handles = state.get_resize_handles();
+        let resize_preview = state.get_resize_preview();
+
         // Build render cache: map (x, y) -> (char, element_id)
         // This is O(total_points) instead of O(pixels × elements)
         let mut render_map: HashMap<(i32, i32), (char, usize)> = HashMap::new();
 
-        for element in state.canvas.elements() {
+        for element in state.canvas.draw_order() {
             let element_id = element.id();
             let is_selected = selected_ids.contains(&element_id);
 
@@ -422,7 +651,10 @@ impl Component for CanvasComponent {
             };
 
             // Generate points from element and add to render map (with offset if moving)
-            let points = element.render_points();
+            let points = match &resize_preview {
+                Some(preview) if preview.id() == element_id => preview.render_points(),
+                _ => element.render_points(),
+            };
             for (x, y, ch) in points {
                 let render_x = x + offset_x;
                 let render_y = y + offset_y;
@@ -430,16 +662,25 @@ impl Component for CanvasComponent {
             }
         }
 
-        // Build Edit Table mode overlays (selected cell highlight + edit buffer)
-        let (edit_table_highlight_map, edit_table_content_map) =
+        // Build Edit Table/Label/Vertices mode overlays (highlight + edit buffer or cursor)
+        let (mut edit_table_highlight_map, mut edit_table_content_map) =
             table_editor::build_edit_table_overlays(state);
-
-        // Check if actively editing a cell (for color selection)
+        let (label_highlight_map, label_content_map) =
+            label_editor::build_edit_label_overlays(state);
+        let (vertex_highlight_map, vertex_content_map) =
+            vertex_editor::build_edit_vertices_overlays(state);
+        edit_table_highlight_map.extend(label_highlight_map);
+        edit_table_content_map.extend(label_content_map);
+        edit_table_highlight_map.extend(vertex_highlight_map);
+        edit_table_content_map.extend(vertex_content_map);
+
+        // Check if actively editing a cell or label (for color selection)
         let is_actively_editing_cell = state
             .editing_table
             .as_ref()
             .map(|e| e.editing_cell)
-            .unwrap_or(false);
+            .unwrap_or(false)
+            || state.is_editing_label();
 
         // Calculate welcome text positioning if needed
         let welcome_text_map = if state.should_show_welcome() {
@@ -451,15 +692,16 @@ impl Component for CanvasComponent {
         for y in 0..area.height.saturating_sub(2) {
             let mut line_chars = vec![];
             for x in 0..area.width.saturating_sub(2) {
-                let px = x as i32;
-                let py = y as i32;
+                // Canvas coordinates of this screen cell
+                let px = x as i32 + state.viewport_x;
+                let py = y as i32 + state.viewport_y;
 
-                // Priority: cursor > welcome text > selection box > preview > elements
+                // Priority: cursor > welcome text > resize handles > selection box > preview > elements
 
                 // Check if actively selecting or moving
                 let is_actively_selecting_or_moving = matches!(
                     state.selection_state.mode,
-                    SelectionMode::Selecting | SelectionMode::Moving
+                    SelectionMode::Selecting | SelectionMode::Moving | SelectionMode::Resizing
                 );
 
                 // Check if hovering over a selected element (to hide cursor)
@@ -475,8 +717,8 @@ impl Component for CanvasComponent {
                     }
                 }
 
-                if x == state.cursor_x
-                    && y == state.cursor_y
+                if px == state.cursor_x
+                    && py == state.cursor_y
                     && state.active_panel == Panel::Canvas
                     && !state.is_drawing()
                     && !is_actively_selecting_or_moving
@@ -506,6 +748,12 @@ impl Component for CanvasComponent {
                     } else {
                         line_chars.push(Span::raw(" "));
                     }
+                } else if state.active_panel == Panel::Canvas && resize_handles.contains(&(px, py))
+                {
+                    line_chars.push(Span::styled(
+                        RESIZE_HANDLE.to_string(),
+                        Style::default().fg(Color::Cyan),
+                    ));
                 } else if let Some(&ch) = selection_box_map.get(&(px, py)) {
                     // Show selection box in grey (during drag-select)
                     line_chars.push(Span::styled(
@@ -520,9 +768,13 @@ impl Component for CanvasComponent {
                         Style::default().fg(preview_color),
                     ));
                 } else if let Some((edit_ch, is_cursor)) = edit_table_content_map.get(&(px, py)) {
-                    // Edit Table mode: show edit buffer content with cursor
-                    // Blue background when editing, cursor is shown as │ character
-                    let style = Style::default().bg(Color::Blue).fg(Color::White);
+                    // Edit Table/Label mode: show edit buffer content with cursor
+                    // Blue background when editing, cursor cell in reverse colors
+                    let style = if *is_cursor {
+                        Style::default().bg(Color::White).fg(Color::Blue)
+                    } else {
+                        Style::default().bg(Color::Blue).fg(Color::White)
+                    };
                     line_chars.push(Span::styled(edit_ch.to_string(), style));
                 } else if let Some((ch, element_id)) = render_map.get(&(px, py)) {
                     // Found element at this position - O(1) lookup!
@@ -575,17 +827,23 @@ impl Component for CanvasComponent {
             Style::default()
         };
 
-        // Build title with filename if available
-        let title = if let Some(ref file) = state.file.current_file {
+        // Build title with filename (if available) and scroll position
+        let mut title = "Canvas".to_string();
+        if let Some(ref file) = state.file.current_file {
             // Extract just the filename from the path
             let filename = std::path::Path::new(file)
                 .file_name()
                 .and_then(|n| n.to_str())
                 .unwrap_or(file);
-            format!("Canvas ─── {} ───", filename)
-        } else {
-            "Canvas".to_string()
-        };
+            title.push_str(&format!(" ─── {}", filename));
+        }
+        if state.file.is_dirty() {
+            title.push_str(" [+]");
+        }
+        title.push_str(&format!(
+            " ─── ({}, {}) ───",
+            state.viewport_x, state.viewport_y
+        ));
 
         let canvas = Paragraph::new(lines).block(
             Block::default()
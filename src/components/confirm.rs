@@ -0,0 +1,141 @@
+use crate::components::Component;
+use crate::events::{ActionType, EventHandler, EventResult, KeyEvent, MouseEvent};
+use crate::keymap::{Action, Context};
+use crate::state::{AppState, ConfirmAction};
+use crate::ui;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph},
+};
+
+const MODAL_WIDTH: u16 = 52;
+const MODAL_HEIGHT: u16 = 7;
+
+/// Modal asking whether unsaved changes may be discarded before quitting or opening a
+/// file, or whether to recover changes from a swap file at startup
+pub struct ConfirmModal;
+
+impl ConfirmModal {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn centered_rect(area: Rect) -> Rect {
+        let width = MODAL_WIDTH.min(area.width);
+        let height = MODAL_HEIGHT.min(area.height);
+        Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        }
+    }
+}
+
+impl EventHandler for ConfirmModal {
+    type State = AppState;
+
+    fn handle_key_event(&mut self, state: &mut AppState, key_event: &KeyEvent) -> EventResult {
+        if !state.is_confirming() {
+            return EventResult::Ignored;
+        }
+
+        match state.keymap.resolve(Context::Modal, key_event) {
+            Some(Action::Yes) => {
+                return if state.confirm_pending_action() {
+                    EventResult::Action(ActionType::Quit)
+                } else {
+                    EventResult::Consumed
+                };
+            }
+            Some(Action::No) => state.cancel_pending_action(),
+            _ => {}
+        }
+        // Modal: nothing else reacts to keys while it is open
+        EventResult::Consumed
+    }
+
+    fn handle_mouse_down(
+        &mut self,
+        state: &mut AppState,
+        _mouse_event: &MouseEvent,
+    ) -> EventResult {
+        if state.is_confirming() {
+            EventResult::Consumed
+        } else {
+            EventResult::Ignored
+        }
+    }
+}
+
+impl Component for ConfirmModal {
+    fn draw(&mut self, state: &AppState, frame: &mut Frame) {
+        let Some(action) = &state.confirm else {
+            return;
+        };
+
+        let (title, message, question, yes, no) = match action {
+            ConfirmAction::Quit => (
+                " Unsaved Changes ",
+                "You have unsaved changes.",
+                "Quit anyway?".to_string(),
+                "Discard changes",
+                "Cancel",
+            ),
+            ConfirmAction::Open(path) => (
+                " Unsaved Changes ",
+                "You have unsaved changes.",
+                format!("Open {} anyway?", path),
+                "Discard changes",
+                "Cancel",
+            ),
+            ConfirmAction::New => (
+                " Unsaved Changes ",
+                "You have unsaved changes.",
+                "Start a new diagram anyway?".to_string(),
+                "Discard changes",
+                "Cancel",
+            ),
+            ConfirmAction::Recover(_) => (
+                " Recover Changes ",
+                "Found unsaved changes from an earlier session.",
+                "Recover them?".to_string(),
+                "Recover",
+                "Not now",
+            ),
+        };
+        let hint = Style::default().fg(ui::theme().hint);
+        let text = vec![
+            Line::from(message),
+            Line::from(question),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled(state.keymap.label(Context::Modal, Action::Yes), hint),
+                Span::raw(format!(": {}   ", yes)),
+                Span::styled(state.keymap.label(Context::Modal, Action::No), hint),
+                Span::raw(format!(": {}", no)),
+            ]),
+        ];
+
+        let area = Self::centered_rect(frame.area());
+        frame.render_widget(Clear, area);
+        let modal = Paragraph::new(text).alignment(Alignment::Center).block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(ui::theme().error))
+                .padding(Padding::horizontal(1)),
+        );
+        frame.render_widget(modal, area);
+    }
+}
+
+impl Default for ConfirmModal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
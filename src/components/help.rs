@@ -1,8 +1,8 @@
 use crate::components::Component;
-use crate::components::help_line::HELP_LINES;
+use crate::components::help_line::help_lines;
 use crate::events::{EventHandler, EventResult, KeyEvent, MouseEvent, MouseEventKind};
+use crate::keymap::{Action, Context};
 use crate::state::AppState;
-use crossterm::event::KeyCode;
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Layout, Rect},
@@ -43,13 +43,13 @@ impl HelpModal {
         self.scroll = self.scroll.saturating_sub(1);
     }
 
-    fn scroll_down(&mut self, viewport_height: u16) {
-        let max_scroll = self.max_scroll_for_viewport(viewport_height);
+    fn scroll_down(&mut self, total_lines: usize, viewport_height: u16) {
+        let max_scroll = Self::max_scroll_for_viewport(total_lines, viewport_height);
         self.scroll = self.scroll.saturating_add(1).min(max_scroll);
     }
 
-    fn max_scroll_for_viewport(&self, viewport_height: u16) -> u16 {
-        let total_lines = HELP_LINES.len() as u16;
+    fn max_scroll_for_viewport(total_lines: usize, viewport_height: u16) -> u16 {
+        let total_lines = total_lines as u16;
         let visible_lines = viewport_height.saturating_sub(2); // Account for borders and padding
         total_lines.saturating_sub(visible_lines)
     }
@@ -62,17 +62,17 @@ impl EventHandler for HelpModal {
             return EventResult::Ignored;
         }
 
-        match key_event.code {
-            KeyCode::Up | KeyCode::Char('k') => {
+        match state.keymap.resolve(Context::Modal, key_event) {
+            Some(Action::Up) => {
                 self.scroll_up();
                 EventResult::Consumed
             }
-            KeyCode::Down | KeyCode::Char('j') => {
+            Some(Action::Down) => {
                 // We need to calculate viewport height for scroll bounds
                 // Use the modal height (60% of terminal)
                 let terminal_height = state.layout.canvas.height;
                 let viewport_height = (terminal_height * 60) / 100;
-                self.scroll_down(viewport_height);
+                self.scroll_down(help_lines(&state.keymap).len(), viewport_height);
                 EventResult::Consumed
             }
             _ => EventResult::Ignored,
@@ -96,7 +96,7 @@ impl EventHandler for HelpModal {
             MouseEventKind::ScrollDown => {
                 let terminal_height = state.layout.canvas.height;
                 let viewport_height = (terminal_height * 60) / 100;
-                self.scroll_down(viewport_height);
+                self.scroll_down(help_lines(&state.keymap).len(), viewport_height);
                 EventResult::Consumed
             }
             _ => EventResult::Ignored,
@@ -115,7 +115,8 @@ impl Component for HelpModal {
         // Clear the area
         frame.render_widget(Clear, area);
 
-        let help_text: Vec<Line> = HELP_LINES.iter().map(|line| line.to_line()).collect();
+        let lines = help_lines(&state.keymap);
+        let help_text: Vec<Line> = lines.iter().map(|line| line.to_line()).collect();
 
         let help = Paragraph::new(help_text)
             .block(
@@ -143,7 +144,7 @@ impl Component for HelpModal {
             .begin_symbol(Some("↑"))
             .end_symbol(Some("↓"));
 
-        let max_scroll = self.max_scroll_for_viewport(area.height);
+        let max_scroll = Self::max_scroll_for_viewport(lines.len(), area.height);
         let mut scrollbar_state =
             ScrollbarState::new(max_scroll as usize).position(self.scroll as usize);
 
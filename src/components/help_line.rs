@@ -1,18 +1,19 @@
+use crate::keymap::{Action, Context, Keymap};
 use ratatui::{
     style::{Color, Modifier, Style},
     text::{Line, Span},
 };
 
-const KEY_COLUMN_WIDTH: usize = 11;
+const KEY_COLUMN_WIDTH: usize = 15;
 
 #[derive(Debug, Clone)]
 pub enum HelpLine {
     Title(&'static str),
     Subtitle(&'static str),
-    Description(&'static str),
+    Description(String),
     Section(&'static str),
     KeyBinding {
-        key: &'static str,
+        key: String,
         desc: &'static str,
     },
     CommandHeader,
@@ -39,9 +40,7 @@ impl HelpLine {
                 text.to_string(),
                 Style::default().fg(Color::DarkGray),
             )),
-            HelpLine::Description(text) => {
-                Line::from(Span::styled(text.to_string(), Style::default()))
-            }
+            HelpLine::Description(text) => Line::from(Span::styled(text.clone(), Style::default())),
             HelpLine::Section(text) => Line::from(Span::styled(
                 text.to_string(),
                 Style::default().add_modifier(Modifier::BOLD),
@@ -54,7 +53,7 @@ impl HelpLine {
                 ])
             }
             HelpLine::CommandHeader => {
-                const CMD_COL_WIDTH: usize = 20;
+                const CMD_COL_WIDTH: usize = 24;
                 const DESC_COL_WIDTH: usize = 18;
 
                 Line::from(vec![
@@ -75,7 +74,7 @@ impl HelpLine {
                 aliases,
                 desc,
             } => {
-                const CMD_COL_WIDTH: usize = 20;
+                const CMD_COL_WIDTH: usize = 24;
                 const DESC_COL_WIDTH: usize = 18;
 
                 let mut spans = vec![];
@@ -122,16 +121,19 @@ const fn subtitle(text: &'static str) -> HelpLine {
     HelpLine::Subtitle(text)
 }
 
-const fn description(text: &'static str) -> HelpLine {
-    HelpLine::Description(text)
+fn description(text: impl Into<String>) -> HelpLine {
+    HelpLine::Description(text.into())
 }
 
 const fn section(text: &'static str) -> HelpLine {
     HelpLine::Section(text)
 }
 
-const fn keybinding(key: &'static str, desc: &'static str) -> HelpLine {
-    HelpLine::KeyBinding { key, desc }
+fn keybinding(key: impl Into<String>, desc: &'static str) -> HelpLine {
+    HelpLine::KeyBinding {
+        key: key.into(),
+        desc,
+    }
 }
 
 const fn command_header() -> HelpLine {
@@ -156,45 +158,116 @@ const fn blank() -> HelpLine {
     HelpLine::Blank
 }
 
-pub const HELP_LINES: &[HelpLine] = &[
-    subtitle("Press Esc to close"),
-    blank(),
-    title("TextDraw v0.1.0"),
-    blank(),
-    description("Interactive terminal ASCII diagram editor built with Ratatui."),
-    description("Create and edit diagrams using simple drawing tools and keyboard shortcuts."),
-    blank(),
-    title("Keyboard Shortcuts"),
-    blank(),
-    section("Tools"),
-    keybinding("s", "Select tool"),
-    keybinding("l", "Line tool"),
-    keybinding("r", "Rectangle tool"),
-    keybinding("a", "Arrow tool"),
-    keybinding("t", "Text tool"),
-    blank(),
-    section("Selection"),
-    keybinding("Click", "Select element"),
-    keybinding("Drag", "Select multiple elements"),
-    keybinding("←↑↓→", "Move selected elements"),
-    keybinding("⌫/Del", "Delete selected elements"),
-    blank(),
-    section("General"),
-    keybinding("Space", "Toggle tools modal"),
-    keybinding("p", "Toggle properties"),
-    keybinding(":", "Enter command mode"),
-    keybinding("Ctrl+S", "Quick save command"),
-    keybinding("Ctrl+O", "Quick open command"),
-    keybinding("Esc", "Cancel"),
-    keybinding("?", "Toggle help"),
-    keybinding("q", "Quit"),
-    blank(),
-    title("Command Mode"),
-    blank(),
-    description("Press : to enter command mode. Type commands to save/load files."),
-    blank(),
-    command_header(),
-    command(":save", "<file>", &[":w", ":s"], "Save diagram"),
-    command(":open", "<file>", &[":e", ":o"], "Open diagram"),
-    blank(),
+/// Global actions listed under "Editing" rather than "General"
+const EDITING_ACTIONS: &[Action] = &[
+    Action::Undo,
+    Action::Redo,
+    Action::Copy,
+    Action::Cut,
+    Action::Paste,
+    Action::Duplicate,
 ];
+
+/// Key bindings of a context (optionally filtered), skipping actions without keys
+fn context_bindings(
+    keymap: &Keymap,
+    context: Context,
+    filter: impl Fn(Action) -> bool,
+) -> Vec<HelpLine> {
+    keymap
+        .bindings(context)
+        .filter(|b| !b.keys.is_empty() && filter(b.action))
+        .map(|b| keybinding(b.label(), b.description))
+        .collect()
+}
+
+/// Help text for the current key bindings
+pub fn help_lines(keymap: &Keymap) -> Vec<HelpLine> {
+    let is_tool = |action| matches!(action, Action::SelectTool(_));
+    let is_editing = |action| EDITING_ACTIONS.contains(&action);
+
+    let mut lines = vec![
+        subtitle("Press Esc to close"),
+        blank(),
+        title("TextDraw v0.1.0"),
+        blank(),
+        description("Interactive terminal ASCII diagram editor built with Ratatui."),
+        description("Create and edit diagrams using simple drawing tools and keyboard shortcuts."),
+        blank(),
+        title("Keyboard Shortcuts"),
+        blank(),
+        section("Tools"),
+    ];
+    lines.extend(context_bindings(keymap, Context::Global, is_tool));
+
+    lines.extend([blank(), section(Context::Selection.title())]);
+    lines.push(keybinding("Click", "Select element"));
+    lines.push(keybinding("Drag", "Select multiple elements"));
+    lines.push(keybinding("Drag handle", "Resize rectangle or table"));
+    lines.extend(context_bindings(keymap, Context::Selection, |_| true));
+
+    lines.extend([blank(), section("Editing")]);
+    lines.extend(context_bindings(keymap, Context::Global, is_editing));
+
+    lines.extend([blank(), section(Context::Canvas.title())]);
+    lines.push(keybinding("Scroll", "Pan canvas vertically"));
+    lines.push(keybinding("Shift+Scroll", "Pan canvas horizontally"));
+    lines.extend(context_bindings(keymap, Context::Canvas, |_| true));
+    lines.push(keybinding("Middle-drag", "Pan canvas"));
+
+    lines.extend([blank(), section(Context::Global.title())]);
+    lines.extend(context_bindings(keymap, Context::Global, |action| {
+        !is_tool(action) && !is_editing(action)
+    }));
+
+    for context in [
+        Context::TableEdit,
+        Context::VertexEdit,
+        Context::Panel,
+        Context::Modal,
+    ] {
+        lines.extend([blank(), section(context.title())]);
+        lines.extend(context_bindings(keymap, context, |_| true));
+    }
+
+    let command_key = keymap.label(Context::Global, Action::Command);
+    lines.extend([
+        blank(),
+        title(Context::Command.title()),
+        blank(),
+        description(format!(
+            "Press {} to enter command mode. Type commands to save/load files.",
+            command_key
+        )),
+        description("Quitting or opening with unsaved changes ([+] in the title) asks first."),
+        blank(),
+    ]);
+    lines.extend(context_bindings(keymap, Context::Command, |_| true));
+    lines.extend([
+        blank(),
+        command_header(),
+        command(":save", "<file>", &[":w", ":s"], "Save diagram"),
+        command(":open", "<file>", &[":e", ":o"], "Open diagram"),
+        command(":open!", "<file>", &[":e!", ":o!"], "Open, drop edits"),
+        command(":quit", "", &[":q"], "Quit"),
+        command(":quit!", "", &[":q!"], "Quit, drop edits"),
+        command(":wq", "[file]", &[], "Save and quit"),
+        command(":exit", "[file]", &[":x"], "Quit, save edits"),
+        command(":new", "", &[], "New diagram"),
+        command(":new!", "", &[], "New, drop edits"),
+        command(":export", "<fmt> <file>", &[], "Export svg/html"),
+        command(":goto", "<x> <y>", &[], "Go to position"),
+        command(":select", "<filter>", &[], "Select elements"),
+        command(":delete", "", &[], "Delete selection"),
+        command(":set", "<prop>=<value>", &[], "Set property"),
+        command(":rename", "<name>", &[], "Rename element"),
+        command(":align", "<edge>", &[], "Align selection"),
+        blank(),
+        description(":select takes all, type=<Type> (e.g. type=Rectangle) or name=<name>."),
+        description(":align takes left, center, right, top, middle or bottom."),
+        description(":set takes property names from the properties panel, e.g. border_style."),
+        blank(),
+    ]);
+
+    lines
+}
@@ -0,0 +1,81 @@
+use crate::components::inputs::{MIXED, PropertyInput};
+use crate::elements::PropertyValue;
+use crate::events::{EventResult, KeyEvent};
+use crate::ui;
+use crossterm::event::KeyCode;
+use ratatui::text::Line;
+
+/// An on/off input component. It has no edit mode: Enter toggles the value.
+pub struct BooleanInput {
+    // Property identification
+    property_name: String,
+    label: String,
+
+    is_focused: bool,
+}
+
+impl BooleanInput {
+    pub fn new(property_name: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            property_name: property_name.into(),
+            label: label.into(),
+            is_focused: false,
+        }
+    }
+}
+
+impl PropertyInput for BooleanInput {
+    fn render_line(
+        &self,
+        current_value: Option<&PropertyValue>,
+        panel_active: bool,
+    ) -> Line<'static> {
+        let styles = ui::input_styles(false, self.is_focused, panel_active);
+        let display_value = match current_value {
+            Some(PropertyValue::Boolean(true)) => "[x] On".to_string(),
+            None => format!("[-] {}", MIXED),
+            _ => "[ ] Off".to_string(),
+        };
+
+        ui::input_line(&self.label, display_value, styles)
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.is_focused = focused;
+    }
+
+    fn is_editing(&self) -> bool {
+        false
+    }
+
+    fn property_name(&self) -> &str {
+        &self.property_name
+    }
+
+    fn handle_key_event(
+        &mut self,
+        key: &KeyEvent,
+        current_value: Option<&PropertyValue>,
+        on_change: &mut dyn FnMut(&str, PropertyValue),
+    ) -> EventResult {
+        if !self.is_focused {
+            return EventResult::Ignored;
+        }
+
+        // Mixed values are all switched on
+        let value = match current_value {
+            Some(PropertyValue::Boolean(b)) => *b,
+            None => false,
+            _ => return EventResult::Ignored,
+        };
+
+        // Other keys are ignored so PropertiesPanel can handle navigation
+        match key.code {
+            KeyCode::Enter => {
+                on_change(&self.property_name, PropertyValue::Boolean(!value));
+                EventResult::Consumed
+            }
+            _ => EventResult::Ignored,
+        }
+    }
+}
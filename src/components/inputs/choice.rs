@@ -1,4 +1,4 @@
-use crate::components::inputs::PropertyInput;
+use crate::components::inputs::{MIXED, PropertyInput};
 use crate::elements::PropertyValue;
 use crate::events::{EventResult, KeyEvent};
 use crate::ui;
@@ -81,9 +81,14 @@ impl ChoiceInput {
 }
 
 impl PropertyInput for ChoiceInput {
-    fn render_line(&self, current_value: &PropertyValue, panel_active: bool) -> Line<'static> {
+    fn render_line(
+        &self,
+        current_value: Option<&PropertyValue>,
+        panel_active: bool,
+    ) -> Line<'static> {
         let value = match current_value {
-            PropertyValue::Choice(s) => s.as_str(),
+            Some(PropertyValue::Choice(s)) => s.as_str(),
+            None => MIXED,
             _ => "", // Fallback, shouldn't happen
         };
 
@@ -124,15 +129,17 @@ impl PropertyInput for ChoiceInput {
     fn handle_key_event(
         &mut self,
         key: &KeyEvent,
-        current_value: &PropertyValue,
+        current_value: Option<&PropertyValue>,
         on_change: &mut dyn FnMut(&str, PropertyValue),
     ) -> EventResult {
         if !self.is_focused {
             return EventResult::Ignored;
         }
 
+        // Mixed values start from the first option
         let value = match current_value {
-            PropertyValue::Choice(s) => s.as_str(),
+            Some(PropertyValue::Choice(s)) => Some(s.as_str()),
+            None => None,
             _ => return EventResult::Ignored,
         };
 
@@ -183,7 +190,7 @@ impl PropertyInput for ChoiceInput {
                     self.selected_index = self
                         .options
                         .iter()
-                        .position(|opt| opt == value)
+                        .position(|opt| Some(opt.as_str()) == value)
                         .unwrap_or(0);
                     self.original_index = self.selected_index;
                     EventResult::Consumed
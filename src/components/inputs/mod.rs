@@ -1,17 +1,32 @@
+#[cfg(test)]
+mod tests;
+
+mod boolean;
 mod choice;
 mod numeric;
+mod text;
 
+pub use boolean::BooleanInput;
 pub use choice::ChoiceInput;
 pub use numeric::NumericInput;
+pub use text::TextInput;
 
 use crate::elements::PropertyValue;
 use crate::events::{EventResult, KeyEvent};
 use ratatui::text::Line;
 
-/// Trait for property input components
+/// Shown instead of the value when the selected elements have different values
+const MIXED: &str = "mixed";
+
+/// Trait for property input components. The current value is `None` when several
+/// elements are selected and their values differ.
 pub trait PropertyInput {
     /// Render this input as a line with the current value
-    fn render_line(&self, current_value: &PropertyValue, panel_active: bool) -> Line<'static>;
+    fn render_line(
+        &self,
+        current_value: Option<&PropertyValue>,
+        panel_active: bool,
+    ) -> Line<'static>;
 
     /// Set focus state
     fn set_focused(&mut self, focused: bool);
@@ -27,7 +42,7 @@ pub trait PropertyInput {
     fn handle_key_event(
         &mut self,
         key: &KeyEvent,
-        current_value: &PropertyValue,
+        current_value: Option<&PropertyValue>,
         on_change: &mut dyn FnMut(&str, PropertyValue),
     ) -> EventResult;
 }
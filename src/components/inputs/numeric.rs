@@ -1,4 +1,4 @@
-use crate::components::inputs::PropertyInput;
+use crate::components::inputs::{MIXED, PropertyInput};
 use crate::elements::PropertyValue;
 use crate::events::{EventResult, KeyEvent};
 use crate::ui;
@@ -8,8 +8,8 @@ use ratatui::text::Line;
 /// A numeric input component with editing capabilities
 pub struct NumericInput {
     // Constraints
-    min: u16,
-    max: u16,
+    min: i32,
+    max: i32,
 
     // Property identification
     property_name: String,
@@ -25,8 +25,8 @@ impl NumericInput {
     pub fn new(
         property_name: impl Into<String>,
         label: impl Into<String>,
-        min: u16,
-        max: u16,
+        min: i32,
+        max: i32,
     ) -> Self {
         Self {
             min,
@@ -40,9 +40,9 @@ impl NumericInput {
     }
 
     /// Adjust value while editing with given operation, returns the new value if changed
-    fn adjust_value(&mut self, op: impl Fn(u16) -> u16) -> Option<u16> {
+    fn adjust_value(&mut self, op: impl Fn(i32) -> i32) -> Option<i32> {
         // Parse buffer, fallback to min if empty
-        let base_value = self.edit_buffer.parse::<u16>().unwrap_or(self.min);
+        let base_value = self.edit_buffer.parse::<i32>().unwrap_or(self.min);
         let new_value = op(base_value);
 
         if new_value != base_value {
@@ -54,20 +54,24 @@ impl NumericInput {
     }
 
     /// Increment value by 1 while editing, returns the new value if changed
-    fn increment(&mut self) -> Option<u16> {
+    fn increment(&mut self) -> Option<i32> {
         let max = self.max;
         self.adjust_value(|v| v.saturating_add(1).min(max))
     }
 
     /// Decrement value by 1 while editing, returns the new value if changed
-    fn decrement(&mut self) -> Option<u16> {
+    fn decrement(&mut self) -> Option<i32> {
         let min = self.min;
         self.adjust_value(|v| v.saturating_sub(1).max(min))
     }
 
-    /// Append a digit character to the buffer
+    /// Append a digit character to the buffer.
+    /// A leading minus sign is accepted when the field allows negative values.
     fn insert_char(&mut self, c: char) {
-        if self.edit_buffer.len() < 5 && c.is_ascii_digit() {
+        let digits = self.edit_buffer.trim_start_matches('-').len();
+        let accept_digit = c.is_ascii_digit() && digits < 5;
+        let accept_sign = c == '-' && self.edit_buffer.is_empty() && self.min < 0;
+        if accept_digit || accept_sign {
             self.edit_buffer.push(c);
         }
     }
@@ -84,18 +88,21 @@ impl NumericInput {
 }
 
 impl PropertyInput for NumericInput {
-    fn render_line(&self, current_value: &PropertyValue, panel_active: bool) -> Line<'static> {
-        let value = match current_value {
-            PropertyValue::Numeric(n) => *n,
-            _ => 0, // Fallback, shouldn't happen
-        };
-
+    fn render_line(
+        &self,
+        current_value: Option<&PropertyValue>,
+        panel_active: bool,
+    ) -> Line<'static> {
         let styles = ui::input_styles(self.is_editing, self.is_focused, panel_active);
 
         let display_value = if self.is_editing {
             format!("{}▎", self.edit_buffer) // Cursor at end
         } else {
-            value.to_string()
+            match current_value {
+                Some(PropertyValue::Numeric(n)) => n.to_string(),
+                None => MIXED.to_string(),
+                _ => "0".to_string(), // Fallback, shouldn't happen
+            }
         };
 
         ui::input_line(&self.label, display_value, styles)
@@ -119,15 +126,17 @@ impl PropertyInput for NumericInput {
     fn handle_key_event(
         &mut self,
         key: &KeyEvent,
-        current_value: &PropertyValue,
+        current_value: Option<&PropertyValue>,
         on_change: &mut dyn FnMut(&str, PropertyValue),
     ) -> EventResult {
         if !self.is_focused {
             return EventResult::Ignored;
         }
 
+        // Mixed values start editing from an empty buffer
         let value = match current_value {
-            PropertyValue::Numeric(n) => *n,
+            Some(PropertyValue::Numeric(n)) => Some(*n),
+            None => None,
             _ => return EventResult::Ignored,
         };
 
@@ -140,14 +149,14 @@ impl PropertyInput for NumericInput {
                 }
                 KeyCode::Enter => {
                     // Parse, clamp, and call callback
-                    if let Ok(new_value) = self.edit_buffer.parse::<u16>() {
+                    if let Ok(new_value) = self.edit_buffer.parse::<i32>() {
                         let clamped = new_value.clamp(self.min, self.max);
                         on_change(&self.property_name, PropertyValue::Numeric(clamped));
                     }
                     self.exit_editing();
                     EventResult::Consumed
                 }
-                KeyCode::Char(c) if c.is_ascii_digit() => {
+                KeyCode::Char(c) if c.is_ascii_digit() || c == '-' => {
                     self.insert_char(c);
                     EventResult::Consumed
                 }
@@ -180,7 +189,7 @@ impl PropertyInput for NumericInput {
             match key.code {
                 KeyCode::Enter => {
                     self.is_editing = true;
-                    self.edit_buffer = value.to_string();
+                    self.edit_buffer = value.map(|n| n.to_string()).unwrap_or_default();
                     EventResult::Consumed
                 }
                 _ => EventResult::Ignored,
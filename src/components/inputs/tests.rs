@@ -0,0 +1,129 @@
+use super::{BooleanInput, PropertyInput, TextInput};
+use crate::elements::PropertyValue;
+use crate::events::{EventResult, KeyEvent};
+use crossterm::event::{KeyCode, KeyModifiers};
+
+fn key(code: KeyCode) -> KeyEvent {
+    KeyEvent {
+        code,
+        modifiers: KeyModifiers::NONE,
+    }
+}
+
+/// Send keys to an input, returning the values it reported
+fn send(
+    input: &mut dyn PropertyInput,
+    value: Option<&PropertyValue>,
+    keys: &[KeyCode],
+) -> Vec<PropertyValue> {
+    let mut changes = Vec::new();
+    for &code in keys {
+        input.handle_key_event(&key(code), value, &mut |_, new_value| {
+            changes.push(new_value)
+        });
+    }
+    changes
+}
+
+fn text(value: &str) -> PropertyValue {
+    PropertyValue::Text(value.to_string())
+}
+
+#[test]
+fn test_text_input_edits_at_cursor() {
+    let mut input = TextInput::new("name", "name", 10);
+    input.set_focused(true);
+    let value = text("Box");
+
+    use KeyCode::*;
+    let changes = send(
+        &mut input,
+        Some(&value),
+        &[
+            Enter,
+            Home,
+            Char('A'),
+            End,
+            Left,
+            Backspace,
+            Delete,
+            Char('!'),
+            Enter,
+        ],
+    );
+
+    assert_eq!(changes, vec![text("AB!")]);
+    assert!(!input.is_editing());
+}
+
+#[test]
+fn test_text_input_max_length_and_cancel() {
+    let mut input = TextInput::new("name", "name", 4);
+    input.set_focused(true);
+    let value = text("abc");
+
+    use KeyCode::*;
+    let changes = send(
+        &mut input,
+        Some(&value),
+        &[Enter, Char('d'), Char('e'), Enter],
+    );
+    assert_eq!(changes, vec![text("abcd")]);
+
+    // Esc drops the edit; an unchanged value isn't reported
+    let changes = send(
+        &mut input,
+        Some(&value),
+        &[Enter, Backspace, Esc, Enter, Enter],
+    );
+    assert!(changes.is_empty());
+
+    // Unfocused inputs ignore keys
+    input.set_focused(false);
+    assert_eq!(
+        input.handle_key_event(&key(Enter), Some(&value), &mut |_, _| {}),
+        EventResult::Ignored
+    );
+}
+
+#[test]
+fn test_boolean_input_toggles() {
+    let mut input = BooleanInput::new("auto_grow", "auto-grow");
+    input.set_focused(true);
+
+    let changes = send(
+        &mut input,
+        Some(&PropertyValue::Boolean(false)),
+        &[KeyCode::Enter],
+    );
+    assert_eq!(changes, vec![PropertyValue::Boolean(true)]);
+    assert!(!input.is_editing());
+
+    // Navigation keys are left to the panel
+    assert_eq!(
+        input.handle_key_event(
+            &key(KeyCode::Down),
+            Some(&PropertyValue::Boolean(true)),
+            &mut |_, _| {}
+        ),
+        EventResult::Ignored
+    );
+}
+
+#[test]
+fn test_mixed_values() {
+    use KeyCode::*;
+
+    // Editing starts empty, and confirming without typing changes nothing
+    let mut input = TextInput::new("name", "name", 10);
+    input.set_focused(true);
+    assert!(send(&mut input, None, &[Enter, Enter]).is_empty());
+    let changes = send(&mut input, None, &[Enter, Char('x'), Enter]);
+    assert_eq!(changes, vec![text("x")]);
+
+    // Toggling a mixed boolean switches everything on
+    let mut input = BooleanInput::new("auto_grow", "auto-grow");
+    input.set_focused(true);
+    let changes = send(&mut input, None, &[Enter]);
+    assert_eq!(changes, vec![PropertyValue::Boolean(true)]);
+}
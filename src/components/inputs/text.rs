@@ -0,0 +1,187 @@
+use crate::components::inputs::{MIXED, PropertyInput};
+use crate::elements::PropertyValue;
+use crate::events::{EventResult, KeyEvent};
+use crate::ui;
+use crossterm::event::KeyCode;
+use ratatui::text::Line;
+
+/// Characters of the value shown at once; longer values scroll with the cursor
+const VISIBLE_WIDTH: usize = 18;
+
+/// A single-line text input component with a movable cursor
+pub struct TextInput {
+    // Constraints
+    max_length: usize,
+
+    // Property identification
+    property_name: String,
+    label: String,
+
+    // Edit state
+    is_focused: bool,
+    is_editing: bool,
+    edit_buffer: Vec<char>,
+    cursor: usize,
+}
+
+impl TextInput {
+    pub fn new(
+        property_name: impl Into<String>,
+        label: impl Into<String>,
+        max_length: usize,
+    ) -> Self {
+        Self {
+            max_length,
+            property_name: property_name.into(),
+            label: label.into(),
+            is_focused: false,
+            is_editing: false,
+            edit_buffer: Vec::new(),
+            cursor: 0,
+        }
+    }
+
+    /// Insert a character at the cursor, unless the value is at its maximum length
+    fn insert_char(&mut self, c: char) {
+        if self.edit_buffer.len() < self.max_length {
+            self.edit_buffer.insert(self.cursor, c);
+            self.cursor += 1;
+        }
+    }
+
+    /// Delete the character before the cursor (backspace)
+    fn delete_before_cursor(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            self.edit_buffer.remove(self.cursor);
+        }
+    }
+
+    /// Delete the character under the cursor (delete)
+    fn delete_at_cursor(&mut self) {
+        if self.cursor < self.edit_buffer.len() {
+            self.edit_buffer.remove(self.cursor);
+        }
+    }
+
+    fn exit_editing(&mut self) {
+        self.is_editing = false;
+        self.edit_buffer.clear();
+        self.cursor = 0;
+    }
+
+    /// The visible part of the edit buffer with the cursor drawn in it
+    fn display_buffer(&self) -> String {
+        let start = (self.cursor + 1).saturating_sub(VISIBLE_WIDTH);
+        let end = (start + VISIBLE_WIDTH).min(self.edit_buffer.len());
+        let mut display: String = self.edit_buffer[start..self.cursor].iter().collect();
+        display.push('▎');
+        display.extend(&self.edit_buffer[self.cursor..end]);
+        display
+    }
+}
+
+impl PropertyInput for TextInput {
+    fn render_line(
+        &self,
+        current_value: Option<&PropertyValue>,
+        panel_active: bool,
+    ) -> Line<'static> {
+        let value = match current_value {
+            Some(PropertyValue::Text(s)) => s.as_str(),
+            None => MIXED,
+            _ => "", // Fallback, shouldn't happen
+        };
+
+        let styles = ui::input_styles(self.is_editing, self.is_focused, panel_active);
+
+        let display_value = if self.is_editing {
+            self.display_buffer()
+        } else if value.chars().count() > VISIBLE_WIDTH {
+            let shown: String = value.chars().take(VISIBLE_WIDTH - 1).collect();
+            format!("{}…", shown)
+        } else {
+            value.to_string()
+        };
+
+        ui::input_line(&self.label, display_value, styles)
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.is_focused = focused;
+        if !focused {
+            self.exit_editing();
+        }
+    }
+
+    fn is_editing(&self) -> bool {
+        self.is_editing
+    }
+
+    fn property_name(&self) -> &str {
+        &self.property_name
+    }
+
+    fn handle_key_event(
+        &mut self,
+        key: &KeyEvent,
+        current_value: Option<&PropertyValue>,
+        on_change: &mut dyn FnMut(&str, PropertyValue),
+    ) -> EventResult {
+        if !self.is_focused {
+            return EventResult::Ignored;
+        }
+
+        // Mixed values start editing from an empty buffer
+        let value = match current_value {
+            Some(PropertyValue::Text(s)) => Some(s.as_str()),
+            None => None,
+            _ => return EventResult::Ignored,
+        };
+
+        if self.is_editing {
+            // Handle editing mode
+            match key.code {
+                KeyCode::Esc => self.exit_editing(),
+                KeyCode::Enter => {
+                    let new_value: String = self.edit_buffer.iter().collect();
+                    let changed = match value {
+                        Some(value) => new_value != value,
+                        None => !new_value.is_empty(),
+                    };
+                    if changed {
+                        on_change(&self.property_name, PropertyValue::Text(new_value));
+                    }
+                    self.exit_editing();
+                }
+                KeyCode::Char(c) => self.insert_char(c),
+                KeyCode::Backspace => self.delete_before_cursor(),
+                KeyCode::Delete => self.delete_at_cursor(),
+                KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+                KeyCode::Right => self.cursor = (self.cursor + 1).min(self.edit_buffer.len()),
+                KeyCode::Home => self.cursor = 0,
+                KeyCode::End => self.cursor = self.edit_buffer.len(),
+                _ => {
+                    // Consume all other keys while editing to prevent leaking to parent handlers
+                }
+            }
+            EventResult::Consumed
+        } else {
+            // When focused but not editing, only handle Enter to start editing
+            // All other keys (Up/Down/etc) are ignored so PropertiesPanel can handle navigation
+            match key.code {
+                KeyCode::Enter => {
+                    self.is_editing = true;
+                    self.edit_buffer = value
+                        .unwrap_or_default()
+                        .chars()
+                        .take(self.max_length)
+                        .collect();
+                    self.cursor = self.edit_buffer.len();
+                    EventResult::Consumed
+                }
+                _ => EventResult::Ignored,
+            }
+        }
+    }
+}
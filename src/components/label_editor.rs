@@ -0,0 +1,118 @@
+use crate::components::table_editor::OverlayMaps;
+use crate::elements::{Element, PropertyValue};
+use crate::events::{EventResult, KeyEvent};
+use crate::state::{AppState, EditKind};
+use crossterm::event::KeyCode;
+use std::collections::HashMap;
+
+/// Build overlays for Edit Label mode (box interior highlight + cursor cell)
+pub fn build_edit_label_overlays(state: &AppState) -> OverlayMaps {
+    let mut highlight_map = HashMap::new();
+    let mut content_map = HashMap::new();
+
+    let Some(edit_state) = &state.editing_label else {
+        return (highlight_map, content_map);
+    };
+
+    let Some(Element::Rectangle(rect)) = state.canvas.get_element(edit_state.rect_id) else {
+        return (highlight_map, content_map);
+    };
+
+    // Highlight everything inside the border
+    let right = rect.start.x + rect.width as i32;
+    let bottom = rect.start.y + rect.height as i32;
+    for y in (rect.start.y + 1)..bottom {
+        for x in (rect.start.x + 1)..right {
+            highlight_map.insert((x, y), true);
+        }
+    }
+
+    // Show the cursor over the character it sits on
+    if let Some(pos) = rect.label_cursor_position(edit_state.cursor_pos) {
+        let ch = rect.label.chars().nth(edit_state.cursor_pos).unwrap_or(' ');
+        content_map.insert((pos.x, pos.y), (ch, true));
+    }
+
+    (highlight_map, content_map)
+}
+
+/// Try to enter Edit Label mode if a single rectangle is selected
+pub fn try_enter_edit_label_mode(state: &mut AppState) -> EventResult {
+    let selected_ids = state.get_selected_element_ids();
+    if selected_ids.len() != 1 {
+        return EventResult::Ignored;
+    }
+
+    let element_id = selected_ids[0];
+    let Some(Element::Rectangle(rect)) = state.canvas.get_element(element_id) else {
+        return EventResult::Ignored;
+    };
+    let label = rect.label.clone();
+    state.enter_edit_label_mode(element_id, &label);
+    EventResult::Consumed
+}
+
+/// Handle keyboard events in Edit Label mode
+pub fn handle_edit_label_key(state: &mut AppState, key_event: &KeyEvent) -> EventResult {
+    let Some(edit_state) = state.editing_label.as_ref() else {
+        return EventResult::Ignored;
+    };
+    let rect_id = edit_state.rect_id;
+    let cursor_pos = edit_state.cursor_pos;
+
+    let Some(Element::Rectangle(rect)) = state.canvas.get_element(rect_id) else {
+        // Rectangle was removed
+        state.exit_edit_label_mode();
+        return EventResult::Consumed;
+    };
+    let mut label: Vec<char> = rect.label.chars().collect();
+
+    match key_event.code {
+        KeyCode::Esc => {
+            // Cancel editing, restore original label
+            let original = edit_state.original_label.clone();
+            set_label(state, rect_id, original);
+            state.exit_edit_label_mode();
+        }
+        KeyCode::Enter => {
+            // Finish editing (label already saved in real-time)
+            state.exit_edit_label_mode();
+        }
+        KeyCode::Char(c) => {
+            label.insert(cursor_pos, c);
+            set_label(state, rect_id, label.into_iter().collect());
+            set_cursor(state, cursor_pos + 1);
+        }
+        KeyCode::Backspace if cursor_pos > 0 => {
+            label.remove(cursor_pos - 1);
+            set_label(state, rect_id, label.into_iter().collect());
+            set_cursor(state, cursor_pos - 1);
+        }
+        KeyCode::Delete if cursor_pos < label.len() => {
+            label.remove(cursor_pos);
+            set_label(state, rect_id, label.into_iter().collect());
+        }
+        KeyCode::Left => set_cursor(state, cursor_pos.saturating_sub(1)),
+        KeyCode::Right => set_cursor(state, (cursor_pos + 1).min(label.len())),
+        KeyCode::Home => set_cursor(state, 0),
+        KeyCode::End => set_cursor(state, label.len()),
+        _ => {}
+    }
+
+    EventResult::Consumed
+}
+
+/// Update the label, growing the box if needed; keystrokes coalesce into one undo step
+fn set_label(state: &mut AppState, rect_id: usize, label: String) {
+    state.history.set_pending_kind(EditKind::Label { rect_id });
+    if let Some(rect) = state.canvas.get_element_mut(rect_id) {
+        let _ = rect.set_property("label", PropertyValue::Text(label));
+    }
+    state.canvas.reroute_connectors_to(rect_id);
+}
+
+fn set_cursor(state: &mut AppState, pos: usize) {
+    if let Some(edit_state) = state.editing_label.as_mut() {
+        edit_state.cursor_pos = pos;
+    }
+}
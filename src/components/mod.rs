@@ -1,15 +1,19 @@
 pub mod canvas;
+pub mod confirm;
 pub mod help;
 mod help_line;
 pub mod inputs;
+pub mod label_editor;
 pub mod panels;
 pub mod statusbar;
 pub mod table_editor;
+pub mod vertex_editor;
 
 pub use canvas::CanvasComponent;
+pub use confirm::ConfirmModal;
 pub use help::HelpModal;
-pub use inputs::{ChoiceInput, NumericInput};
-pub use panels::{PropertiesPanel, ToolsPanel};
+pub use inputs::{BooleanInput, ChoiceInput, NumericInput, TextInput};
+pub use panels::{LayersPanel, OutlinePanel, PropertiesPanel, ToolsPanel};
 pub use statusbar::StatusBar;
 
 // Re-export the PropertyInput trait for use in other modules
@@ -0,0 +1,268 @@
+use crate::components::{Component, PropertyInput, TextInput};
+use crate::elements::PropertyValue;
+use crate::events::{EventHandler, EventResult, KeyEvent, MouseEvent};
+use crate::keymap::{Action, Context};
+use crate::state::{AppState, Layer};
+use crate::types::Panel;
+use crate::ui;
+use crate::utils::ModalArea;
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+/// Panel dimensions for layers panel
+const LAYERS_PANEL_WIDTH: u16 = 34;
+
+/// Characters of a layer name shown in its row
+const NAME_WIDTH: usize = 14;
+
+/// Longest layer name accepted when renaming
+const MAX_LAYER_NAME_LENGTH: usize = 30;
+
+/// Fixed content lines in layers panel:
+/// - 1 blank line at top
+/// - 1 blank line after the layer rows
+/// - 2 lines for shortcut helpers at bottom
+const LAYERS_FIXED_LINES: u16 = 4;
+
+pub struct LayersPanel {
+    // ID of the layer the panel's keys act on
+    focused_layer: Option<usize>,
+    // Name input while renaming the focused layer
+    rename_input: Option<TextInput>,
+}
+
+impl LayersPanel {
+    pub fn new() -> Self {
+        Self {
+            focused_layer: None,
+            rename_input: None,
+        }
+    }
+
+    /// The focused layer, falling back to the active one if it was removed (undo)
+    fn focused(&self, state: &AppState) -> usize {
+        self.focused_layer
+            .filter(|&id| state.canvas.get_layer(id).is_some())
+            .unwrap_or_else(|| state.canvas.active_layer())
+    }
+
+    /// Layers in display order: the top layer first
+    fn rows(state: &AppState) -> Vec<&Layer> {
+        state.canvas.layers().iter().rev().collect()
+    }
+
+    /// Move focus one row up or down
+    fn move_focus(&mut self, state: &AppState, up: bool) {
+        let rows = Self::rows(state);
+        let focused = self.focused(state);
+        let Some(row) = rows.iter().position(|l| l.id == focused) else {
+            return;
+        };
+        let row = if up {
+            row.saturating_sub(1)
+        } else {
+            (row + 1).min(rows.len() - 1)
+        };
+        self.focused_layer = Some(rows[row].id);
+    }
+
+    /// Start renaming the focused layer
+    fn start_rename(&mut self, state: &AppState) {
+        let Some(layer) = state.canvas.get_layer(self.focused(state)) else {
+            return;
+        };
+        let mut input = TextInput::new("name", "name", MAX_LAYER_NAME_LENGTH);
+        input.set_focused(true);
+        let enter = KeyEvent {
+            code: KeyCode::Enter,
+            modifiers: KeyModifiers::NONE,
+        };
+        let name = PropertyValue::Text(layer.name.clone());
+        input.handle_key_event(&enter, Some(&name), &mut |_, _| {});
+        self.rename_input = Some(input);
+    }
+
+    /// Forward a key to the rename input, applying the new name when it is committed
+    fn forward_to_rename(&mut self, state: &mut AppState, key: &KeyEvent) -> EventResult {
+        let id = self.focused(state);
+        let Some(input) = &mut self.rename_input else {
+            return EventResult::Ignored;
+        };
+        let name = state
+            .canvas
+            .get_layer(id)
+            .map(|l| PropertyValue::Text(l.name.clone()));
+        let result = input.handle_key_event(key, name.as_ref(), &mut |_, value| {
+            if let PropertyValue::Text(name) = value {
+                state.rename_layer(id, name);
+            }
+        });
+        if !input.is_editing() {
+            self.rename_input = None;
+        }
+        result
+    }
+
+    /// Calculate modal position at bottom-right corner
+    fn calculate_modal_area(canvas_area: Rect, layer_count: usize) -> ModalArea {
+        // Add 2 for top and bottom borders
+        let total_height = LAYERS_FIXED_LINES + layer_count as u16 + 2;
+        ModalArea::bottom_right(canvas_area, LAYERS_PANEL_WIDTH, total_height)
+    }
+
+    fn render_layer_line(
+        layer: &Layer,
+        is_active: bool,
+        is_focused: bool,
+        panel_active: bool,
+    ) -> Line<'static> {
+        let marker = if is_active { "▸ " } else { "  " };
+        let name = if layer.name.chars().count() > NAME_WIDTH {
+            let shown: String = layer.name.chars().take(NAME_WIDTH - 1).collect();
+            format!("{}…", shown)
+        } else {
+            layer.name.clone()
+        };
+        let flags = match (layer.visible, layer.locked) {
+            (true, false) => "",
+            (false, false) => "hidden",
+            (true, true) => "locked",
+            (false, true) => "hidden locked",
+        };
+
+        let styles = ui::input_styles(false, is_focused, panel_active);
+        let text = format!(" {}{:<width$} ", marker, name, width = NAME_WIDTH);
+        // -2 for borders
+        let padding =
+            (LAYERS_PANEL_WIDTH as usize - 2).saturating_sub(text.chars().count() + flags.len());
+        Line::from(vec![
+            Span::styled(text, styles.label),
+            Span::styled(" ".repeat(padding), styles.background),
+            Span::styled(flags, ui::muted_style().patch(styles.background)),
+        ])
+    }
+
+    /// Shortcut helper line, e.g. `New: n  Rename: r`
+    fn helper_line(state: &AppState, hints: &[(&str, Action)]) -> Line<'static> {
+        let mut spans = Vec::new();
+        for &(label, action) in hints {
+            if let Some(key) = state.keymap.key(Context::Layers, action) {
+                spans.push(ui::padded_span(format!("{}: ", label), ui::muted_style()));
+                spans.push(Span::styled(key.to_string(), ui::muted_style()));
+            }
+        }
+        Line::from(spans)
+    }
+}
+
+impl EventHandler for LayersPanel {
+    type State = AppState;
+
+    fn handle_key_event(&mut self, state: &mut AppState, key: &KeyEvent) -> EventResult {
+        // Only handle when layers panel is visible and active
+        if !state.show_layers || state.active_panel != Panel::Layers {
+            self.rename_input = None;
+            return EventResult::Ignored;
+        }
+
+        if self.rename_input.is_some() {
+            return self.forward_to_rename(state, key);
+        }
+
+        let focused = self.focused(state);
+        match state.keymap.resolve(Context::Layers, key) {
+            Some(Action::Up) => self.move_focus(state, true),
+            Some(Action::Down) => self.move_focus(state, false),
+            Some(Action::Raise) => state.move_layer(focused, true),
+            Some(Action::Lower) => state.move_layer(focused, false),
+            Some(Action::Submit) => state.set_active_layer(focused),
+            Some(Action::NewLayer) => self.focused_layer = Some(state.add_layer(Some(focused))),
+            Some(Action::Edit) => self.start_rename(state),
+            Some(Action::ToggleVisible) => state.toggle_layer_visible(focused),
+            Some(Action::ToggleLock) => state.toggle_layer_locked(focused),
+            Some(Action::MoveToLayer) => state.move_selected_to_layer(focused),
+            _ => return EventResult::Ignored,
+        }
+        EventResult::Consumed
+    }
+
+    fn handle_mouse_down(&mut self, state: &mut AppState, mouse_event: &MouseEvent) -> EventResult {
+        if !state.show_layers {
+            return EventResult::Ignored;
+        }
+
+        let rows = Self::rows(state);
+        let area = Self::calculate_modal_area(state.layout.canvas, rows.len());
+        if !area.contains(mouse_event.column, mouse_event.row) {
+            return EventResult::Ignored;
+        }
+
+        // Activate layers panel when clicked, focusing the clicked layer
+        let row = area.content_relative_y(mouse_event.row) as usize;
+        if let Some(layer) = rows.get(row) {
+            self.focused_layer = Some(layer.id);
+        }
+        state.switch_panel(Panel::Layers);
+        EventResult::Consumed
+    }
+}
+
+impl Component for LayersPanel {
+    fn draw(&mut self, state: &AppState, frame: &mut Frame) {
+        if !state.show_layers {
+            return;
+        }
+
+        let rows = Self::rows(state);
+        let area = Self::calculate_modal_area(state.layout.canvas, rows.len());
+        area.clear(frame);
+
+        let panel_active = state.active_panel == Panel::Layers;
+        let focused = self.focused(state);
+        let active = state.canvas.active_layer();
+
+        let mut lines = vec![ui::blank_line()];
+        for layer in rows {
+            match &self.rename_input {
+                Some(input) if layer.id == focused => {
+                    let name = PropertyValue::Text(layer.name.clone());
+                    lines.push(input.render_line(Some(&name), panel_active));
+                }
+                _ => lines.push(Self::render_layer_line(
+                    layer,
+                    layer.id == active,
+                    layer.id == focused,
+                    panel_active,
+                )),
+            }
+        }
+        lines.push(ui::blank_line());
+
+        // Add shortcut helpers at the bottom
+        lines.push(Self::helper_line(
+            state,
+            &[
+                ("New", Action::NewLayer),
+                ("Use", Action::Submit),
+                ("Move to", Action::MoveToLayer),
+            ],
+        ));
+        lines.push(Self::helper_line(
+            state,
+            &[
+                ("Hide", Action::ToggleVisible),
+                ("Lock", Action::ToggleLock),
+                ("Rename", Action::Edit),
+            ],
+        ));
+
+        let block = ui::panel_block(" Layers ", panel_active);
+        let widget = Paragraph::new(lines).block(block);
+        frame.render_widget(widget, area.rect());
+    }
+}
@@ -1,5 +1,9 @@
+mod layers;
+mod outline;
 mod properties;
 mod tools;
 
+pub use layers::LayersPanel;
+pub use outline::OutlinePanel;
 pub use properties::PropertiesPanel;
 pub use tools::ToolsPanel;
@@ -0,0 +1,425 @@
+use crate::components::{Component, PropertyInput, TextInput};
+use crate::elements::{Element, MAX_NAME_LENGTH, PropertyValue};
+use crate::events::{EventHandler, EventResult, KeyEvent, MouseEvent, MouseEventKind};
+use crate::keymap::{Action, Context};
+use crate::state::AppState;
+use crate::types::Panel;
+use crate::ui;
+use crate::utils::ModalArea;
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+/// Panel dimensions for outline panel
+const OUTLINE_PANEL_WIDTH: u16 = 42;
+
+/// Characters of an element name shown in its row
+const NAME_WIDTH: usize = 24;
+
+/// Most element rows shown at once; longer lists scroll
+const MAX_VISIBLE_ROWS: u16 = 15;
+
+/// Rows scrolled per mouse wheel notch
+const SCROLL_STEP: usize = 3;
+
+/// Fixed content lines in outline panel:
+/// - 1 blank line at top
+/// - 1 line for the filter
+/// - 1 blank line after the filter
+/// - 1 blank line after the element rows
+/// - 1 line for shortcut helper at bottom
+const OUTLINE_FIXED_LINES: u16 = 5;
+
+/// Content line of the first element row
+const FIRST_ROW_LINE: usize = 2;
+
+pub struct OutlinePanel {
+    // Typed text; only elements whose name or type contains it are listed
+    filter: String,
+    // ID of the element the panel's keys act on
+    focused_element: Option<usize>,
+    // First listed row that is shown
+    scroll_offset: usize,
+    // Name input while renaming the focused element
+    rename_input: Option<TextInput>,
+}
+
+impl OutlinePanel {
+    pub fn new() -> Self {
+        Self {
+            filter: String::new(),
+            focused_element: None,
+            scroll_offset: 0,
+            rename_input: None,
+        }
+    }
+
+    /// Elements matching the filter, in drawing order
+    fn rows<'a>(&self, state: &'a AppState) -> Vec<&'a Element> {
+        let filter = self.filter.to_lowercase();
+        state
+            .canvas
+            .elements()
+            .iter()
+            .filter(|e| {
+                e.name().to_lowercase().contains(&filter)
+                    || e.type_name().to_lowercase().contains(&filter)
+            })
+            .collect()
+    }
+
+    /// Row of the focused element, falling back to the first row if it is filtered
+    /// out or was deleted
+    fn focused_row(&self, rows: &[&Element]) -> Option<usize> {
+        if rows.is_empty() {
+            return None;
+        }
+        let row = self
+            .focused_element
+            .and_then(|id| rows.iter().position(|e| e.id() == id));
+        Some(row.unwrap_or_default())
+    }
+
+    /// How many element rows fit in the canvas area
+    fn visible_rows(canvas_area: Rect) -> usize {
+        // Leave room for the panel borders and margins
+        let available = canvas_area.height.saturating_sub(OUTLINE_FIXED_LINES + 4);
+        available.clamp(1, MAX_VISIBLE_ROWS) as usize
+    }
+
+    /// Move focus by `delta` rows, scrolling to keep it in view
+    fn move_focus(&mut self, state: &AppState, delta: isize) {
+        let rows = self.rows(state);
+        let Some(row) = self.focused_row(&rows) else {
+            return;
+        };
+        let row = row.saturating_add_signed(delta).min(rows.len() - 1);
+        self.focused_element = Some(rows[row].id());
+
+        let visible = Self::visible_rows(state.layout.canvas);
+        if row < self.scroll_offset {
+            self.scroll_offset = row;
+        } else if row >= self.scroll_offset + visible {
+            self.scroll_offset = row + 1 - visible;
+        }
+    }
+
+    /// The filter changed: start from the top of the new list
+    fn refilter(&mut self) {
+        self.scroll_offset = 0;
+    }
+
+    /// ID of the focused element, if any is listed
+    fn focused(&self, state: &AppState) -> Option<usize> {
+        let rows = self.rows(state);
+        self.focused_row(&rows).map(|row| rows[row].id())
+    }
+
+    /// Start renaming the focused element
+    fn start_rename(&mut self, state: &AppState) {
+        let Some(element) = self
+            .focused(state)
+            .and_then(|id| state.canvas.get_element(id))
+        else {
+            return;
+        };
+        let mut input = TextInput::new("name", "name", MAX_NAME_LENGTH);
+        input.set_focused(true);
+        let enter = KeyEvent {
+            code: KeyCode::Enter,
+            modifiers: KeyModifiers::NONE,
+        };
+        let name = PropertyValue::Text(element.name().to_string());
+        input.handle_key_event(&enter, Some(&name), &mut |_, _| {});
+        self.focused_element = Some(element.id());
+        self.rename_input = Some(input);
+    }
+
+    /// Forward a key to the rename input, applying the new name when it is committed
+    fn forward_to_rename(&mut self, state: &mut AppState, key: &KeyEvent) -> EventResult {
+        let Some(id) = self.focused(state) else {
+            self.rename_input = None;
+            return EventResult::Ignored;
+        };
+        let Some(input) = &mut self.rename_input else {
+            return EventResult::Ignored;
+        };
+        let name = state
+            .canvas
+            .get_element(id)
+            .map(|e| PropertyValue::Text(e.name().to_string()));
+        let result = input.handle_key_event(key, name.as_ref(), &mut |_, value| {
+            if let PropertyValue::Text(name) = value
+                && let Err(e) = state.rename_element(id, &name)
+            {
+                state.file.status_message = Some(format!("Error: {}", e));
+            }
+        });
+        if !input.is_editing() {
+            self.rename_input = None;
+        }
+        result
+    }
+
+    /// Calculate modal position at top-right corner
+    fn calculate_modal_area(canvas_area: Rect, row_count: usize) -> ModalArea {
+        let rows = row_count.clamp(1, Self::visible_rows(canvas_area)) as u16;
+        // Add 2 for top and bottom borders
+        let total_height = OUTLINE_FIXED_LINES + rows + 2;
+        ModalArea::top_right(canvas_area, OUTLINE_PANEL_WIDTH, total_height)
+    }
+
+    fn render_filter_line(&self, panel_active: bool) -> Line<'static> {
+        if self.filter.is_empty() && !panel_active {
+            return Line::from(ui::padded_span("Type to filter", ui::muted_style()));
+        }
+        let cursor = if panel_active && self.rename_input.is_none() {
+            "▎"
+        } else {
+            ""
+        };
+        Line::from(vec![
+            Span::styled(format!("{}Filter: ", ui::PADDING_LEFT), ui::label_style()),
+            Span::raw(format!("{}{}", self.filter, cursor)),
+        ])
+    }
+
+    fn render_element_line(
+        state: &AppState,
+        element: &Element,
+        is_focused: bool,
+        panel_active: bool,
+    ) -> Line<'static> {
+        let is_selected = state.selection_state.selected_ids.contains(&element.id());
+        let marker = if is_selected { "● " } else { "  " };
+        let name = if element.name().chars().count() > NAME_WIDTH {
+            let shown: String = element.name().chars().take(NAME_WIDTH - 1).collect();
+            format!("{}…", shown)
+        } else {
+            element.name().to_string()
+        };
+
+        let styles = ui::input_styles(false, is_focused, panel_active);
+        let name_style = if !state.canvas.is_selectable(element) {
+            // Hidden or locked layer
+            ui::muted_style().patch(styles.background)
+        } else if is_selected {
+            Style::default().fg(Color::Yellow).patch(styles.background)
+        } else {
+            styles.value
+        };
+        let text = format!(" {}{:<width$} ", marker, name, width = NAME_WIDTH);
+        let type_name = element.type_name();
+        // -2 for borders, -1 for the space after the type
+        let padding = (OUTLINE_PANEL_WIDTH as usize - 3)
+            .saturating_sub(text.chars().count() + type_name.len());
+        Line::from(vec![
+            Span::styled(text, name_style),
+            Span::styled(" ".repeat(padding), styles.background),
+            Span::styled(type_name, ui::muted_style().patch(styles.background)),
+            Span::styled(" ", styles.background),
+        ])
+    }
+
+    /// Shortcut helper line, e.g. `Jump: Enter  Add: Tab`
+    fn helper_line(state: &AppState) -> Line<'static> {
+        let hints = [
+            ("Jump", Action::Submit),
+            ("Add", Action::ToggleSelect),
+            ("Rename", Action::Edit),
+        ];
+        let mut spans = Vec::new();
+        for (label, action) in hints {
+            if let Some(key) = state.keymap.key(Context::Outline, action) {
+                spans.push(ui::padded_span(format!("{}: ", label), ui::muted_style()));
+                spans.push(Span::styled(key.to_string(), ui::muted_style()));
+            }
+        }
+        Line::from(spans)
+    }
+}
+
+impl EventHandler for OutlinePanel {
+    type State = AppState;
+
+    fn handle_key_event(&mut self, state: &mut AppState, key: &KeyEvent) -> EventResult {
+        // Only handle when outline panel is visible and active
+        if !state.show_outline || state.active_panel != Panel::Outline {
+            self.rename_input = None;
+            return EventResult::Ignored;
+        }
+
+        if self.rename_input.is_some() {
+            return self.forward_to_rename(state, key);
+        }
+
+        let page = Self::visible_rows(state.layout.canvas) as isize;
+        let focused = self.focused(state);
+        match state.keymap.resolve(Context::Outline, key) {
+            Some(Action::Up) => self.move_focus(state, -1),
+            Some(Action::Down) => self.move_focus(state, 1),
+            Some(Action::PanUp) => self.move_focus(state, -page),
+            Some(Action::PanDown) => self.move_focus(state, page),
+            Some(Action::Submit) => {
+                if let Some(id) = focused {
+                    state.select_and_jump_to(id);
+                }
+            }
+            Some(Action::ToggleSelect) => {
+                if let Some(id) = focused {
+                    state.toggle_element_selection(id);
+                }
+            }
+            Some(Action::Edit) => self.start_rename(state),
+            Some(Action::Cancel) => {
+                if self.filter.is_empty() {
+                    state.toggle_outline();
+                    state.switch_panel(Panel::Canvas);
+                } else {
+                    self.filter.clear();
+                    self.refilter();
+                }
+            }
+            // Typing into the filter isn't remappable
+            _ => match key.code {
+                KeyCode::Char(c)
+                    if !key
+                        .modifiers
+                        .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+                {
+                    self.filter.push(c);
+                    self.refilter();
+                }
+                KeyCode::Backspace => {
+                    self.filter.pop();
+                    self.refilter();
+                }
+                _ => return EventResult::Ignored,
+            },
+        }
+        EventResult::Consumed
+    }
+
+    fn handle_mouse_down(&mut self, state: &mut AppState, mouse_event: &MouseEvent) -> EventResult {
+        if !state.show_outline {
+            return EventResult::Ignored;
+        }
+
+        let rows = self.rows(state);
+        let area = Self::calculate_modal_area(state.layout.canvas, rows.len());
+        if !area.contains(mouse_event.column, mouse_event.row) {
+            return EventResult::Ignored;
+        }
+
+        // Activate outline panel when clicked. Clicking an element selects it and
+        // Shift+click adds it to the selection.
+        let line = area.content_relative_y(mouse_event.row) as usize;
+        let clicked = line
+            .checked_sub(FIRST_ROW_LINE)
+            .filter(|&row| row < Self::visible_rows(state.layout.canvas))
+            .and_then(|row| rows.get(self.scroll_offset + row))
+            .map(|e| e.id());
+        state.switch_panel(Panel::Outline);
+        if let Some(id) = clicked {
+            self.focused_element = Some(id);
+            if mouse_event.is_shift() {
+                state.toggle_element_selection(id);
+            } else {
+                state.select_and_jump_to(id);
+            }
+        }
+        EventResult::Consumed
+    }
+
+    fn handle_mouse_scroll(
+        &mut self,
+        state: &mut AppState,
+        mouse_event: &MouseEvent,
+    ) -> EventResult {
+        if !state.show_outline {
+            return EventResult::Ignored;
+        }
+
+        let row_count = self.rows(state).len();
+        let area = Self::calculate_modal_area(state.layout.canvas, row_count);
+        if !area.contains(mouse_event.column, mouse_event.row) {
+            return EventResult::Ignored;
+        }
+
+        let max_offset = row_count.saturating_sub(Self::visible_rows(state.layout.canvas));
+        self.scroll_offset = match mouse_event.kind {
+            MouseEventKind::ScrollUp => self.scroll_offset.saturating_sub(SCROLL_STEP),
+            MouseEventKind::ScrollDown => (self.scroll_offset + SCROLL_STEP).min(max_offset),
+            _ => self.scroll_offset,
+        };
+        EventResult::Consumed
+    }
+}
+
+impl Component for OutlinePanel {
+    fn draw(&mut self, state: &AppState, frame: &mut Frame) {
+        if !state.show_outline {
+            return;
+        }
+
+        let rows = self.rows(state);
+        let area = Self::calculate_modal_area(state.layout.canvas, rows.len());
+        area.clear(frame);
+
+        // Elements may have been deleted since the list was scrolled
+        let visible = Self::visible_rows(state.layout.canvas);
+        self.scroll_offset = self.scroll_offset.min(rows.len().saturating_sub(visible));
+
+        let panel_active = state.active_panel == Panel::Outline;
+        let focused_row = self.focused_row(&rows);
+
+        let mut lines = vec![ui::blank_line()];
+        lines.push(self.render_filter_line(panel_active));
+        lines.push(ui::blank_line());
+
+        if rows.is_empty() {
+            lines.push(Line::from(ui::padded_span(
+                "No elements",
+                ui::muted_style(),
+            )));
+        }
+        for (row, element) in rows
+            .iter()
+            .enumerate()
+            .skip(self.scroll_offset)
+            .take(visible)
+        {
+            let is_focused = Some(row) == focused_row;
+            match &self.rename_input {
+                Some(input) if is_focused => {
+                    let name = PropertyValue::Text(element.name().to_string());
+                    lines.push(input.render_line(Some(&name), panel_active));
+                }
+                _ => lines.push(Self::render_element_line(
+                    state,
+                    element,
+                    is_focused,
+                    panel_active,
+                )),
+            }
+        }
+        lines.push(ui::blank_line());
+
+        // Add shortcut helper at the bottom
+        lines.push(Self::helper_line(state));
+
+        let title = if rows.len() > visible {
+            format!(" Outline ({}) ", rows.len())
+        } else {
+            " Outline ".to_string()
+        };
+        let block = ui::panel_block(&title, panel_active);
+        let widget = Paragraph::new(lines).block(block);
+        frame.render_widget(widget, area.rect());
+    }
+}
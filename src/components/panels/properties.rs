@@ -1,11 +1,14 @@
-use crate::components::{ChoiceInput, Component, NumericInput, PropertyInput};
-use crate::elements::{Element, FieldType, PropertiesSpec, PropertyValue};
+use crate::components::{
+    BooleanInput, ChoiceInput, Component, NumericInput, PropertyInput, TextInput,
+};
+use crate::elements::{FieldType, PropertiesSpec};
 use crate::events::{EventHandler, EventResult, KeyEvent, MouseEvent};
+use crate::keymap::{Action, Context};
 use crate::state::AppState;
 use crate::types::Panel;
 use crate::ui;
 use crate::utils::ModalArea;
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyModifiers};
 use ratatui::{
     Frame,
     layout::Rect,
@@ -18,18 +21,18 @@ const PROPERTIES_PANEL_WIDTH: u16 = 35;
 
 /// Fixed content lines in properties panel:
 /// - 1 blank line at top
-/// - 2 lines for element type and name
+/// - 1 line for element type (the name is the first editable field)
 /// - 1 blank line after header
 /// - 1 line for shortcut helper at bottom
-const PROPERTIES_FIXED_LINES: u16 = 5;
+const PROPERTIES_FIXED_LINES: u16 = 4;
 
 pub struct PropertiesPanel {
     // Input components for each property field
     inputs: Vec<Box<dyn PropertyInput>>,
     // Currently focused input index
     focused_input_index: Option<usize>,
-    // Element ID we're currently editing
-    current_element_id: Option<usize>,
+    // IDs of the elements we're currently editing
+    current_element_ids: Vec<usize>,
 }
 
 impl PropertiesPanel {
@@ -37,25 +40,22 @@ impl PropertiesPanel {
         Self {
             inputs: Vec::new(),
             focused_input_index: None,
-            current_element_id: None,
+            current_element_ids: Vec::new(),
         }
     }
 
-    /// Initialize inputs for the given element
-    fn initialize(&mut self, element: &Element) {
-        // Only reinitialize if element changed
-        if self.current_element_id == Some(element.id()) {
-            // Same element, no need to recreate inputs
+    /// Initialize inputs for the given selection
+    fn initialize(&mut self, element_ids: &[usize], spec: &PropertiesSpec) {
+        // Only reinitialize if the selection changed
+        if self.current_element_ids == element_ids {
+            // Same elements, no need to recreate inputs
             return;
         }
 
         // Clear existing inputs
         self.inputs.clear();
         self.focused_input_index = None;
-        self.current_element_id = Some(element.id());
-
-        // Get the properties spec from element
-        let spec = element.properties_spec();
+        self.current_element_ids = element_ids.to_vec();
 
         // Create input for each field
         for field in spec.all_fields() {
@@ -68,8 +68,13 @@ impl PropertiesPanel {
                     let input = ChoiceInput::new(&field.name, &field.label, options.clone());
                     self.inputs.push(Box::new(input));
                 }
-                _ => {
-                    // Other field types not yet supported
+                FieldType::Text { max_length } => {
+                    let input = TextInput::new(&field.name, &field.label, *max_length);
+                    self.inputs.push(Box::new(input));
+                }
+                FieldType::Boolean => {
+                    let input = BooleanInput::new(&field.name, &field.label);
+                    self.inputs.push(Box::new(input));
                 }
             }
         }
@@ -154,47 +159,55 @@ impl PropertiesPanel {
         height
     }
 
-    /// Get the selected element if properties panel should be visible
-    fn get_selected_element(state: &AppState) -> Option<(usize, &Element)> {
-        // Don't show properties when in edit table mode
-        if state.is_editing_table() {
+    /// Get the selected element IDs and the properties they share, if the properties
+    /// panel should be visible
+    fn get_selection(state: &AppState) -> Option<(Vec<usize>, PropertiesSpec)> {
+        // Don't show properties when in edit table/label/vertices mode
+        if state.is_editing_table() || state.is_editing_label() || state.is_editing_vertices() {
             return None;
         }
 
-        // Only show when properties visible and exactly one element selected
-        if !state.show_properties || state.selection_state.selected_ids.len() != 1 {
+        // Only show when properties visible and something is selected
+        if !state.show_properties {
             return None;
         }
 
-        let element_id = state.selection_state.selected_ids[0];
-        state
-            .canvas
-            .get_element(element_id)
-            .map(|e| (element_id, e))
+        let spec = state.selected_properties_spec()?;
+        Some((state.selection_state.selected_ids.clone(), spec))
+    }
+
+    /// Type of the selected element; for several elements, their shared type (or
+    /// "Mixed") and how many are selected
+    fn selection_type(state: &AppState, element_ids: &[usize]) -> String {
+        let mut types = element_ids
+            .iter()
+            .filter_map(|&id| state.canvas.get_element(id))
+            .map(|element| element.type_name());
+        let first = types.next().unwrap_or_default();
+        if element_ids.len() == 1 {
+            return first.to_string();
+        }
+        let shared = if types.all(|type_name| type_name == first) {
+            first
+        } else {
+            "Mixed"
+        };
+        format!("{} ({})", shared, element_ids.len())
     }
 
-    /// Forward key event to focused input with callback
+    /// Forward key event to focused input, applying changes to every selected element
     fn forward_to_input(
         &mut self,
         idx: usize,
         key: &KeyEvent,
-        element_id: usize,
         state: &mut AppState,
     ) -> EventResult {
-        let prop_name = self.inputs[idx].property_name();
-
-        // Get current value
-        let current_value = state
-            .canvas
-            .get_element(element_id)
-            .and_then(|e| e.get_property(prop_name))
-            .unwrap_or(PropertyValue::Numeric(0));
-
-        self.inputs[idx].handle_key_event(key, &current_value, &mut |prop_name, value| {
-            // Callback: update the element when value changes
-            if let Some(element) = state.canvas.get_element_mut(element_id) {
-                let _ = element.set_property(prop_name, value);
-            }
+        // Current value, or None when the selected elements differ
+        let current_value = state.selected_property_value(self.inputs[idx].property_name());
+
+        self.inputs[idx].handle_key_event(key, current_value.as_ref(), &mut |prop_name, value| {
+            // Callback: update the elements when value changes
+            state.apply_selected_property(prop_name, value);
         })
     }
 }
@@ -208,13 +221,13 @@ impl EventHandler for PropertiesPanel {
             return EventResult::Ignored;
         }
 
-        // Get selected element
-        let Some((element_id, element)) = Self::get_selected_element(state) else {
+        // Get selected elements
+        let Some((element_ids, spec)) = Self::get_selection(state) else {
             return EventResult::Ignored;
         };
 
         // Initialize inputs if needed
-        self.initialize(element);
+        self.initialize(&element_ids, &spec);
 
         if self.inputs.is_empty() {
             return EventResult::Ignored;
@@ -229,44 +242,54 @@ impl EventHandler for PropertiesPanel {
         // If editing, forward to focused input with callback
         if is_editing {
             if let Some(idx) = self.focused_input_index {
-                return self.forward_to_input(idx, key, element_id, state);
+                return self.forward_to_input(idx, key, state);
             }
         }
 
         // Handle navigation between inputs
-        match key.code {
-            KeyCode::Tab | KeyCode::Down | KeyCode::Char('j') => {
+        match state.keymap.resolve(Context::Panel, key) {
+            Some(Action::Down) => {
                 self.focus_next();
                 EventResult::Consumed
             }
-            KeyCode::Up | KeyCode::Char('k') => {
+            Some(Action::Up) => {
                 self.focus_prev();
                 EventResult::Consumed
             }
-            KeyCode::Enter => {
-                // Forward to focused input to start editing
+            Some(Action::Edit) => {
+                // Forward to focused input to start editing; inputs start on Enter, so
+                // a remapped edit key is passed on as one
                 if let Some(idx) = self.focused_input_index {
-                    return self.forward_to_input(idx, key, element_id, state);
+                    let enter = KeyEvent {
+                        code: KeyCode::Enter,
+                        modifiers: KeyModifiers::NONE,
+                    };
+                    return self.forward_to_input(idx, &enter, state);
                 }
                 EventResult::Ignored
             }
             // Consume Left/Right to prevent element movement when properties panel is open
-            KeyCode::Left | KeyCode::Right => EventResult::Consumed,
+            _ if matches!(
+                state.keymap.resolve(Context::Selection, key),
+                Some(Action::Left | Action::Right)
+            ) =>
+            {
+                EventResult::Consumed
+            }
             _ => EventResult::Ignored,
         }
     }
 
     fn handle_mouse_down(&mut self, state: &mut AppState, mouse_event: &MouseEvent) -> EventResult {
-        // Get selected element to check if properties should be visible
-        let Some((element_id, element)) = Self::get_selected_element(state) else {
+        // Get selected elements to check if properties should be visible
+        let Some((element_ids, spec)) = Self::get_selection(state) else {
             return EventResult::Ignored;
         };
 
         // Initialize inputs if needed
-        self.initialize(element);
+        self.initialize(&element_ids, &spec);
 
         // Calculate properties area
-        let spec = element.properties_spec();
         let content_height = self.calculate_content_height(&spec);
         let canvas_area = state.layout.canvas;
         let area = Self::calculate_modal_area(canvas_area, content_height);
@@ -284,16 +307,15 @@ impl EventHandler for PropertiesPanel {
 
 impl Component for PropertiesPanel {
     fn draw(&mut self, state: &AppState, frame: &mut Frame) {
-        // Get selected element
-        let Some((element_id, element)) = Self::get_selected_element(state) else {
+        // Get selected elements
+        let Some((element_ids, spec)) = Self::get_selection(state) else {
             return;
         };
 
         // Initialize inputs if needed
-        self.initialize(element);
+        self.initialize(&element_ids, &spec);
 
-        // Get properties spec to calculate height
-        let spec = element.properties_spec();
+        // Calculate height from the properties spec
         let content_height = self.calculate_content_height(&spec);
 
         // Calculate dynamic position at bottom-left corner
@@ -305,27 +327,28 @@ impl Component for PropertiesPanel {
         // Build content lines
         let mut lines = vec![ui::blank_line()];
 
-        // Element type and name
-        lines.push(ui::label_value_line("Type", element.type_name()));
-        lines.push(ui::label_value_line("Name", element.name().to_string()));
+        // Element type
+        lines.push(ui::label_value_line(
+            "Type",
+            Self::selection_type(state, &element_ids),
+        ));
 
         lines.push(ui::blank_line());
 
         // Render editable properties (if any)
-        let mut field_index = 0;
         let panel_active = state.active_panel == Panel::Properties;
         for section in &spec.sections {
             lines.push(ui::section_header(&section.title));
 
             // Render inputs for this section
-            for _field in &section.fields {
-                if field_index < self.inputs.len() {
-                    let prop_name = self.inputs[field_index].property_name();
-                    let current_value = element
-                        .get_property(prop_name)
-                        .unwrap_or(PropertyValue::Numeric(0));
-                    lines.push(self.inputs[field_index].render_line(&current_value, panel_active));
-                    field_index += 1;
+            for field in &section.fields {
+                if let Some(input) = self
+                    .inputs
+                    .iter()
+                    .find(|input| input.property_name() == field.name)
+                {
+                    let current_value = state.selected_property_value(&field.name);
+                    lines.push(input.render_line(current_value.as_ref(), panel_active));
                 }
             }
 
@@ -1,11 +1,11 @@
 use crate::components::Component;
 use crate::events::{EventHandler, EventResult, KeyEvent, MouseEvent};
+use crate::keymap::{Action, Context, KeyBinding};
 use crate::state::AppState;
 use crate::tools::Tool;
 use crate::types::Panel;
-use crate::ui::{self, COLOR_LABEL, COLOR_PRIMARY, COLOR_SELECTED_BG, COLOR_SUCCESS};
+use crate::ui;
 use crate::utils::ModalArea;
-use crossterm::event::KeyCode;
 use ratatui::{
     Frame,
     layout::Rect,
@@ -15,8 +15,8 @@ use ratatui::{
 };
 
 const MODAL_WIDTH: u16 = 25;
-const MODAL_HEIGHT: u16 = 13;
-const KEY_DISPLAY_WIDTH: usize = 4; // "  X " format
+const MODAL_HEIGHT: u16 = 14;
+const KEY_DISPLAY_WIDTH: usize = 4; // "  X " format, for tools without a key
 
 fn get_modal_area(canvas_area: Rect) -> ModalArea {
     ModalArea::bottom_left(canvas_area, MODAL_WIDTH, MODAL_HEIGHT)
@@ -47,8 +47,20 @@ impl EventHandler for ToolsPanel {
             return EventResult::Ignored;
         }
 
-        match key_event.code {
-            KeyCode::Esc | KeyCode::Char(' ') => {
+        if let Some(action) = state.keymap.resolve(Context::Modal, key_event) {
+            match action {
+                Action::Up => state.select_prev_tool(),
+                Action::Down => state.select_next_tool(),
+                // Close modal after selecting tool with Enter
+                Action::Submit => state.toggle_tools_modal(),
+                Action::ToggleLock => state.toggle_tool_lock(),
+                _ => return EventResult::Ignored,
+            }
+            return EventResult::Consumed;
+        }
+
+        match state.keymap.resolve(Context::Global, key_event) {
+            Some(Action::Cancel | Action::Tools) => {
                 state.toggle_tools_modal();
                 // Switch back to canvas when closing
                 if !state.show_tools_modal {
@@ -56,31 +68,10 @@ impl EventHandler for ToolsPanel {
                 }
                 EventResult::Consumed
             }
-            KeyCode::Up | KeyCode::Char('k') => {
-                state.select_prev_tool();
-                EventResult::Consumed
-            }
-            KeyCode::Down | KeyCode::Char('j') => {
-                state.select_next_tool();
-                EventResult::Consumed
-            }
-            KeyCode::Enter => {
-                // Close modal after selecting tool with Enter
-                state.toggle_tools_modal();
-                EventResult::Consumed
-            }
-            KeyCode::Tab | KeyCode::Char('x') => {
-                state.toggle_tool_lock();
-                EventResult::Consumed
-            }
-            KeyCode::Char(c) => {
+            Some(Action::SelectTool(tool)) => {
                 // Direct tool selection by shortcut (stays open)
-                if let Some(tool) = Tool::from_key(c) {
-                    state.select_tool(tool);
-                    EventResult::Consumed
-                } else {
-                    EventResult::Ignored
-                }
+                state.select_tool(tool);
+                EventResult::Consumed
             }
             _ => EventResult::Ignored,
         }
@@ -117,32 +108,31 @@ impl EventHandler for ToolsPanel {
 
 // --- RENDERING ---
 
-fn render_tool_line(tool: Tool, is_selected: bool) -> Line<'static> {
-    let key_char = tool.key();
+fn render_tool_line(tool: Tool, key: Option<KeyBinding>, is_selected: bool) -> Line<'static> {
     let name = tool.name().to_string();
 
-    let key_display = if key_char == '\0' {
-        " ".repeat(KEY_DISPLAY_WIDTH)
-    } else {
-        format!("  {} ", key_char)
+    let key_display = match key {
+        Some(key) => format!("  {} ", key),
+        None => " ".repeat(KEY_DISPLAY_WIDTH),
     };
 
+    let theme = ui::theme();
     let (key_style, name_style, bg_style) = if is_selected {
         (
-            Style::default().fg(COLOR_PRIMARY).bg(COLOR_SELECTED_BG),
-            Style::default().fg(COLOR_LABEL).bg(COLOR_SELECTED_BG),
-            Style::default().bg(COLOR_SELECTED_BG),
+            Style::default().fg(theme.primary).bg(theme.selected_bg),
+            Style::default().fg(theme.label).bg(theme.selected_bg),
+            Style::default().bg(theme.selected_bg),
         )
     } else {
         (
-            Style::default().fg(COLOR_PRIMARY),
+            Style::default().fg(theme.primary),
             Style::default(),
             Style::default(),
         )
     };
 
     // Calculate padding to fill rest of line with background
-    let content_width = KEY_DISPLAY_WIDTH + name.len();
+    let content_width = key_display.chars().count() + name.len();
     let padding_width = (MODAL_WIDTH as usize).saturating_sub(content_width + 2); // -2 for borders
 
     Line::from(vec![
@@ -155,7 +145,7 @@ fn render_tool_line(tool: Tool, is_selected: bool) -> Line<'static> {
 fn render_lock_line(is_locked: bool) -> Line<'static> {
     let lock_text = if is_locked { "[x]" } else { "[ ]" };
     let lock_style = if is_locked {
-        Style::default().fg(COLOR_SUCCESS)
+        Style::default().fg(ui::theme().success)
     } else {
         Style::default()
     };
@@ -179,7 +169,11 @@ impl Component for ToolsPanel {
 
         // Render tool lines
         for &tool in Tool::all() {
-            lines.push(render_tool_line(tool, state.tool.selected_tool == tool));
+            lines.push(render_tool_line(
+                tool,
+                state.keymap.tool_key(tool),
+                state.tool.selected_tool == tool,
+            ));
         }
 
         // Add separator and lock line
@@ -1,8 +1,10 @@
 use crate::components::Component;
+use crate::elements::Element;
 use crate::events::EventHandler;
-use crate::state::AppState;
+use crate::keymap::{Action, Context};
+use crate::state::{AppState, Member};
 use crate::tools::Tool;
-use crate::ui::{self, COLOR_ERROR, COLOR_HINT, COLOR_SUCCESS, CURSOR_BLOCK};
+use crate::ui::{self, CURSOR_BLOCK};
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Layout},
@@ -11,12 +13,7 @@ use ratatui::{
     widgets::Paragraph,
 };
 
-const CURSOR_POSITION_WIDTH: u16 = 20;
-
-// Mode badge background colors
-const MODE_COLOR_SELECT: Color = Color::Yellow;
-const MODE_COLOR_DRAW: Color = Color::Blue;
-const MODE_COLOR_EDIT: Color = Color::Magenta;
+const CURSOR_POSITION_WIDTH: u16 = 24;
 
 // ============================================================================
 // StatusBar Component
@@ -78,6 +75,23 @@ fn render_command_mode(state: &AppState, frame: &mut Frame, area: ratatui::layou
         Style::default().fg(Color::White),
     ));
 
+    // Tab completion candidates, with the one in the buffer highlighted
+    if let Some(completion) = &state.command.completion {
+        spans.push(Span::raw(" "));
+        for (i, candidate) in completion.candidates.iter().enumerate() {
+            let color = if i == completion.index {
+                ui::theme().hint
+            } else {
+                ui::theme().muted
+            };
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(
+                candidate.trim_end().to_string(),
+                Style::default().fg(color),
+            ));
+        }
+    }
+
     let status = Paragraph::new(Line::from(spans)).style(Style::default().fg(Color::White));
     frame.render_widget(status, area);
 }
@@ -85,9 +99,9 @@ fn render_command_mode(state: &AppState, frame: &mut Frame, area: ratatui::layou
 /// Render status message (error or success)
 fn render_status_message(message: &str, frame: &mut Frame, area: ratatui::layout::Rect) {
     let color = if message.starts_with("Error") {
-        COLOR_ERROR
+        ui::theme().error
     } else {
-        COLOR_SUCCESS
+        ui::theme().success
     };
 
     let text = format!(" {}", message);
@@ -120,13 +134,35 @@ fn build_left_status(state: &AppState) -> Paragraph<'static> {
     let (badge_text, badge_color) = get_mode_badge(state);
     spans.push(create_mode_badge(&badge_text, badge_color));
 
+    // Show the group being edited, if any
+    if let Some(group) = state
+        .group_scope()
+        .and_then(|id| state.canvas.get_group(id))
+    {
+        spans.push(ui::separator());
+        spans.push(Span::raw("In: "));
+        spans.push(Span::styled(
+            group.name.clone(),
+            Style::default().fg(Color::Yellow),
+        ));
+        add_action_hint(
+            &mut spans,
+            state,
+            "Exit group",
+            Context::Global,
+            Action::Cancel,
+        );
+    }
+
     // Add contextual hints for select tool
-    if !state.is_editing_table() && state.is_select_tool() {
+    if state.is_editing_vertices() {
+        add_vertex_hints(&mut spans, state);
+    } else if !state.is_editing_table() && !state.is_editing_label() && state.is_select_tool() {
         add_selection_hints(&mut spans, state);
     }
 
     // Add global hints
-    add_global_hints(&mut spans);
+    add_global_hints(&mut spans, state);
 
     Paragraph::new(Line::from(spans)).style(Style::default().fg(Color::White))
 }
@@ -148,7 +184,17 @@ fn get_mode_badge(state: &AppState) -> (String, Color) {
     if state.is_editing_table() {
         (
             format!(" {:^width$} ", "EDIT TABLE", width = Tool::max_name_len()),
-            MODE_COLOR_EDIT,
+            ui::theme().mode_edit,
+        )
+    } else if state.is_editing_label() {
+        (
+            format!(" {:^width$} ", "EDIT LABEL", width = Tool::max_name_len()),
+            ui::theme().mode_edit,
+        )
+    } else if state.is_editing_vertices() {
+        (
+            format!(" {:^width$} ", "EDIT PATH", width = Tool::max_name_len()),
+            ui::theme().mode_edit,
         )
     } else {
         let tool_name = format!(
@@ -157,9 +203,9 @@ fn get_mode_badge(state: &AppState) -> (String, Color) {
             width = Tool::max_name_len()
         );
         let color = if state.tool.selected_tool == Tool::Select {
-            MODE_COLOR_SELECT
+            ui::theme().mode_select
         } else {
-            MODE_COLOR_DRAW
+            ui::theme().mode_draw
         };
         (tool_name, color)
     }
@@ -187,21 +233,99 @@ fn add_selection_hints(spans: &mut Vec<Span<'static>>, state: &AppState) {
         Style::default().fg(Color::Yellow),
     ));
 
-    // Show properties hint when exactly one element is selected
+    // Show grouping hints for the selected elements and groups
+    let units = state.selected_units();
+    if units.len() >= 2 {
+        add_action_hint(spans, state, "Group", Context::Selection, Action::Group);
+    } else if let [Member::Group(_)] = units[..] {
+        add_action_hint(
+            spans,
+            state,
+            "Enter group",
+            Context::Selection,
+            Action::Edit,
+        );
+    }
+    if units.iter().any(|unit| matches!(unit, Member::Group(_))) {
+        add_action_hint(spans, state, "Ungroup", Context::Selection, Action::Ungroup);
+    }
+
+    // Show properties and edit hints when exactly one element is selected
     if selected_ids.len() == 1 {
-        add_hint(spans, "Properties", "p");
+        add_action_hint(
+            spans,
+            state,
+            "Properties",
+            Context::Global,
+            Action::Properties,
+        );
+        let editable = state
+            .canvas
+            .get_element(selected_ids[0])
+            .and_then(|e| match e {
+                Element::Table(_) => Some("Edit cells"),
+                Element::Rectangle(_) => Some("Edit label"),
+                Element::Line(_) | Element::Arrow(_) => Some("Edit path"),
+                _ => None,
+            });
+        // Enter opens a selected group before editing its element
+        if let Some(label) = editable
+            && !matches!(units[..], [Member::Group(_)])
+        {
+            add_action_hint(spans, state, label, Context::Selection, Action::Edit);
+        }
     }
 
     // Show move/delete hints
-    add_hint(spans, "Move", "←↑↓→");
-    add_hint(spans, "Delete", "⌫");
+    let move_keys: String = [Action::Left, Action::Up, Action::Down, Action::Right]
+        .into_iter()
+        .filter_map(|action| state.keymap.key(Context::Selection, action))
+        .map(|key| key.to_string())
+        .collect();
+    if !move_keys.is_empty() {
+        add_hint(spans, "Move", &move_keys);
+    }
+    add_action_hint(spans, state, "Delete", Context::Selection, Action::Delete);
+}
+
+/// Add Edit Vertices mode hints
+fn add_vertex_hints(spans: &mut Vec<Span<'static>>, state: &AppState) {
+    let hints = [
+        ("Next", Action::NextVertex),
+        ("Add bend", Action::InsertBend),
+        ("Remove bend", Action::Delete),
+        ("Reverse", Action::Reverse),
+        ("Done", Action::Submit),
+    ];
+    for (label, action) in hints {
+        add_action_hint(spans, state, label, Context::VertexEdit, action);
+    }
 }
 
-/// Add global hints (tools, help, quit)
-fn add_global_hints(spans: &mut Vec<Span<'static>>) {
-    add_hint(spans, "Tools", "Space");
-    add_hint(spans, "Help", "?");
-    add_hint(spans, "Quit", "q");
+/// Add global hints (undo/redo, tools, help, quit)
+fn add_global_hints(spans: &mut Vec<Span<'static>>, state: &AppState) {
+    if state.history.can_undo() {
+        add_action_hint(spans, state, "Undo", Context::Global, Action::Undo);
+    }
+    if state.history.can_redo() {
+        add_action_hint(spans, state, "Redo", Context::Global, Action::Redo);
+    }
+    add_action_hint(spans, state, "Tools", Context::Global, Action::Tools);
+    add_action_hint(spans, state, "Help", Context::Global, Action::Help);
+    add_action_hint(spans, state, "Quit", Context::Global, Action::Quit);
+}
+
+/// Add a hint with the action's first key, if it has one
+fn add_action_hint(
+    spans: &mut Vec<Span<'static>>,
+    state: &AppState,
+    label: &str,
+    context: Context,
+    action: Action,
+) {
+    if let Some(key) = state.keymap.key(context, action) {
+        add_hint(spans, label, &key.to_string());
+    }
 }
 
 /// Add a hint (label + key) to the spans
@@ -210,6 +334,6 @@ fn add_hint(spans: &mut Vec<Span<'static>>, label: &str, key: &str) {
     spans.push(Span::raw(format!("{}: ", label)));
     spans.push(Span::styled(
         key.to_string(),
-        Style::default().fg(COLOR_HINT),
+        Style::default().fg(ui::theme().hint),
     ));
 }
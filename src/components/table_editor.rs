@@ -1,6 +1,7 @@
 use crate::elements::{Element, TableElement};
 use crate::events::{EventResult, KeyEvent};
-use crate::state::AppState;
+use crate::keymap::{Action, Context};
+use crate::state::{AppState, EditKind};
 use crossterm::event::KeyCode;
 use std::collections::HashMap;
 
@@ -22,8 +23,8 @@ pub fn build_edit_table_overlays(state: &AppState) -> OverlayMaps {
         return (highlight_map, content_map);
     };
 
-    let x = table.start.x as i32;
-    let y = table.start.y as i32;
+    let x = table.start.x;
+    let y = table.start.y;
     let row_height = TableElement::CELL_HEIGHT;
 
     let selected_row = edit_state.selected_row;
@@ -113,6 +114,9 @@ pub fn handle_edit_table_key(state: &mut AppState, key_event: &KeyEvent) -> Even
                 };
 
                 // Restore original content
+                state
+                    .history
+                    .set_pending_kind(EditKind::TableCell { table_id, row, col });
                 let Some(Element::Table(table)) = state.canvas.get_element_mut(table_id) else {
                     return EventResult::Consumed;
                 };
@@ -143,6 +147,9 @@ pub fn handle_edit_table_key(state: &mut AppState, key_event: &KeyEvent) -> Even
                 };
 
                 // Update table cell and resize
+                state
+                    .history
+                    .set_pending_kind(EditKind::TableCell { table_id, row, col });
                 let Some(Element::Table(table)) = state.canvas.get_element_mut(table_id) else {
                     return EventResult::Consumed;
                 };
@@ -168,6 +175,9 @@ pub fn handle_edit_table_key(state: &mut AppState, key_event: &KeyEvent) -> Even
                 };
 
                 // Update table cell and resize
+                state
+                    .history
+                    .set_pending_kind(EditKind::TableCell { table_id, row, col });
                 let Some(Element::Table(table)) = state.canvas.get_element_mut(table_id) else {
                     return EventResult::Consumed;
                 };
@@ -192,6 +202,9 @@ pub fn handle_edit_table_key(state: &mut AppState, key_event: &KeyEvent) -> Even
                 };
 
                 // Update table cell and resize
+                state
+                    .history
+                    .set_pending_kind(EditKind::TableCell { table_id, row, col });
                 let Some(Element::Table(table)) = state.canvas.get_element_mut(table_id) else {
                     return EventResult::Consumed;
                 };
@@ -221,20 +234,20 @@ pub fn handle_edit_table_key(state: &mut AppState, key_event: &KeyEvent) -> Even
     }
 
     // Not editing cell - handle navigation and cell editing activation
-    match key_event.code {
-        KeyCode::Esc | KeyCode::Char('q') => {
+    match state.keymap.resolve(Context::TableEdit, key_event) {
+        Some(Action::Cancel) => {
             // Exit Edit Table mode
             state.exit_edit_table_mode();
             EventResult::Consumed
         }
-        KeyCode::Up => {
+        Some(Action::Up) => {
             let edit_state = state.editing_table_mut().unwrap();
             if edit_state.selected_row > 0 {
                 edit_state.selected_row -= 1;
             }
             EventResult::Consumed
         }
-        KeyCode::Down => {
+        Some(Action::Down) => {
             let max_rows = {
                 let Some(Element::Table(table)) = state.canvas.get_element(table_id) else {
                     return EventResult::Consumed;
@@ -247,14 +260,14 @@ pub fn handle_edit_table_key(state: &mut AppState, key_event: &KeyEvent) -> Even
             }
             EventResult::Consumed
         }
-        KeyCode::Left => {
+        Some(Action::Left) => {
             let edit_state = state.editing_table_mut().unwrap();
             if edit_state.selected_col > 0 {
                 edit_state.selected_col -= 1;
             }
             EventResult::Consumed
         }
-        KeyCode::Right => {
+        Some(Action::Right) => {
             let max_cols = {
                 let Some(Element::Table(table)) = state.canvas.get_element(table_id) else {
                     return EventResult::Consumed;
@@ -267,7 +280,7 @@ pub fn handle_edit_table_key(state: &mut AppState, key_event: &KeyEvent) -> Even
             }
             EventResult::Consumed
         }
-        KeyCode::Enter => {
+        Some(Action::Edit) => {
             // Start editing current cell - load its content into edit buffer
             let cell_content = {
                 let Some(Element::Table(table)) = state.canvas.get_element(table_id) else {
@@ -0,0 +1,213 @@
+use crate::components::table_editor::OverlayMaps;
+use crate::elements::{self, Element};
+use crate::events::{EventResult, KeyEvent};
+use crate::keymap::{Action, Context};
+use crate::state::{AppState, EditKind};
+use crate::types::Coord;
+use std::collections::HashMap;
+
+/// Marker drawn on each vertex of the line or arrow being edited
+const VERTEX_MARKER: char = '●';
+
+/// Build overlays for Edit Vertices mode (a marker on every vertex, the selected one
+/// drawn as the cursor)
+pub fn build_edit_vertices_overlays(state: &AppState) -> OverlayMaps {
+    let highlight_map = HashMap::new();
+    let mut content_map = HashMap::new();
+
+    if let Some((_, points, selected)) = current(state) {
+        for (index, point) in points.iter().enumerate() {
+            content_map.insert((point.x, point.y), (VERTEX_MARKER, index == selected));
+        }
+    }
+
+    (highlight_map, content_map)
+}
+
+/// Try to enter Edit Vertices mode if a single line or arrow is selected
+pub fn try_enter_edit_vertices_mode(state: &mut AppState) -> EventResult {
+    let &[element_id] = state.get_selected_element_ids() else {
+        return EventResult::Ignored;
+    };
+    if !matches!(
+        state.canvas.get_element(element_id),
+        Some(Element::Line(_) | Element::Arrow(_))
+    ) {
+        return EventResult::Ignored;
+    }
+    state.enter_edit_vertices_mode(element_id);
+    EventResult::Consumed
+}
+
+/// Handle keyboard events in Edit Vertices mode
+pub fn handle_edit_vertices_key(state: &mut AppState, key_event: &KeyEvent) -> EventResult {
+    let Some((element_id, mut points, vertex)) = current(state) else {
+        // Line was removed
+        state.exit_edit_vertices_mode();
+        return EventResult::Ignored;
+    };
+    let Some(action) = state.keymap.resolve(Context::VertexEdit, key_event) else {
+        return EventResult::Ignored;
+    };
+
+    let step = |dx: i32, dy: i32| Coord {
+        x: points[vertex].x + dx,
+        y: points[vertex].y + dy,
+    };
+    match action {
+        Action::Cancel => {
+            // Restore the original path and attachments
+            let original = state
+                .editing_vertices
+                .as_ref()
+                .map(|edit_state| edit_state.original.clone());
+            if let Some(original) = original {
+                state
+                    .history
+                    .set_pending_kind(EditKind::Vertices { element_id });
+                if let Some(element) = state.canvas.get_element_mut(element_id) {
+                    *element = original;
+                }
+            }
+            state.exit_edit_vertices_mode();
+        }
+        Action::Submit => state.exit_edit_vertices_mode(),
+        Action::Up | Action::Down | Action::Left | Action::Right => {
+            let to = match action {
+                Action::Up => step(0, -1),
+                Action::Down => step(0, 1),
+                Action::Left => step(-1, 0),
+                _ => step(1, 0),
+            };
+            let vertex = elements::move_vertex(&mut points, vertex, to);
+            apply(state, element_id, &points, vertex);
+        }
+        Action::NextVertex => set_vertex(state, (vertex + 1) % points.len()),
+        Action::PrevVertex => set_vertex(state, (vertex + points.len() - 1) % points.len()),
+        Action::InsertBend => {
+            // Halfway along the segment after the vertex (before it, for the end)
+            let start = vertex.min(points.len() - 2);
+            let (a, b) = (points[start], points[start + 1]);
+            let middle = Coord {
+                x: (a.x + b.x) / 2,
+                y: (a.y + b.y) / 2,
+            };
+            if let Some(vertex) = elements::insert_vertex(&mut points, start, middle) {
+                apply(state, element_id, &points, vertex);
+            }
+        }
+        Action::Delete => {
+            if let Some(vertex) = elements::remove_vertex(&mut points, vertex) {
+                apply(state, element_id, &points, vertex);
+            }
+        }
+        Action::Reverse => {
+            state
+                .history
+                .set_pending_kind(EditKind::Vertices { element_id });
+            if let Some(element) = state.canvas.get_element_mut(element_id) {
+                element.reverse();
+            }
+            set_vertex(state, points.len() - 1 - vertex);
+        }
+        _ => return EventResult::Ignored,
+    }
+
+    EventResult::Consumed
+}
+
+/// Handle a click in Edit Vertices mode: grab the vertex under the mouse, or add a bend
+/// where the path was clicked. Returns false (and leaves the mode) for clicks elsewhere.
+pub fn handle_edit_vertices_mouse_down(state: &mut AppState, x: i32, y: i32) -> bool {
+    let Some((element_id, mut points, _)) = current(state) else {
+        state.exit_edit_vertices_mode();
+        return false;
+    };
+    let at = Coord { x, y };
+
+    if let Some(vertex) = points.iter().position(|&point| point == at) {
+        set_vertex(state, vertex);
+    } else if let Some(vertex) =
+        (0..points.len() - 1).find_map(|index| elements::insert_vertex(&mut points, index, at))
+    {
+        apply(state, element_id, &points, vertex);
+    } else {
+        state.exit_edit_vertices_mode();
+        return false;
+    }
+
+    if let Some(edit_state) = state.editing_vertices.as_mut() {
+        edit_state.dragging = true;
+    }
+    true
+}
+
+/// Move the grabbed vertex to the mouse position. Returns false if no vertex is grabbed.
+pub fn drag_vertex(state: &mut AppState, x: i32, y: i32) -> bool {
+    if !state
+        .editing_vertices
+        .as_ref()
+        .is_some_and(|edit_state| edit_state.dragging)
+    {
+        return false;
+    }
+    let Some((element_id, mut points, vertex)) = current(state) else {
+        return false;
+    };
+    if points[vertex] != (Coord { x, y }) {
+        let vertex = elements::move_vertex(&mut points, vertex, Coord { x, y });
+        apply(state, element_id, &points, vertex);
+    }
+    true
+}
+
+/// Let go of the grabbed vertex. Returns false if no vertex was grabbed.
+pub fn release_vertex(state: &mut AppState) -> bool {
+    match state.editing_vertices.as_mut() {
+        Some(edit_state) if edit_state.dragging => {
+            edit_state.dragging = false;
+            true
+        }
+        _ => false,
+    }
+}
+
+/// The edited element's ID, its vertices and the selected vertex
+fn current(state: &AppState) -> Option<(usize, Vec<Coord>, usize)> {
+    let edit_state = state.editing_vertices.as_ref()?;
+    let points = state
+        .canvas
+        .get_element(edit_state.element_id)?
+        .vertices()
+        .filter(|points| points.len() >= 2)?;
+    // Undo can shorten the path under us
+    let vertex = edit_state.vertex.min(points.len() - 1);
+    Some((edit_state.element_id, points, vertex))
+}
+
+/// Store the edited path; all changes in one session coalesce into one undo step
+fn apply(state: &mut AppState, element_id: usize, points: &[Coord], vertex: usize) {
+    state
+        .history
+        .set_pending_kind(EditKind::Vertices { element_id });
+    if let Some(element) = state.canvas.get_element_mut(element_id) {
+        let ends = element.endpoints();
+        element.set_vertices(points);
+        // Ends that moved come off their rectangles (and may attach to new ones below)
+        if element.endpoints() != ends
+            && let Some(slots) = element.attachments_mut()
+        {
+            for slot in slots {
+                *slot = None;
+            }
+        }
+    }
+    state.canvas.attach_connector(element_id);
+    set_vertex(state, vertex);
+}
+
+fn set_vertex(state: &mut AppState, vertex: usize) {
+    if let Some(edit_state) = state.editing_vertices.as_mut() {
+        edit_state.vertex = vertex;
+    }
+}
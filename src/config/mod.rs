@@ -0,0 +1,78 @@
+//! User configuration loaded from `~/.config/textdraw/config.toml`.
+//!
+//! Every section and key is optional; anything left out keeps its built-in default.
+//! Unknown sections or keys are rejected so that typos don't go unnoticed.
+
+#[cfg(test)]
+mod tests;
+
+use crate::elements::BorderStyle;
+use crate::keymap::{Keymap, KeymapConfig};
+use crate::ui::Theme;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    pub keymap: Keymap,
+    pub defaults: Defaults,
+    pub theme: Theme,
+}
+
+/// The config file as written; the keymap sections are resolved against the default
+/// bindings in `Config::parse`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    keymap: KeymapConfig,
+    defaults: Defaults,
+    theme: Theme,
+}
+
+impl Config {
+    /// Load the config file if there is one, otherwise use the defaults
+    pub fn load() -> Result<Self> {
+        match config_path() {
+            Some(path) if path.exists() => Self::load_from_file(&path),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    pub fn load_from_file(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+        Self::parse(&text).with_context(|| format!("Invalid config file: {}", path.display()))
+    }
+
+    /// Parse and validate the contents of a config file
+    pub fn parse(text: &str) -> Result<Self> {
+        let file: ConfigFile = toml::from_str(text)?;
+        Ok(Self {
+            keymap: Keymap::from_config(&file.keymap)?,
+            defaults: file.defaults,
+            theme: file.theme,
+        })
+    }
+}
+
+/// Defaults for new elements and the editor
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Defaults {
+    /// Border style of new rectangles
+    pub rectangle_border: BorderStyle,
+    /// Border style of new tables (header and body)
+    pub table_border: BorderStyle,
+    /// Start with the tool lock on, so tools stay active after drawing
+    pub tool_lock: bool,
+}
+
+/// `$XDG_CONFIG_HOME/textdraw/config.toml`, falling back to `~/.config/textdraw/config.toml`
+pub fn config_path() -> Option<PathBuf> {
+    let base = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+    };
+    Some(base.join("textdraw").join("config.toml"))
+}
@@ -0,0 +1,107 @@
+use super::Config;
+use crate::elements::BorderStyle;
+use crate::keymap::{Action, Context, KeyBinding};
+use crate::tools::Tool;
+use ratatui::style::Color;
+
+#[test]
+fn test_empty_config_uses_defaults() {
+    let config = Config::parse("").unwrap();
+
+    assert_eq!(
+        config.keymap.tool_key(Tool::Rectangle),
+        Some(KeyBinding::parse("r").unwrap())
+    );
+    assert!(!config.defaults.tool_lock);
+    assert_eq!(config.theme.primary, Color::Cyan);
+}
+
+#[test]
+fn test_full_config() {
+    let config = Config::parse(
+        r##"
+[keymap.global]
+rectangle = "b"
+table = "T"
+quit = ["Q", "Ctrl+q"]
+
+[defaults]
+rectangle_border = "Rounded"
+table_border = "Double"
+tool_lock = true
+
+[theme]
+primary = "magenta"
+hint = "#ff8800"
+"##,
+    )
+    .unwrap();
+
+    let keymap = &config.keymap;
+    assert_eq!(
+        keymap.label(Context::Global, Action::SelectTool(Tool::Rectangle)),
+        "b"
+    );
+    assert_eq!(
+        keymap.label(Context::Global, Action::SelectTool(Tool::Table)),
+        "T"
+    );
+    assert_eq!(keymap.label(Context::Global, Action::Quit), "Q/Ctrl+Q");
+    assert_eq!(keymap.label(Context::Global, Action::Undo), "u/Ctrl+Z");
+
+    assert_eq!(config.defaults.rectangle_border, BorderStyle::Rounded);
+    assert_eq!(config.defaults.table_border, BorderStyle::Double);
+    assert!(config.defaults.tool_lock);
+
+    assert_eq!(config.theme.primary, Color::Magenta);
+    assert_eq!(config.theme.hint, Color::Rgb(0xff, 0x88, 0x00));
+    assert_eq!(config.theme.error, Color::Red);
+}
+
+fn parse_error(text: &str) -> String {
+    format!("{:#}", Config::parse(text).unwrap_err())
+}
+
+#[test]
+fn test_unknown_keys_are_rejected() {
+    let error = parse_error("[keymap.global]\nrectangel = \"b\"\n");
+    assert!(
+        error.contains("unknown action `rectangel` in [keymap.global]"),
+        "{}",
+        error
+    );
+
+    let error = parse_error("[keymap.canvs]\npan_up = \"K\"\n");
+    assert!(error.contains("unknown field `canvs`"), "{}", error);
+
+    let error = parse_error("[colors]\nprimary = \"red\"\n");
+    assert!(error.contains("unknown field `colors`"), "{}", error);
+}
+
+#[test]
+fn test_invalid_values_are_rejected() {
+    let error = parse_error("[defaults]\nrectangle_border = \"Dotted\"\n");
+    assert!(error.contains("unknown variant `Dotted`"), "{}", error);
+
+    let error = parse_error("[theme]\nprimary = \"not a color\"\n");
+    assert!(error.contains("primary"), "{}", error);
+
+    let error = parse_error("[keymap.global]\nquit = \"Hyper+q\"\n");
+    assert!(
+        error.contains("invalid key for `quit` in [keymap.global]"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_duplicate_keys_are_rejected() {
+    let error = parse_error("[keymap.global]\nquit = \"l\"\n");
+    assert_eq!(
+        error,
+        "key l is bound to both `line` and `quit` in [keymap.global]"
+    );
+
+    // The same key may be used again in another context
+    assert!(Config::parse("[keymap.table_edit]\ncancel = \"u\"\n").is_ok());
+}
@@ -1,17 +1,29 @@
-use super::Segment;
 use super::segment;
+use super::{
+    Attachment, HasProperties, HeadStyle, LineStyle, PropertiesSpec, PropertyValue, Segment,
+};
 use crate::types::{Bounds, Direction, RenderPoint};
+use anyhow::{Result, bail};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ArrowElement {
     pub id: usize,
     pub name: String,
+    /// ID of the layer the element is drawn on
+    #[serde(default)]
+    pub layer: usize,
     pub segments: Vec<Segment>,
     pub bounds: Bounds,
     pub head_start: char,
     pub head_end: char,
+    #[serde(default)]
+    pub line_style: LineStyle,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_attachment: Option<Attachment>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_attachment: Option<Attachment>,
 }
 
 impl ArrowElement {
@@ -20,28 +32,47 @@ impl ArrowElement {
         let bounds = segment::calculate_bounds(&segments);
 
         // Determine arrowhead based on last segment direction
-        let head_end = if let Some(last_seg) = segments.last() {
-            match last_seg.direction {
-                Direction::Right => '▶',
-                Direction::Left => '◀',
-                Direction::Down => '▼',
-                Direction::Up => '▲',
-            }
-        } else {
-            '▶'
-        };
+        let head_end = segments
+            .last()
+            .map_or('▶', |last_seg| Self::head_char(last_seg.direction));
 
         Self {
             id,
             name,
+            layer: 0,
             segments,
             bounds,
             head_start: ' ',
             head_end,
+            line_style: LineStyle::default(),
+            start_attachment: None,
+            end_attachment: None,
         }
     }
 
-    pub fn translate(&mut self, dx: i16, dy: i16) {
+    /// Arrowhead character pointing in the given direction
+    pub fn head_char(direction: Direction) -> char {
+        HeadStyle::Triangle.char(direction)
+    }
+
+    /// Replace the path, keeping the arrowheads pointing along the new ends
+    pub fn set_segments(&mut self, segments: Vec<Segment>) {
+        self.segments = segments;
+        self.bounds = segment::calculate_bounds(&self.segments);
+        self.set_heads(HeadStyle::of(self.head_start), HeadStyle::of(self.head_end));
+    }
+
+    /// Set the style of both arrowheads, pointing them along the path's ends
+    pub fn set_heads(&mut self, start: HeadStyle, end: HeadStyle) {
+        if let Some(first) = self.segments.first() {
+            self.head_start = start.char(first.direction.opposite());
+        }
+        if let Some(last) = self.segments.last() {
+            self.head_end = end.char(last.direction);
+        }
+    }
+
+    pub fn translate(&mut self, dx: i32, dy: i32) {
         for segment in &mut self.segments {
             segment.translate(dx, dy);
         }
@@ -79,14 +110,14 @@ impl ArrowElement {
 
         // Find the start and end points for arrowheads
         let start_point = if let Some(first_seg) = self.segments.first() {
-            Some((first_seg.start.x as i32, first_seg.start.y as i32))
+            Some((first_seg.start.x, first_seg.start.y))
         } else {
             None
         };
 
         let end_point = if let Some(last_seg) = self.segments.last() {
-            let x = last_seg.start.x as i32;
-            let y = last_seg.start.y as i32;
+            let x = last_seg.start.x;
+            let y = last_seg.start.y;
             Some(match last_seg.direction {
                 Direction::Right => (x + last_seg.length as i32, y),
                 Direction::Left => (x - last_seg.length as i32, y),
@@ -106,7 +137,7 @@ impl ArrowElement {
                 } else if Some(pos) == end_point && self.head_end != ' ' {
                     self.head_end
                 } else {
-                    directions_to_char(&dirs)
+                    self.line_style.restyle(directions_to_char(&dirs))
                 };
                 (pos.0, pos.1, ch)
             })
@@ -114,10 +145,52 @@ impl ArrowElement {
     }
 }
 
+impl HasProperties for ArrowElement {
+    fn properties_spec(&self) -> PropertiesSpec {
+        PropertiesSpec::new()
+            .section("Style", |s| {
+                s.choice("line_style", "line-style", LineStyle::all_options())
+            })
+            .section("Heads", |s| {
+                s.choice("head_start", "start", HeadStyle::all_options())
+                    .choice("head_end", "end", HeadStyle::all_options())
+            })
+    }
+
+    fn get_property(&self, name: &str) -> Option<PropertyValue> {
+        use PropertyValue::*;
+        let value = match name {
+            "line_style" => Choice(self.line_style.as_str().to_string()),
+            "head_start" => Choice(HeadStyle::of(self.head_start).as_str().to_string()),
+            "head_end" => Choice(HeadStyle::of(self.head_end).as_str().to_string()),
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    fn set_property(&mut self, name: &str, value: PropertyValue) -> Result<()> {
+        match name {
+            "line_style" => {
+                self.line_style = LineStyle::from_str(value.as_choice()?)?;
+            }
+            "head_start" => {
+                let start = HeadStyle::from_str(value.as_choice()?)?;
+                self.set_heads(start, HeadStyle::of(self.head_end));
+            }
+            "head_end" => {
+                let end = HeadStyle::from_str(value.as_choice()?)?;
+                self.set_heads(HeadStyle::of(self.head_start), end);
+            }
+            _ => bail!("Unknown property: {}", name),
+        }
+        Ok(())
+    }
+}
+
 /// Get all points for a segment
 fn get_segment_points(segment: &Segment) -> Vec<(i32, i32)> {
-    let x = segment.start.x as i32;
-    let y = segment.start.y as i32;
+    let x = segment.start.x;
+    let y = segment.start.y;
     let mut points = Vec::new();
 
     match segment.direction {
@@ -0,0 +1,148 @@
+//! Attachments that keep line and arrow ends connected to rectangles, and the
+//! routing used to redraw a connector when the rectangles it joins change.
+
+use super::Segment;
+use crate::types::{Bounds, Coord, Direction};
+use serde::{Deserialize, Serialize};
+
+/// Side of a rectangle that a connector end is attached to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Side {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl Side {
+    /// Direction a connector leaves the rectangle in from this side
+    pub fn outward(self) -> Direction {
+        match self {
+            Side::Top => Direction::Up,
+            Side::Bottom => Direction::Down,
+            Side::Left => Direction::Left,
+            Side::Right => Direction::Right,
+        }
+    }
+}
+
+/// A connector end attached to another element. The end sits in the cell just
+/// outside `side`, `offset` cells from the side's top/left corner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attachment {
+    pub element_id: usize,
+    pub side: Side,
+    pub offset: u16,
+}
+
+impl Attachment {
+    /// Attachment for a connector end at `point`, if it lies just outside one side
+    /// of `bounds` (not diagonally off a corner)
+    pub fn find(element_id: usize, bounds: &Bounds, point: Coord) -> Option<Self> {
+        let (min, max) = (bounds.min, bounds.max);
+        let inside_x = point.x > min.x && point.x < max.x;
+        let inside_y = point.y > min.y && point.y < max.y;
+
+        let (side, offset) = if inside_x && point.y == min.y - 1 {
+            (Side::Top, point.x - min.x)
+        } else if inside_x && point.y == max.y + 1 {
+            (Side::Bottom, point.x - min.x)
+        } else if inside_y && point.x == min.x - 1 {
+            (Side::Left, point.y - min.y)
+        } else if inside_y && point.x == max.x + 1 {
+            (Side::Right, point.y - min.y)
+        } else {
+            return None;
+        };
+
+        Some(Self {
+            element_id,
+            side,
+            offset: offset as u16,
+        })
+    }
+
+    /// Cell the connector end sits in for the element's current bounds.
+    /// The offset is clamped so the end stays off the corners after a resize.
+    pub fn anchor(&self, bounds: &Bounds) -> Coord {
+        let (min, max) = (bounds.min, bounds.max);
+        let along = |length: i32| (self.offset as i32).clamp(1, (length - 1).max(1));
+
+        match self.side {
+            Side::Top => Coord {
+                x: min.x + along(max.x - min.x),
+                y: min.y - 1,
+            },
+            Side::Bottom => Coord {
+                x: min.x + along(max.x - min.x),
+                y: max.y + 1,
+            },
+            Side::Left => Coord {
+                x: min.x - 1,
+                y: min.y + along(max.y - min.y),
+            },
+            Side::Right => Coord {
+                x: max.x + 1,
+                y: min.y + along(max.y - min.y),
+            },
+        }
+    }
+}
+
+/// Route a connector from `start` to `end` with horizontal and vertical segments.
+///
+/// `start_out` and `end_out` are the directions the ends leave their rectangles in,
+/// when attached. Ends leaving sideways get a horizontal first/last segment and ends
+/// leaving through the top or bottom a vertical one, giving an L or Z shaped path.
+pub fn route(
+    start: Coord,
+    start_out: Option<Direction>,
+    end: Coord,
+    end_out: Option<Direction>,
+) -> Vec<Segment> {
+    let is_horizontal = |dir: Direction| matches!(dir, Direction::Left | Direction::Right);
+
+    let start_horizontal = match (start_out, end_out) {
+        (Some(dir), _) => is_horizontal(dir),
+        (None, Some(dir)) => !is_horizontal(dir),
+        (None, None) => (end.x - start.x).abs() >= (end.y - start.y).abs(),
+    };
+    let end_horizontal = end_out.map_or(!start_horizontal, is_horizontal);
+
+    let mut points = vec![start];
+    if start.x != end.x && start.y != end.y {
+        match (start_horizontal, end_horizontal) {
+            (true, true) => {
+                let mid_x = (start.x + end.x) / 2;
+                points.push(Coord {
+                    x: mid_x,
+                    y: start.y,
+                });
+                points.push(Coord { x: mid_x, y: end.y });
+            }
+            (false, false) => {
+                let mid_y = (start.y + end.y) / 2;
+                points.push(Coord {
+                    x: start.x,
+                    y: mid_y,
+                });
+                points.push(Coord { x: end.x, y: mid_y });
+            }
+            (true, false) => points.push(Coord {
+                x: end.x,
+                y: start.y,
+            }),
+            (false, true) => points.push(Coord {
+                x: start.x,
+                y: end.y,
+            }),
+        }
+    }
+    points.push(end);
+
+    let segments = Segment::path(&points);
+    if segments.is_empty() {
+        return vec![Segment::from_coords(start, end)];
+    }
+    segments
+}
@@ -0,0 +1,167 @@
+use serde::{Deserialize, Serialize};
+
+/// Horizontal placement of a label inside its box
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum HorizontalAlign {
+    Left,
+    #[default]
+    Center,
+    Right,
+}
+
+impl HorizontalAlign {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HorizontalAlign::Left => "Left",
+            HorizontalAlign::Center => "Center",
+            HorizontalAlign::Right => "Right",
+        }
+    }
+
+    pub fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "Left" => Ok(HorizontalAlign::Left),
+            "Center" => Ok(HorizontalAlign::Center),
+            "Right" => Ok(HorizontalAlign::Right),
+            _ => anyhow::bail!("Invalid horizontal alignment: {}", s),
+        }
+    }
+
+    /// Get all alignments as strings (for property choices)
+    pub fn all_options() -> Vec<String> {
+        vec![
+            "Left".to_string(),
+            "Center".to_string(),
+            "Right".to_string(),
+        ]
+    }
+
+    /// Offset of a line of `len` characters within `width` columns
+    fn offset(&self, len: usize, width: usize) -> usize {
+        let free = width.saturating_sub(len);
+        match self {
+            HorizontalAlign::Left => 0,
+            HorizontalAlign::Center => free / 2,
+            HorizontalAlign::Right => free,
+        }
+    }
+}
+
+/// Vertical placement of a label inside its box
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum VerticalAlign {
+    Top,
+    #[default]
+    Middle,
+    Bottom,
+}
+
+impl VerticalAlign {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VerticalAlign::Top => "Top",
+            VerticalAlign::Middle => "Middle",
+            VerticalAlign::Bottom => "Bottom",
+        }
+    }
+
+    pub fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "Top" => Ok(VerticalAlign::Top),
+            "Middle" => Ok(VerticalAlign::Middle),
+            "Bottom" => Ok(VerticalAlign::Bottom),
+            _ => anyhow::bail!("Invalid vertical alignment: {}", s),
+        }
+    }
+
+    /// Get all alignments as strings (for property choices)
+    pub fn all_options() -> Vec<String> {
+        vec![
+            "Top".to_string(),
+            "Middle".to_string(),
+            "Bottom".to_string(),
+        ]
+    }
+
+    /// Offset of a block of `lines` rows within `height` rows
+    fn offset(&self, lines: usize, height: usize) -> usize {
+        let free = height.saturating_sub(lines);
+        match self {
+            VerticalAlign::Top => 0,
+            VerticalAlign::Middle => free / 2,
+            VerticalAlign::Bottom => free,
+        }
+    }
+}
+
+/// One wrapped line of a label
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LabelLine {
+    /// Character index in the label where this line starts
+    pub start: usize,
+    pub text: String,
+}
+
+/// Word-wrap text to `width` columns. Explicit newlines start a new line, words
+/// longer than the width are broken, and the spaces a line breaks at are dropped.
+pub fn wrap(text: &str, width: usize) -> Vec<LabelLine> {
+    let width = width.max(1);
+    let chars: Vec<char> = text.chars().collect();
+    let mut lines = Vec::new();
+
+    let mut paragraph_start = 0;
+    for paragraph in text.split('\n') {
+        let len = paragraph.chars().count();
+        let mut pos = paragraph_start;
+        let end = paragraph_start + len;
+
+        loop {
+            if end - pos <= width {
+                lines.push(LabelLine {
+                    start: pos,
+                    text: chars[pos..end].iter().collect(),
+                });
+                break;
+            }
+
+            // Break at the last space that fits, or mid-word if there is none
+            let break_at = (pos + 1..=pos + width)
+                .rev()
+                .find(|&i| chars[i] == ' ')
+                .unwrap_or(pos + width);
+            lines.push(LabelLine {
+                start: pos,
+                text: chars[pos..break_at].iter().collect(),
+            });
+            pos = break_at;
+            while pos < end && chars[pos] == ' ' {
+                pos += 1;
+            }
+        }
+
+        paragraph_start = end + 1;
+    }
+
+    lines
+}
+
+/// Position wrapped lines in a `width` x `height` area. Returns (column, row, line)
+/// relative to the area, dropping lines that don't fit.
+pub fn layout(
+    lines: Vec<LabelLine>,
+    width: usize,
+    height: usize,
+    align: HorizontalAlign,
+    valign: VerticalAlign,
+) -> Vec<(usize, usize, LabelLine)> {
+    let top = valign.offset(lines.len(), height);
+    lines
+        .into_iter()
+        .take(height)
+        .enumerate()
+        .map(|(row, line)| {
+            let column = align.offset(line.text.chars().count(), width);
+            (column, top + row, line)
+        })
+        .collect()
+}
@@ -1,15 +1,25 @@
-use super::Segment;
 use super::segment;
+use super::{Attachment, HasProperties, LineStyle, PropertiesSpec, PropertyValue, Segment};
 use crate::types::{Bounds, Direction, RenderPoint};
+use anyhow::{Result, bail};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LineElement {
     pub id: usize,
     pub name: String,
+    /// ID of the layer the element is drawn on
+    #[serde(default)]
+    pub layer: usize,
     pub segments: Vec<Segment>,
     pub bounds: Bounds,
+    #[serde(default)]
+    pub line_style: LineStyle,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_attachment: Option<Attachment>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_attachment: Option<Attachment>,
 }
 
 impl LineElement {
@@ -19,12 +29,22 @@ impl LineElement {
         Self {
             id,
             name,
+            layer: 0,
             segments,
             bounds,
+            line_style: LineStyle::default(),
+            start_attachment: None,
+            end_attachment: None,
         }
     }
 
-    pub fn translate(&mut self, dx: i16, dy: i16) {
+    /// Replace the path
+    pub fn set_segments(&mut self, segments: Vec<Segment>) {
+        self.bounds = segment::calculate_bounds(&segments);
+        self.segments = segments;
+    }
+
+    pub fn translate(&mut self, dx: i32, dy: i32) {
         for segment in &mut self.segments {
             segment.translate(dx, dy);
         }
@@ -63,15 +83,46 @@ impl LineElement {
         // Convert points with their direction sets to render points with proper characters
         point_connections
             .into_iter()
-            .map(|(pos, dirs)| (pos.0, pos.1, directions_to_char(&dirs)))
+            .map(|(pos, dirs)| {
+                (
+                    pos.0,
+                    pos.1,
+                    self.line_style.restyle(directions_to_char(&dirs)),
+                )
+            })
             .collect()
     }
 }
 
+impl HasProperties for LineElement {
+    fn properties_spec(&self) -> PropertiesSpec {
+        PropertiesSpec::new().section("Style", |s| {
+            s.choice("line_style", "line-style", LineStyle::all_options())
+        })
+    }
+
+    fn get_property(&self, name: &str) -> Option<PropertyValue> {
+        match name {
+            "line_style" => Some(PropertyValue::Choice(self.line_style.as_str().to_string())),
+            _ => None,
+        }
+    }
+
+    fn set_property(&mut self, name: &str, value: PropertyValue) -> Result<()> {
+        match name {
+            "line_style" => {
+                self.line_style = LineStyle::from_str(value.as_choice()?)?;
+            }
+            _ => bail!("Unknown property: {}", name),
+        }
+        Ok(())
+    }
+}
+
 /// Get all points for a segment
 fn get_segment_points(segment: &Segment) -> Vec<(i32, i32)> {
-    let x = segment.start.x as i32;
-    let y = segment.start.y as i32;
+    let x = segment.start.x;
+    let y = segment.start.y;
     let mut points = Vec::new();
 
     match segment.direction {
@@ -0,0 +1,165 @@
+use crate::types::Direction;
+use serde::{Deserialize, Serialize};
+
+/// Stroke used to draw a line or arrow
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum LineStyle {
+    #[default]
+    Single,
+    Double,
+    Bold,
+    Dashed,
+}
+
+impl LineStyle {
+    /// Redraw a single-stroke box drawing character in this style
+    pub fn restyle(self, ch: char) -> char {
+        match self {
+            LineStyle::Single => ch,
+            LineStyle::Double => match ch {
+                '─' => '═',
+                '│' => '║',
+                '└' => '╚',
+                '┘' => '╝',
+                '┌' => '╔',
+                '┐' => '╗',
+                '├' => '╠',
+                '┤' => '╣',
+                '┬' => '╦',
+                '┴' => '╩',
+                '┼' => '╬',
+                _ => ch,
+            },
+            LineStyle::Bold => match ch {
+                '─' => '━',
+                '│' => '┃',
+                '└' => '┗',
+                '┘' => '┛',
+                '┌' => '┏',
+                '┐' => '┓',
+                '├' => '┣',
+                '┤' => '┫',
+                '┬' => '┳',
+                '┴' => '┻',
+                '┼' => '╋',
+                _ => ch,
+            },
+            // Corners and junctions have no dashed form
+            LineStyle::Dashed => match ch {
+                '─' => '╌',
+                '│' => '╎',
+                _ => ch,
+            },
+        }
+    }
+
+    /// Convert to string representation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineStyle::Single => "Single",
+            LineStyle::Double => "Double",
+            LineStyle::Bold => "Bold",
+            LineStyle::Dashed => "Dashed",
+        }
+    }
+
+    /// Parse from string representation
+    pub fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "Single" => Ok(LineStyle::Single),
+            "Double" => Ok(LineStyle::Double),
+            "Bold" => Ok(LineStyle::Bold),
+            "Dashed" => Ok(LineStyle::Dashed),
+            _ => anyhow::bail!("Invalid line style: {}", s),
+        }
+    }
+
+    /// Get all available line styles as strings (for property choices)
+    pub fn all_options() -> Vec<String> {
+        vec![
+            "Single".to_string(),
+            "Double".to_string(),
+            "Bold".to_string(),
+            "Dashed".to_string(),
+        ]
+    }
+}
+
+/// Marker drawn at either end of an arrow. Arrows store the head character itself, so
+/// the style is read back from it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeadStyle {
+    None,
+    Triangle,
+    Ascii,
+    Dot,
+    Diamond,
+}
+
+impl HeadStyle {
+    /// Style of a stored head character
+    pub fn of(ch: char) -> Self {
+        match ch {
+            '▶' | '◀' | '▼' | '▲' => HeadStyle::Triangle,
+            '>' | '<' | 'v' | '^' => HeadStyle::Ascii,
+            '●' => HeadStyle::Dot,
+            '◆' => HeadStyle::Diamond,
+            _ => HeadStyle::None,
+        }
+    }
+
+    /// Head character pointing in the given direction (a space for no head)
+    pub fn char(self, direction: Direction) -> char {
+        match self {
+            HeadStyle::None => ' ',
+            HeadStyle::Triangle => match direction {
+                Direction::Right => '▶',
+                Direction::Left => '◀',
+                Direction::Down => '▼',
+                Direction::Up => '▲',
+            },
+            HeadStyle::Ascii => match direction {
+                Direction::Right => '>',
+                Direction::Left => '<',
+                Direction::Down => 'v',
+                Direction::Up => '^',
+            },
+            HeadStyle::Dot => '●',
+            HeadStyle::Diamond => '◆',
+        }
+    }
+
+    /// Convert to string representation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HeadStyle::None => "None",
+            HeadStyle::Triangle => "Triangle",
+            HeadStyle::Ascii => "ASCII",
+            HeadStyle::Dot => "Dot",
+            HeadStyle::Diamond => "Diamond",
+        }
+    }
+
+    /// Parse from string representation
+    pub fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "None" => Ok(HeadStyle::None),
+            "Triangle" => Ok(HeadStyle::Triangle),
+            "ASCII" => Ok(HeadStyle::Ascii),
+            "Dot" => Ok(HeadStyle::Dot),
+            "Diamond" => Ok(HeadStyle::Diamond),
+            _ => anyhow::bail!("Invalid head style: {}", s),
+        }
+    }
+
+    /// Get all available head styles as strings (for property choices)
+    pub fn all_options() -> Vec<String> {
+        vec![
+            "None".to_string(),
+            "Triangle".to_string(),
+            "ASCII".to_string(),
+            "Dot".to_string(),
+            "Diamond".to_string(),
+        ]
+    }
+}
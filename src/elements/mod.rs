@@ -1,26 +1,38 @@
 mod arrow;
 mod borders;
+mod connector;
+mod label;
 mod line;
+mod line_style;
 mod properties;
 mod rectangle;
+mod routing;
 mod segment;
 mod table;
 mod text;
+mod vertices;
 
 pub use arrow::ArrowElement;
 pub use borders::{BorderChars, BorderStyle};
+pub use connector::{Attachment, route};
 pub use line::LineElement;
+pub use line_style::{HeadStyle, LineStyle};
 pub use properties::{
     FieldType, HasProperties, PropertiesSpec, PropertyField, PropertySection, PropertyValue,
 };
 pub use rectangle::RectangleElement;
+pub use routing::route_around;
 pub use segment::Segment;
 pub use table::TableElement;
 pub use text::TextElement;
+pub use vertices::{insert_vertex, move_vertex, remove_vertex};
 
-use crate::types::Bounds;
+use crate::types::{Bounds, Coord, ResizeHandle};
 use serde::{Deserialize, Serialize};
 
+/// Longest element name accepted from the properties panel or `:set`
+pub const MAX_NAME_LENGTH: usize = 40;
+
 macro_rules! delegate_element {
     ($self:expr, $field:ident) => {
         match $self {
@@ -42,7 +54,7 @@ macro_rules! delegate_element {
     };
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Element {
     Line(LineElement),
     Rectangle(RectangleElement),
@@ -60,6 +72,31 @@ impl Element {
         delegate_element!(self, name)
     }
 
+    pub fn set_name(&mut self, name: String) {
+        match self {
+            Element::Line(e) => e.name = name,
+            Element::Rectangle(e) => e.name = name,
+            Element::Arrow(e) => e.name = name,
+            Element::Text(e) => e.name = name,
+            Element::Table(e) => e.name = name,
+        }
+    }
+
+    /// ID of the layer the element is drawn on
+    pub fn layer(&self) -> usize {
+        *delegate_element!(self, layer)
+    }
+
+    pub fn set_layer(&mut self, layer: usize) {
+        match self {
+            Element::Line(e) => e.layer = layer,
+            Element::Rectangle(e) => e.layer = layer,
+            Element::Arrow(e) => e.layer = layer,
+            Element::Text(e) => e.layer = layer,
+            Element::Table(e) => e.layer = layer,
+        }
+    }
+
     pub fn type_name(&self) -> &'static str {
         match self {
             Element::Line(_) => "Line",
@@ -70,21 +107,115 @@ impl Element {
         }
     }
 
+    /// Clone this element under a new ID, with the default name for that ID
+    pub fn duplicate(&self, id: usize) -> Element {
+        let mut element = self.clone();
+        let name = format!("{} {}", self.type_name(), id + 1);
+        match &mut element {
+            Element::Line(e) => (e.id, e.name) = (id, name),
+            Element::Rectangle(e) => (e.id, e.name) = (id, name),
+            Element::Arrow(e) => (e.id, e.name) = (id, name),
+            Element::Text(e) => (e.id, e.name) = (id, name),
+            Element::Table(e) => (e.id, e.name) = (id, name),
+        }
+        element
+    }
+
     pub fn bounds(&self) -> Bounds {
         *delegate_element!(self, bounds)
     }
 
-    pub fn translate(&mut self, dx: i16, dy: i16) {
+    pub fn translate(&mut self, dx: i32, dy: i32) {
         delegate_element!(self, translate(dx, dy))
     }
 
+    /// Rectangles and tables get resize handles when selected on their own
+    pub fn is_resizable(&self) -> bool {
+        matches!(self, Element::Rectangle(_) | Element::Table(_))
+    }
+
+    /// Drag a resize handle to (x, y) (no-op for elements that aren't resizable)
+    pub fn resize(&mut self, handle: ResizeHandle, x: i32, y: i32) {
+        let bounds = handle.drag(&self.bounds(), x, y);
+        match self {
+            Element::Rectangle(e) => e.resize(bounds),
+            Element::Table(e) => e.resize(bounds, handle.moves_left(), handle.moves_top()),
+            _ => {}
+        }
+    }
+
+    /// Start and end attachments of a line or arrow
+    pub fn attachments(&self) -> [Option<Attachment>; 2] {
+        match self {
+            Element::Line(e) => [e.start_attachment, e.end_attachment],
+            Element::Arrow(e) => [e.start_attachment, e.end_attachment],
+            _ => [None, None],
+        }
+    }
+
+    /// Mutable start and end attachments, for lines and arrows only
+    pub fn attachments_mut(&mut self) -> Option<[&mut Option<Attachment>; 2]> {
+        match self {
+            Element::Line(e) => Some([&mut e.start_attachment, &mut e.end_attachment]),
+            Element::Arrow(e) => Some([&mut e.start_attachment, &mut e.end_attachment]),
+            _ => None,
+        }
+    }
+
+    /// First and last points of a line or arrow
+    pub fn endpoints(&self) -> Option<(Coord, Coord)> {
+        let segments = match self {
+            Element::Line(e) => &e.segments,
+            Element::Arrow(e) => &e.segments,
+            _ => return None,
+        };
+        Some((segments.first()?.start, segments.last()?.end()))
+    }
+
+    /// Start, bends and end of a line or arrow
+    pub fn vertices(&self) -> Option<Vec<Coord>> {
+        match self {
+            Element::Line(e) => Some(vertices::vertices(&e.segments)),
+            Element::Arrow(e) => Some(vertices::vertices(&e.segments)),
+            _ => None,
+        }
+    }
+
+    /// Replace the path of a line or arrow with one segment per pair of points.
+    /// Ignored if the points don't make up at least one segment.
+    pub fn set_vertices(&mut self, points: &[Coord]) {
+        let segments = Segment::polyline(points);
+        if !segments.is_empty() {
+            self.set_segments(segments);
+        }
+    }
+
+    /// Swap the start and end of a line or arrow, along with their attachments.
+    /// Arrowheads stay at the same end of the path, so an arrow points the other way.
+    pub fn reverse(&mut self) {
+        let Some(mut points) = self.vertices() else {
+            return;
+        };
+        points.reverse();
+        self.set_vertices(&points);
+        if let Some([start, end]) = self.attachments_mut() {
+            std::mem::swap(start, end);
+        }
+    }
+
+    /// Replace the path of a line or arrow (no-op for other elements)
+    pub fn set_segments(&mut self, segments: Vec<Segment>) {
+        match self {
+            Element::Line(e) => e.set_segments(segments),
+            Element::Arrow(e) => e.set_segments(segments),
+            _ => {}
+        }
+    }
+
     /// Check if a point is within the element's bounding box
     pub fn point_in_bounds(&self, x: i32, y: i32) -> bool {
         let bounds = self.bounds();
-        x >= bounds.min.x as i32
-            && x <= bounds.max.x as i32
-            && y >= bounds.min.y as i32
-            && y <= bounds.max.y as i32
+        x >= bounds.min.x && x <= bounds.max.x && y >= bounds.min.y && y <= bounds.max.y
     }
 
     /// Check if a point contains actual content (for precise selection)
@@ -96,10 +227,10 @@ impl Element {
     /// Check if element is fully inside rectangle
     pub fn is_fully_inside_rect(&self, x1: i32, y1: i32, x2: i32, y2: i32) -> bool {
         let bounds = self.bounds();
-        let ex1 = bounds.min.x as i32;
-        let ey1 = bounds.min.y as i32;
-        let ex2 = bounds.max.x as i32;
-        let ey2 = bounds.max.y as i32;
+        let ex1 = bounds.min.x;
+        let ey1 = bounds.min.y;
+        let ex2 = bounds.max.x;
+        let ey2 = bounds.max.y;
         ex1 >= x1 && ex2 <= x2 && ey1 >= y1 && ey2 <= y2
     }
 
@@ -108,30 +239,52 @@ impl Element {
         delegate_element!(self, render_points())
     }
 
-    /// Get properties spec (returns empty spec for elements without properties)
+    /// Get properties spec. Every element starts with its name, followed by the
+    /// properties of its type.
     pub fn properties_spec(&self) -> PropertiesSpec {
-        match self {
+        let mut spec = match self {
+            Element::Line(line) => line.properties_spec(),
             Element::Rectangle(rect) => rect.properties_spec(),
+            Element::Arrow(arrow) => arrow.properties_spec(),
+            Element::Text(text) => text.properties_spec(),
             Element::Table(table) => table.properties_spec(),
-            _ => PropertiesSpec::default(),
-        }
+        };
+        let section = PropertySection::new("Element").text("name", "name", MAX_NAME_LENGTH);
+        spec.sections.insert(0, section);
+        spec
     }
 
     /// Get property value by name
     pub fn get_property(&self, name: &str) -> Option<PropertyValue> {
         match self {
+            _ if name == "name" => Some(PropertyValue::Text(self.name().to_string())),
+            Element::Line(line) => line.get_property(name),
             Element::Rectangle(rect) => rect.get_property(name),
+            Element::Arrow(arrow) => arrow.get_property(name),
+            Element::Text(text) => text.get_property(name),
             Element::Table(table) => table.get_property(name),
-            _ => None,
         }
     }
 
     /// Set property value by name
     pub fn set_property(&mut self, name: &str, value: PropertyValue) -> anyhow::Result<()> {
         match self {
+            _ if name == "name" => {
+                let name = value.as_text()?.trim();
+                if name.is_empty() {
+                    anyhow::bail!("Name cannot be empty");
+                }
+                if name.chars().count() > MAX_NAME_LENGTH {
+                    anyhow::bail!("Name is longer than {} characters", MAX_NAME_LENGTH);
+                }
+                self.set_name(name.to_string());
+                Ok(())
+            }
+            Element::Line(line) => line.set_property(name, value),
             Element::Rectangle(rect) => rect.set_property(name, value),
+            Element::Arrow(arrow) => arrow.set_property(name, value),
+            Element::Text(text) => text.set_property(name, value),
             Element::Table(table) => table.set_property(name, value),
-            _ => Ok(()), // No-op for elements without properties
         }
     }
 }
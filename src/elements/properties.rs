@@ -52,6 +52,29 @@ impl PropertiesSpec {
     pub fn get_field(&self, name: &str) -> Option<&PropertyField> {
         self.all_fields().into_iter().find(|f| f.name == name)
     }
+
+    /// Keep only the fields `other` also has with the same type, dropping sections
+    /// left empty
+    pub fn intersect(mut self, other: &PropertiesSpec) -> Self {
+        for section in &mut self.sections {
+            section.fields.retain(|field| {
+                other
+                    .get_field(&field.name)
+                    .is_some_and(|f| f.field_type == field.field_type)
+            });
+        }
+        self.sections.retain(|section| !section.fields.is_empty());
+        self
+    }
+
+    /// Drop the field with the given name, and its section if that leaves it empty
+    pub fn without(mut self, name: &str) -> Self {
+        for section in &mut self.sections {
+            section.fields.retain(|field| field.name != name);
+        }
+        self.sections.retain(|section| !section.fields.is_empty());
+        self
+    }
 }
 
 impl Default for PropertiesSpec {
@@ -84,8 +107,8 @@ impl PropertySection {
         mut self,
         name: impl Into<String>,
         label: impl Into<String>,
-        min: u16,
-        max: u16,
+        min: i32,
+        max: i32,
     ) -> Self {
         self.fields.push(PropertyField::new(
             name,
@@ -152,18 +175,53 @@ impl PropertyField {
 }
 
 /// Type of a property field
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum FieldType {
-    Numeric { min: u16, max: u16 },
+    Numeric { min: i32, max: i32 },
     Text { max_length: usize },
     Choice { options: Vec<String> },
     Boolean,
 }
 
+impl FieldType {
+    /// Parse a value typed as text (e.g. `:set width=20`), checking it against the field
+    pub fn parse_value(&self, text: &str) -> Result<PropertyValue> {
+        match self {
+            FieldType::Numeric { min, max } => {
+                let Ok(n) = text.parse::<i32>() else {
+                    bail!("{} is not a number", text);
+                };
+                if n < *min || n > *max {
+                    bail!("{} is out of range ({} to {})", n, min, max);
+                }
+                Ok(PropertyValue::Numeric(n))
+            }
+            FieldType::Text { max_length } => {
+                if text.chars().count() > *max_length {
+                    bail!("Text is longer than {} characters", max_length);
+                }
+                Ok(PropertyValue::Text(text.to_string()))
+            }
+            FieldType::Choice { options } => match options
+                .iter()
+                .find(|option| option.eq_ignore_ascii_case(text))
+            {
+                Some(option) => Ok(PropertyValue::Choice(option.clone())),
+                None => bail!("{} is not one of {}", text, options.join(", ")),
+            },
+            FieldType::Boolean => match text.to_ascii_lowercase().as_str() {
+                "true" | "on" | "yes" => Ok(PropertyValue::Boolean(true)),
+                "false" | "off" | "no" => Ok(PropertyValue::Boolean(false)),
+                _ => bail!("{} is not true or false", text),
+            },
+        }
+    }
+}
+
 /// Value of a property
 #[derive(Debug, Clone, PartialEq)]
 pub enum PropertyValue {
-    Numeric(u16),
+    Numeric(i32),
     Text(String),
     Choice(String),
     Boolean(bool),
@@ -171,7 +229,7 @@ pub enum PropertyValue {
 
 impl PropertyValue {
     /// Try to extract a numeric value
-    pub fn as_numeric(&self) -> Result<u16> {
+    pub fn as_numeric(&self) -> Result<i32> {
         match self {
             PropertyValue::Numeric(n) => Ok(*n),
             _ => bail!("Property value is not numeric"),
@@ -1,19 +1,42 @@
 use crate::elements::borders::BorderStyle;
+use crate::elements::label::{self, HorizontalAlign, LabelLine, VerticalAlign};
 use crate::elements::properties::{HasProperties, PropertiesSpec, PropertyValue};
 use crate::types::{Bounds, Coord, RenderPoint};
 use anyhow::{Result, bail};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RectangleElement {
     pub id: usize,
     pub name: String,
+    /// ID of the layer the element is drawn on
+    #[serde(default)]
+    pub layer: usize,
     pub start: Coord,
     pub width: u16,
     pub height: u16,
     pub bounds: Bounds,
     #[serde(default)]
     pub border_style: BorderStyle,
+    #[serde(default)]
+    pub label: String,
+    #[serde(default)]
+    pub label_align: HorizontalAlign,
+    #[serde(default)]
+    pub label_valign: VerticalAlign,
+    /// Blank columns kept between each side border and the label
+    #[serde(default = "default_label_padding")]
+    pub label_padding: u16,
+    /// Grow the box taller when the wrapped label doesn't fit
+    #[serde(default)]
+    pub auto_grow: bool,
+    /// Blank out the inside, hiding whatever is drawn below the box
+    #[serde(default)]
+    pub fill: bool,
+}
+
+fn default_label_padding() -> u16 {
+    1
 }
 
 impl RectangleElement {
@@ -22,30 +45,37 @@ impl RectangleElement {
         let bounds = Bounds {
             min: start,
             max: Coord {
-                x: start.x.saturating_add(width),
-                y: start.y.saturating_add(height),
+                x: start.x + width as i32,
+                y: start.y + height as i32,
             },
         };
         Self {
             id,
             name,
+            layer: 0,
             start,
             width,
             height,
             bounds,
             border_style: BorderStyle::Single,
+            label: String::new(),
+            label_align: HorizontalAlign::default(),
+            label_valign: VerticalAlign::default(),
+            label_padding: default_label_padding(),
+            auto_grow: false,
+            fill: false,
         }
     }
 
-    pub fn translate(&mut self, dx: i16, dy: i16) {
+    pub fn translate(&mut self, dx: i32, dy: i32) {
         self.start.translate(dx, dy);
         self.bounds.translate(dx, dy);
     }
 
     pub fn render_points(&self) -> Vec<RenderPoint> {
         let mut points = vec![];
-        let left = self.start.x as i32;
-        let top = self.start.y as i32;
+        let left = self.start.x;
+        let top = self.start.y;
         let right = left + self.width as i32;
         let bottom = top + self.height as i32;
 
@@ -70,16 +100,108 @@ impl RectangleElement {
             points.push((right, y, chars.vertical));
         }
 
+        // Fill, drawn before the label so the label shows on top
+        if self.fill {
+            for y in (top + 1)..bottom {
+                for x in (left + 1)..right {
+                    points.push((x, y, ' '));
+                }
+            }
+        }
+
+        // Label
+        for (pos, line) in self.label_lines() {
+            for (i, ch) in line.text.chars().enumerate() {
+                if ch != ' ' {
+                    points.push((pos.x + i as i32, pos.y, ch));
+                }
+            }
+        }
+
         points
     }
 
+    /// Top-left corner and size of the area available to the label
+    fn label_area(&self) -> (Coord, usize, usize) {
+        let padding = self.label_padding as usize;
+        let origin = Coord {
+            x: self.start.x + 1 + padding as i32,
+            y: self.start.y + 1,
+        };
+        let width = (self.width as usize).saturating_sub(1 + 2 * padding);
+        let height = (self.height as usize).saturating_sub(1);
+        (origin, width, height)
+    }
+
+    /// Wrapped and aligned label lines, with the canvas position of each line's first
+    /// character. Lines that don't fit in the box are left out.
+    pub fn label_lines(&self) -> Vec<(Coord, LabelLine)> {
+        let (origin, width, height) = self.label_area();
+        if width == 0 {
+            return Vec::new();
+        }
+
+        let lines = label::wrap(&self.label, width);
+        label::layout(lines, width, height, self.label_align, self.label_valign)
+            .into_iter()
+            .map(|(column, row, line)| {
+                let pos = Coord {
+                    x: origin.x + column as i32,
+                    y: origin.y + row as i32,
+                };
+                (pos, line)
+            })
+            .collect()
+    }
+
+    /// Canvas position of the label character at `index` (or just past the end),
+    /// used to place the cursor while editing
+    pub fn label_cursor_position(&self, index: usize) -> Option<Coord> {
+        let lines = self.label_lines();
+        let (pos, line) = lines
+            .iter()
+            .rev()
+            .find(|(_, line)| line.start <= index)
+            .or(lines.first())?;
+        let column = index
+            .saturating_sub(line.start)
+            .min(line.text.chars().count());
+        Some(Coord {
+            x: pos.x + column as i32,
+            y: pos.y,
+        })
+    }
+
+    /// Grow the box to fit the wrapped label, if auto-grow is on
+    pub fn fit_label(&mut self) {
+        let (_, width, _) = self.label_area();
+        if !self.auto_grow || width == 0 || self.label.is_empty() {
+            return;
+        }
+
+        let needed = label::wrap(&self.label, width).len() + 1;
+        if (self.height as usize) < needed {
+            self.height = needed.min(u16::MAX as usize) as u16;
+            self.update_bounds();
+        }
+    }
+
+    /// Move and resize to exactly the given bounds (resize handles)
+    pub fn resize(&mut self, bounds: Bounds) {
+        self.start = bounds.min;
+        self.width = (bounds.max.x - bounds.min.x).clamp(1, u16::MAX as i32) as u16;
+        self.height = (bounds.max.y - bounds.min.y).clamp(1, u16::MAX as i32) as u16;
+        self.update_bounds();
+        self.fit_label();
+    }
+
     /// Update bounds after modifying position or size
     fn update_bounds(&mut self) {
         self.bounds = Bounds {
             min: self.start,
             max: Coord {
-                x: self.start.x.saturating_add(self.width),
-                y: self.start.y.saturating_add(self.height),
+                x: self.start.x + self.width as i32,
+                y: self.start.y + self.height as i32,
             },
         };
     }
@@ -89,7 +211,8 @@ impl HasProperties for RectangleElement {
     fn properties_spec(&self) -> PropertiesSpec {
         PropertiesSpec::new()
             .section("Position", |s| {
-                s.numeric("x", "x", 0, 1000).numeric("y", "y", 0, 1000)
+                s.numeric("x", "x", -1000, 1000)
+                    .numeric("y", "y", -1000, 1000)
             })
             .section("Size", |s| {
                 s.numeric("width", "width", 1, 200)
@@ -97,6 +220,13 @@ impl HasProperties for RectangleElement {
             })
             .section("Style", |s| {
                 s.choice("border_style", "border-style", BorderStyle::all_options())
+                    .boolean("fill", "fill")
+            })
+            .section("Label", |s| {
+                s.choice("label_align", "align", HorizontalAlign::all_options())
+                    .choice("label_valign", "v-align", VerticalAlign::all_options())
+                    .numeric("label_padding", "padding", 0, 10)
+                    .boolean("auto_grow", "auto-grow")
             })
     }
 
@@ -105,9 +235,15 @@ impl HasProperties for RectangleElement {
         let value = match name {
             "x" => Numeric(self.start.x),
             "y" => Numeric(self.start.y),
-            "width" => Numeric(self.width),
-            "height" => Numeric(self.height),
+            "width" => Numeric(self.width as i32),
+            "height" => Numeric(self.height as i32),
             "border_style" => Choice(self.border_style.as_str().to_string()),
+            "label" => Text(self.label.clone()),
+            "label_align" => Choice(self.label_align.as_str().to_string()),
+            "label_valign" => Choice(self.label_valign.as_str().to_string()),
+            "label_padding" => Numeric(self.label_padding as i32),
+            "auto_grow" => Boolean(self.auto_grow),
+            "fill" => Boolean(self.fill),
             _ => return None,
         };
         Some(value)
@@ -125,23 +261,50 @@ impl HasProperties for RectangleElement {
             }
             "width" => {
                 let new_width = value.as_numeric()?;
-                if new_width == 0 {
+                if new_width <= 0 {
                     bail!("Width must be greater than 0");
                 }
-                self.width = new_width;
+                self.width = new_width.min(u16::MAX as i32) as u16;
                 self.update_bounds();
+                self.fit_label();
             }
             "height" => {
                 let new_height = value.as_numeric()?;
-                if new_height == 0 {
+                if new_height <= 0 {
                     bail!("Height must be greater than 0");
                 }
-                self.height = new_height;
+                self.height = new_height.min(u16::MAX as i32) as u16;
                 self.update_bounds();
+                self.fit_label();
             }
             "border_style" => {
                 self.border_style = BorderStyle::from_str(value.as_choice()?)?;
             }
+            "label" => {
+                self.label = value.as_text()?.to_string();
+                self.fit_label();
+            }
+            "label_align" => {
+                self.label_align = HorizontalAlign::from_str(value.as_choice()?)?;
+            }
+            "label_valign" => {
+                self.label_valign = VerticalAlign::from_str(value.as_choice()?)?;
+            }
+            "label_padding" => {
+                let padding = value.as_numeric()?;
+                if padding < 0 {
+                    bail!("Padding cannot be negative");
+                }
+                self.label_padding = padding.min(u16::MAX as i32) as u16;
+                self.fit_label();
+            }
+            "auto_grow" => {
+                self.auto_grow = value.as_boolean()?;
+                self.fit_label();
+            }
+            "fill" => {
+                self.fill = value.as_boolean()?;
+            }
             _ => bail!("Unknown property: {}", name),
         }
         Ok(())
@@ -0,0 +1,144 @@
+//! Grid-based A* routing for connectors that avoid other elements.
+
+use super::Segment;
+use crate::types::{Bounds, Coord, Direction};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Extra cost of a bend, in cells. High enough that the router takes a short detour
+/// rather than zig-zagging, low enough that it won't go around the whole diagram.
+const BEND_PENALTY: u32 = 10;
+
+/// Free space kept around the obstacles and endpoints when limiting the search
+const SEARCH_MARGIN: i32 = 2;
+
+/// Give up (and let the caller fall back) after visiting this many cells
+const MAX_VISITED: usize = 20_000;
+
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Left,
+    Direction::Right,
+];
+
+/// Search node: a cell plus the direction it was entered in (index into DIRECTIONS,
+/// or 4 for the start cell), since bends depend on how a cell was reached
+type Node = ((i32, i32), usize);
+
+/// Find an orthogonal path from `start` to `end` with as few bends as possible that
+/// doesn't cross any of the `obstacles`. Obstacles containing an endpoint are ignored,
+/// so paths can start or end on an element. Returns `None` when no path is found.
+pub fn route_around(start: Coord, end: Coord, obstacles: &[Bounds]) -> Option<Vec<Segment>> {
+    if start == end {
+        return None;
+    }
+
+    let contains = |b: &Bounds, (x, y): (i32, i32)| {
+        x >= b.min.x && x <= b.max.x && y >= b.min.y && y <= b.max.y
+    };
+    let obstacles: Vec<&Bounds> = obstacles
+        .iter()
+        .filter(|b| !contains(b, (start.x, start.y)) && !contains(b, (end.x, end.y)))
+        .collect();
+    let blocked = |pos| obstacles.iter().any(|b| contains(b, pos));
+
+    // Keep the search within the area around the endpoints and obstacles
+    let mut area = Bounds {
+        min: Coord {
+            x: start.x.min(end.x),
+            y: start.y.min(end.y),
+        },
+        max: Coord {
+            x: start.x.max(end.x),
+            y: start.y.max(end.y),
+        },
+    };
+    for b in &obstacles {
+        area.min.x = area.min.x.min(b.min.x);
+        area.min.y = area.min.y.min(b.min.y);
+        area.max.x = area.max.x.max(b.max.x);
+        area.max.y = area.max.y.max(b.max.y);
+    }
+    let in_area = |(x, y): (i32, i32)| {
+        x >= area.min.x - SEARCH_MARGIN
+            && x <= area.max.x + SEARCH_MARGIN
+            && y >= area.min.y - SEARCH_MARGIN
+            && y <= area.max.y + SEARCH_MARGIN
+    };
+
+    // Manhattan distance, plus one bend whenever the end isn't straight ahead
+    let heuristic = |(x, y): (i32, i32)| {
+        let bend = if x != end.x && y != end.y {
+            BEND_PENALTY
+        } else {
+            0
+        };
+        x.abs_diff(end.x) + y.abs_diff(end.y) + bend
+    };
+
+    let start_node: Node = ((start.x, start.y), DIRECTIONS.len());
+    let mut costs: HashMap<Node, u32> = HashMap::from([(start_node, 0)]);
+    let mut came_from: HashMap<Node, Node> = HashMap::new();
+    let mut open = BinaryHeap::from([Reverse((heuristic(start_node.0), 0, start_node))]);
+
+    while let Some(Reverse((_, cost, node))) = open.pop() {
+        let (pos, entered) = node;
+        if pos == (end.x, end.y) {
+            return Some(Segment::path(&reconstruct(&came_from, node)));
+        }
+        if costs.get(&node).is_some_and(|&best| best < cost) {
+            continue;
+        }
+        if costs.len() > MAX_VISITED {
+            return None;
+        }
+
+        for (index, &direction) in DIRECTIONS.iter().enumerate() {
+            // Never double back on the way we came
+            if entered < DIRECTIONS.len() && DIRECTIONS[entered].opposite() == direction {
+                continue;
+            }
+            let next_pos = step(pos, direction);
+            if !in_area(next_pos) || blocked(next_pos) {
+                continue;
+            }
+
+            let bend = if entered < DIRECTIONS.len() && entered != index {
+                BEND_PENALTY
+            } else {
+                0
+            };
+            let next = (next_pos, index);
+            let next_cost = cost + 1 + bend;
+            if costs.get(&next).is_none_or(|&best| next_cost < best) {
+                costs.insert(next, next_cost);
+                came_from.insert(next, node);
+                open.push(Reverse((next_cost + heuristic(next_pos), next_cost, next)));
+            }
+        }
+    }
+
+    None
+}
+
+fn step((x, y): (i32, i32), direction: Direction) -> (i32, i32) {
+    match direction {
+        Direction::Up => (x, y - 1),
+        Direction::Down => (x, y + 1),
+        Direction::Left => (x - 1, y),
+        Direction::Right => (x + 1, y),
+    }
+}
+
+/// Walk back from the end node to the start, returning the cells in path order
+fn reconstruct(came_from: &HashMap<Node, Node>, end: Node) -> Vec<Coord> {
+    let mut points = vec![end.0];
+    let mut node = end;
+    while let Some(&previous) = came_from.get(&node) {
+        points.push(previous.0);
+        node = previous;
+    }
+    points.reverse();
+    points.into_iter().map(|(x, y)| Coord { x, y }).collect()
+}
@@ -1,7 +1,7 @@
 use crate::types::{Bounds, Coord, Direction};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Segment {
     pub start: Coord,
     pub length: u16,
@@ -12,8 +12,8 @@ impl Segment {
     /// Create a segment from start and end coordinates
     /// Automatically determines direction and length
     pub fn from_coords(start: Coord, end: Coord) -> Self {
-        let dx = (end.x as i32) - (start.x as i32);
-        let dy = (end.y as i32) - (start.y as i32);
+        let dx = end.x - start.x;
+        let dy = end.y - start.y;
 
         let direction = if dx.abs() > dy.abs() {
             // Horizontal line
@@ -32,8 +32,8 @@ impl Segment {
         };
 
         let length = match direction {
-            Direction::Right | Direction::Left => dx.abs() as u16,
-            Direction::Down | Direction::Up => dy.abs() as u16,
+            Direction::Right | Direction::Left => dx.unsigned_abs() as u16,
+            Direction::Down | Direction::Up => dy.unsigned_abs() as u16,
         };
 
         Self {
@@ -43,27 +43,57 @@ impl Segment {
         }
     }
 
-    pub fn translate(&mut self, dx: i16, dy: i16) {
+    /// Build segments through a sequence of horizontally/vertically aligned points,
+    /// merging straight runs so each segment ends at a corner of the path
+    pub fn path(points: &[Coord]) -> Vec<Segment> {
+        let mut segments: Vec<Segment> = Vec::new();
+        for pair in points.windows(2) {
+            if pair[0] == pair[1] {
+                continue;
+            }
+            let segment = Segment::from_coords(pair[0], pair[1]);
+            match segments.last_mut() {
+                Some(last) if last.direction == segment.direction => {
+                    last.length += segment.length;
+                }
+                _ => segments.push(segment),
+            }
+        }
+        segments
+    }
+
+    /// Build one segment per pair of consecutive aligned points, without merging
+    /// straight runs, so every point stays a vertex of the path (vertex editing)
+    pub fn polyline(points: &[Coord]) -> Vec<Segment> {
+        points
+            .windows(2)
+            .filter(|pair| pair[0] != pair[1])
+            .map(|pair| Segment::from_coords(pair[0], pair[1]))
+            .collect()
+    }
+
+    pub fn translate(&mut self, dx: i32, dy: i32) {
         self.start.translate(dx, dy);
     }
 
     pub fn end(&self) -> Coord {
+        let length = self.length as i32;
         match self.direction {
             Direction::Right => Coord {
-                x: self.start.x.saturating_add(self.length),
+                x: self.start.x + length,
                 y: self.start.y,
             },
             Direction::Left => Coord {
-                x: self.start.x.saturating_sub(self.length),
+                x: self.start.x - length,
                 y: self.start.y,
             },
             Direction::Down => Coord {
                 x: self.start.x,
-                y: self.start.y.saturating_add(self.length),
+                y: self.start.y + length,
             },
             Direction::Up => Coord {
                 x: self.start.x,
-                y: self.start.y.saturating_sub(self.length),
+                y: self.start.y - length,
             },
         }
     }
@@ -91,10 +121,10 @@ pub fn calculate_bounds(segments: &[Segment]) -> Bounds {
         };
     }
 
-    let mut min_x = u16::MAX;
-    let mut min_y = u16::MAX;
-    let mut max_x = u16::MIN;
-    let mut max_y = u16::MIN;
+    let mut min_x = i32::MAX;
+    let mut min_y = i32::MAX;
+    let mut max_x = i32::MIN;
+    let mut max_y = i32::MIN;
 
     for segment in segments {
         let bounds = segment.bounds();
@@ -4,10 +4,13 @@ use crate::types::{Bounds, Coord, RenderPoint};
 use anyhow::{Result, bail};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TableElement {
     pub id: usize,
     pub name: String,
+    /// ID of the layer the element is drawn on
+    #[serde(default)]
+    pub layer: usize,
     pub start: Coord,
     pub width: u16,
     pub height: u16,
@@ -76,14 +79,15 @@ impl TableElement {
         let bounds = Bounds {
             min: start,
             max: Coord {
-                x: start.x.saturating_add(width),
-                y: start.y.saturating_add(height),
+                x: start.x + width as i32,
+                y: start.y + height as i32,
             },
         };
 
         Self {
             id,
             name: format!("Table {}", id + 1),
+            layer: 0,
             start,
             width,
             height,
@@ -96,7 +100,32 @@ impl TableElement {
         }
     }
 
-    pub fn translate(&mut self, dx: i16, dy: i16) {
+    /// Resize towards the given bounds, recomputing rows/cols like `new_from_drag`.
+    /// The size snaps to whole cells, so the edges that weren't dragged stay in place
+    /// and the dragged ones end up where the cells do.
+    pub fn resize(&mut self, bounds: Bounds, keep_right: bool, keep_bottom: bool) {
+        let width = (bounds.max.x - bounds.min.x).clamp(0, u16::MAX as i32) as u16;
+        let height = (bounds.max.y - bounds.min.y).clamp(0, u16::MAX as i32) as u16;
+        (self.rows, self.cols) = Self::calculate_dimensions(width, height);
+        self.sync_table_structure();
+        self.update_size_from_content();
+
+        self.start = Coord {
+            x: if keep_right {
+                bounds.max.x - self.width as i32
+            } else {
+                bounds.min.x
+            },
+            y: if keep_bottom {
+                bounds.max.y - self.height as i32
+            } else {
+                bounds.min.y
+            },
+        };
+        self.update_bounds();
+    }
+
+    pub fn translate(&mut self, dx: i32, dy: i32) {
         self.start.translate(dx, dy);
         self.bounds.translate(dx, dy);
     }
@@ -192,8 +221,8 @@ impl TableElement {
 
     pub fn render_points(&self) -> Vec<RenderPoint> {
         let mut points = vec![];
-        let x = self.start.x as i32;
-        let y = self.start.y as i32;
+        let x = self.start.x;
+        let y = self.start.y;
         let row_height = Self::CELL_HEIGHT;
         let col_widths = self.calculate_column_widths();
 
@@ -368,8 +397,8 @@ impl TableElement {
         self.bounds = Bounds {
             min: self.start,
             max: Coord {
-                x: self.start.x.saturating_add(self.width),
-                y: self.start.y.saturating_add(self.height),
+                x: self.start.x + self.width as i32,
+                y: self.start.y + self.height as i32,
             },
         };
     }
@@ -391,8 +420,8 @@ impl HasProperties for TableElement {
     fn get_property(&self, name: &str) -> Option<PropertyValue> {
         use PropertyValue::*;
         let value = match name {
-            "rows" => Numeric(self.rows as u16),
-            "cols" => Numeric(self.cols as u16),
+            "rows" => Numeric(self.rows as i32),
+            "cols" => Numeric(self.cols as i32),
             "header_border" => Choice(self.header_border.as_str().to_string()),
             "body_border" => Choice(self.body_border.as_str().to_string()),
             _ => return None,
@@ -403,19 +432,19 @@ impl HasProperties for TableElement {
     fn set_property(&mut self, name: &str, value: PropertyValue) -> Result<()> {
         match name {
             "rows" => {
-                let new_rows = value.as_numeric()? as usize;
-                if new_rows == 0 || new_rows > 20 {
+                let new_rows = value.as_numeric()?;
+                if !(1..=20).contains(&new_rows) {
                     bail!("Rows must be between 1 and 20");
                 }
-                self.rows = new_rows;
+                self.rows = new_rows as usize;
                 self.sync_table_structure();
             }
             "cols" => {
-                let new_cols = value.as_numeric()? as usize;
-                if new_cols == 0 || new_cols > 10 {
+                let new_cols = value.as_numeric()?;
+                if !(1..=10).contains(&new_cols) {
                     bail!("Cols must be between 1 and 10");
                 }
-                self.cols = new_cols;
+                self.cols = new_cols as usize;
                 self.sync_table_structure();
             }
             "header_border" => {
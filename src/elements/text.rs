@@ -1,10 +1,18 @@
+use super::{HasProperties, PropertiesSpec, PropertyValue};
 use crate::types::{Bounds, Coord, RenderPoint};
+use anyhow::{Result, bail};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Longest text accepted from the properties panel or `:set`
+const MAX_TEXT_LENGTH: usize = 200;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TextElement {
     pub id: usize,
     pub name: String,
+    /// ID of the layer the element is drawn on
+    #[serde(default)]
+    pub layer: usize,
     pub position: Coord,
     pub text: String,
     pub bounds: Bounds,
@@ -13,24 +21,32 @@ pub struct TextElement {
 impl TextElement {
     pub fn new(id: usize, position: Coord, text: String) -> Self {
         let name = format!("Text {}", id + 1);
-        let width = text.len() as u16;
-        let bounds = Bounds {
-            min: position,
-            max: Coord {
-                x: position.x.saturating_add(width),
-                y: position.y,
-            },
-        };
+        let bounds = Self::calculate_bounds(position, &text);
         Self {
             id,
             name,
+            layer: 0,
             position,
             text,
             bounds,
         }
     }
 
-    pub fn translate(&mut self, dx: i16, dy: i16) {
+    fn calculate_bounds(position: Coord, text: &str) -> Bounds {
+        Bounds {
+            min: position,
+            max: Coord {
+                x: position.x + text.chars().count() as i32,
+                y: position.y,
+            },
+        }
+    }
+
+    fn update_bounds(&mut self) {
+        self.bounds = Self::calculate_bounds(self.position, &self.text);
+    }
+
+    pub fn translate(&mut self, dx: i32, dy: i32) {
         self.position.translate(dx, dy);
         self.bounds.translate(dx, dy);
     }
@@ -38,12 +54,53 @@ impl TextElement {
     pub fn render_points(&self) -> Vec<RenderPoint> {
         let mut points = Vec::new();
         for (i, ch) in self.text.chars().enumerate() {
-            points.push((
-                self.position.x as i32 + i as i32,
-                self.position.y as i32,
-                ch,
-            ));
+            points.push((self.position.x + i as i32, self.position.y, ch));
         }
         points
     }
 }
+
+impl HasProperties for TextElement {
+    fn properties_spec(&self) -> PropertiesSpec {
+        PropertiesSpec::new()
+            .section("Position", |s| {
+                s.numeric("x", "x", -1000, 1000)
+                    .numeric("y", "y", -1000, 1000)
+            })
+            .section("Content", |s| s.text("text", "text", MAX_TEXT_LENGTH))
+    }
+
+    fn get_property(&self, name: &str) -> Option<PropertyValue> {
+        use PropertyValue::*;
+        let value = match name {
+            "x" => Numeric(self.position.x),
+            "y" => Numeric(self.position.y),
+            "text" => Text(self.text.clone()),
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    fn set_property(&mut self, name: &str, value: PropertyValue) -> Result<()> {
+        match name {
+            "x" => {
+                self.position.x = value.as_numeric()?;
+                self.update_bounds();
+            }
+            "y" => {
+                self.position.y = value.as_numeric()?;
+                self.update_bounds();
+            }
+            "text" => {
+                let text = value.as_text()?;
+                if text.is_empty() {
+                    bail!("Text cannot be empty");
+                }
+                self.text = text.to_string();
+                self.update_bounds();
+            }
+            _ => bail!("Unknown property: {}", name),
+        }
+        Ok(())
+    }
+}
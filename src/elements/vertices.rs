@@ -0,0 +1,125 @@
+//! Editing the corner points of a line or arrow.
+//!
+//! A path is handled as its list of vertices (start, bends, end), which always stays
+//! orthogonal: consecutive vertices share a row or a column. Moving a vertex slides the
+//! neighbouring bends along with it, and adds a corner next to an end that can't move.
+
+use super::Segment;
+use crate::types::Coord;
+
+/// Start of the path followed by the end of every segment
+pub fn vertices(segments: &[Segment]) -> Vec<Coord> {
+    let mut points: Vec<Coord> = segments.first().map(|s| s.start).into_iter().collect();
+    points.extend(segments.iter().map(Segment::end));
+    points
+}
+
+/// Move the vertex at `index` to `to`. Returns the vertex's index afterwards, which
+/// changes when corners are added or points collapse.
+pub fn move_vertex(points: &mut Vec<Coord>, index: usize, to: Coord) -> usize {
+    let Some(&old) = points.get(index) else {
+        return index;
+    };
+    let last = points.len() - 1;
+    // A vertex in the middle of a straight run (an inserted bend) pulls that part of
+    // the path out sideways instead of turning the run at the ends
+    let straight = index > 0 && index < last && {
+        let (prev, next) = (points[index - 1], points[index + 1]);
+        (prev.x == old.x && next.x == old.x) || (prev.y == old.y && next.y == old.y)
+    };
+    points[index] = to;
+
+    let mut corners = [None, None];
+    for (slot, neighbour) in [index.checked_sub(1), Some(index + 1)]
+        .into_iter()
+        .enumerate()
+    {
+        let Some(n) = neighbour.filter(|&n| n <= last) else {
+            continue;
+        };
+        let horizontal = points[n].y == old.y;
+        if n != 0 && n != last {
+            // Slide the neighbouring bend so the segment keeps its direction
+            if horizontal {
+                points[n].y = to.y;
+            } else {
+                points[n].x = to.x;
+            }
+        }
+
+        let fixed = points[n];
+        if fixed.x != to.x && fixed.y != to.y {
+            corners[slot] = Some(if horizontal != straight {
+                Coord {
+                    x: to.x,
+                    y: fixed.y,
+                }
+            } else {
+                Coord {
+                    x: fixed.x,
+                    y: to.y,
+                }
+            });
+        }
+    }
+
+    let [before, after] = corners;
+    let mut index = index;
+    if let Some(corner) = after {
+        points.insert(index + 1, corner);
+    }
+    if let Some(corner) = before {
+        points.insert(index, corner);
+        index += 1;
+    }
+    dedup(points, index)
+}
+
+/// Add a vertex at `at` on the segment after vertex `index`. Returns the new vertex's
+/// index, or `None` if `at` is not strictly inside that segment.
+pub fn insert_vertex(points: &mut Vec<Coord>, index: usize, at: Coord) -> Option<usize> {
+    let (&a, &b) = (points.get(index)?, points.get(index + 1)?);
+    let between = |p: i32, q: i32, v: i32| p.min(q) < v && v < p.max(q);
+    let inside = (a.y == b.y && at.y == a.y && between(a.x, b.x, at.x))
+        || (a.x == b.x && at.x == a.x && between(a.y, b.y, at.y));
+    if !inside {
+        return None;
+    }
+    points.insert(index + 1, at);
+    Some(index + 1)
+}
+
+/// Remove the bend at `index` (never an end). When its neighbours don't line up, the
+/// corner between them moves to the other side. Returns the vertex to select next.
+pub fn remove_vertex(points: &mut Vec<Coord>, index: usize) -> Option<usize> {
+    if index == 0 || index + 1 >= points.len() {
+        return None;
+    }
+    let removed = points.remove(index);
+    let (a, b) = (points[index - 1], points[index]);
+    if a.x != b.x && a.y != b.y {
+        let corner = if removed == (Coord { x: b.x, y: a.y }) {
+            Coord { x: a.x, y: b.y }
+        } else {
+            Coord { x: b.x, y: a.y }
+        };
+        points.insert(index, corner);
+    }
+    Some(dedup(points, index - 1))
+}
+
+/// Drop repeated points, keeping track of where the point at `index` ends up
+fn dedup(points: &mut Vec<Coord>, mut index: usize) -> usize {
+    let mut i = 1;
+    while i < points.len() {
+        if points[i] == points[i - 1] {
+            points.remove(i);
+            if index >= i {
+                index -= 1;
+            }
+        } else {
+            i += 1;
+        }
+    }
+    index.min(points.len().saturating_sub(1))
+}
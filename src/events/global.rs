@@ -1,9 +1,10 @@
 use crate::events::{ActionType, EventHandler, EventResult, KeyEvent, MouseEvent};
 use crate::input;
+use crate::keymap::{Action, Context};
 use crate::state::AppState;
 use crate::tools::Tool;
 use crate::types::{Coord, Panel};
-use crossterm::event::{KeyCode, KeyModifiers};
+use crossterm::event::KeyCode;
 
 /// Global event handler that handles fallthrough events not consumed by components
 pub struct GlobalHandler;
@@ -14,56 +15,63 @@ impl EventHandler for GlobalHandler {
     fn handle_key_event(&mut self, state: &mut AppState, key_event: &KeyEvent) -> EventResult {
         // Handle command mode if active
         if state.is_command_mode_active() {
-            return match key_event.code {
-                KeyCode::Char(c) => {
-                    state.add_char_to_command(c);
-                    EventResult::Consumed
+            return match state.keymap.resolve(Context::Command, key_event) {
+                Some(Action::Submit) => {
+                    if state.execute_command() {
+                        EventResult::Action(ActionType::Quit)
+                    } else {
+                        EventResult::Consumed
+                    }
                 }
-                KeyCode::Backspace => {
-                    state.backspace_command();
+                Some(Action::Cancel) => {
+                    state.exit_command_mode();
                     EventResult::Consumed
                 }
-                KeyCode::Enter => {
-                    state.execute_command();
+                Some(Action::Up) => {
+                    state.command_history_prev();
                     EventResult::Consumed
                 }
-                KeyCode::Esc => {
-                    state.exit_command_mode();
+                Some(Action::Down) => {
+                    state.command_history_next();
                     EventResult::Consumed
                 }
-                _ => EventResult::Consumed, // Consume all other keys while in command mode
-            };
-        }
-
-        // Handle Ctrl+S and Ctrl+O - enter command mode with pre-filled command
-        if key_event.modifiers.contains(KeyModifiers::CONTROL) {
-            return match key_event.code {
-                KeyCode::Char('s') => {
-                    // Pre-fill with :save or :w
-                    state.enter_command_mode_with("save ");
+                Some(Action::Complete) => {
+                    state.complete_command();
                     EventResult::Consumed
                 }
-                KeyCode::Char('o') => {
-                    // Pre-fill with :open or :e
-                    state.enter_command_mode_with("open ");
-                    EventResult::Consumed
+                // Typing into the command line isn't remappable
+                _ => {
+                    match key_event.code {
+                        KeyCode::Char(c) => state.add_char_to_command(c),
+                        KeyCode::Backspace => state.backspace_command(),
+                        _ => {}
+                    }
+                    EventResult::Consumed // Consume all other keys while in command mode
                 }
-                _ => EventResult::Ignored,
             };
         }
 
-        match key_event.code {
-            KeyCode::Char(':') => {
-                // Enter command mode
-                state.enter_command_mode();
-                EventResult::Consumed
-            }
-            KeyCode::Char('q') => EventResult::Action(ActionType::Quit),
-            KeyCode::Char('?') => {
-                state.toggle_help();
-                EventResult::Consumed
+        let Some(action) = state.keymap.resolve(Context::Global, key_event) else {
+            return EventResult::Ignored;
+        };
+        match action {
+            Action::Command => state.enter_command_mode(),
+            // Save/open enter command mode pre-filled with the command
+            Action::Save => state.enter_command_mode_with("save "),
+            Action::Open => state.enter_command_mode_with("open "),
+            Action::Copy => state.copy_selection(),
+            Action::Cut => state.cut_selection(),
+            Action::Paste => state.paste(),
+            Action::Duplicate => state.duplicate_selection(),
+            Action::Undo => state.undo(),
+            Action::Redo => state.redo(),
+            Action::Quit => {
+                if state.request_quit() {
+                    return EventResult::Action(ActionType::Quit);
+                }
             }
-            KeyCode::Char('p') => {
+            Action::Help => state.toggle_help(),
+            Action::Properties => {
                 if !state.show_properties {
                     // Properties hidden -> show and make active
                     state.toggle_properties();
@@ -76,9 +84,36 @@ impl EventHandler for GlobalHandler {
                     state.toggle_properties();
                     state.switch_panel(Panel::Canvas);
                 }
-                EventResult::Consumed
             }
-            KeyCode::Char(' ') => {
+            Action::Layers => {
+                if !state.show_layers {
+                    // Layers hidden -> show and make active
+                    state.toggle_layers();
+                    state.switch_panel(Panel::Layers);
+                } else if state.active_panel != Panel::Layers {
+                    // Layers visible but inactive -> make active
+                    state.switch_panel(Panel::Layers);
+                } else {
+                    // Layers visible and active -> hide
+                    state.toggle_layers();
+                    state.switch_panel(Panel::Canvas);
+                }
+            }
+            Action::Outline => {
+                if !state.show_outline {
+                    // Outline hidden -> show and make active
+                    state.toggle_outline();
+                    state.switch_panel(Panel::Outline);
+                } else if state.active_panel != Panel::Outline {
+                    // Outline visible but inactive -> make active
+                    state.switch_panel(Panel::Outline);
+                } else {
+                    // Outline visible and active -> hide
+                    state.toggle_outline();
+                    state.switch_panel(Panel::Canvas);
+                }
+            }
+            Action::Tools => {
                 if !state.show_tools_modal {
                     // Tools hidden -> show and make active
                     state.toggle_tools_modal();
@@ -91,32 +126,26 @@ impl EventHandler for GlobalHandler {
                     state.toggle_tools_modal();
                     state.switch_panel(Panel::Canvas);
                 }
-                EventResult::Consumed
             }
-            // Tool shortcuts
-            KeyCode::Esc => {
-                // Close tools modal first, then help modal, then switch to Select tool
+            Action::Cancel => {
+                // Close tools modal first, then help modal, then leave the group being
+                // edited, then switch to Select tool
                 if state.show_tools_modal {
                     state.toggle_tools_modal();
                     state.switch_panel(Panel::Canvas);
                 } else if state.show_help {
                     state.toggle_help();
+                } else if state.is_select_tool() && state.exit_group() {
+                    // Left the group
                 } else {
                     state.select_tool(Tool::Select);
                 }
-                EventResult::Consumed
-            }
-            // Tool selection - automatically handles all tools defined in types.rs
-            KeyCode::Char(c) => {
-                if let Some(tool) = Tool::from_key(c) {
-                    state.select_tool(tool);
-                    EventResult::Consumed
-                } else {
-                    EventResult::Ignored
-                }
             }
-            _ => EventResult::Ignored,
+            Action::SelectTool(tool) => state.select_tool(tool),
+            // Actions of other contexts don't have global bindings
+            _ => return EventResult::Ignored,
         }
+        EventResult::Consumed
     }
 
     fn handle_mouse_down(&mut self, state: &mut AppState, mouse_event: &MouseEvent) -> EventResult {
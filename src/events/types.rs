@@ -15,18 +15,21 @@ pub enum MouseEventKind {
     ScrollRight,
 }
 
-/// Our own mouse event type with helper methods for coordinate transformations
+/// Our own mouse event type with helper methods for coordinate transformations.
+///
+/// Coordinates are screen cells when the event arrives from the terminal, and
+/// (possibly negative) canvas coordinates once the canvas has translated it for tools.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct MouseEvent {
-    pub column: u16,
-    pub row: u16,
+    pub column: i32,
+    pub row: i32,
     pub kind: MouseEventKind,
     pub modifiers: KeyModifiers,
 }
 
 impl MouseEvent {
     /// Create a new event with specific coordinates
-    pub fn with_coords(&self, column: u16, row: u16) -> Self {
+    pub fn with_coords(&self, column: i32, row: i32) -> Self {
         Self {
             column,
             row,
@@ -62,8 +65,8 @@ impl From<crossterm::event::MouseEvent> for MouseEvent {
         };
 
         Self {
-            column: event.column,
-            row: event.row,
+            column: event.column as i32,
+            row: event.row as i32,
             kind,
             modifiers: event.modifiers,
         }
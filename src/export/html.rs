@@ -0,0 +1,104 @@
+//! HTML export: the rendered character grid in a `<pre>`, with a span around every run
+//! of characters drawn by the same element. The text stays plain ASCII/box-drawing, so
+//! readers can select and copy it straight from the page.
+
+use super::{BACKGROUND_COLOR, ExportOptions, FONT_FAMILY, FOREGROUND_COLOR, escape};
+use crate::state::CanvasState;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Highlight color for the element under the mouse
+const HIGHLIGHT_COLOR: &str = "#fff3b0";
+
+/// Highlights every span of the hovered element, not just the one under the mouse
+const HOVER_SCRIPT: &str = r#"document.querySelectorAll("pre.textdraw .element").forEach(function (span) {
+  var parts = document.querySelectorAll('pre.textdraw [data-element-id="' + span.dataset.elementId + '"]');
+  span.addEventListener("mouseenter", function () { parts.forEach(function (p) { p.classList.add("highlight"); }); });
+  span.addEventListener("mouseleave", function () { parts.forEach(function (p) { p.classList.remove("highlight"); }); });
+});"#;
+
+/// Render the canvas as a self-contained HTML document
+pub fn render(canvas: &CanvasState, options: ExportOptions) -> String {
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>TextDraw diagram</title>
+<style>
+body {{ margin: 0; background: {bg}; }}
+pre.textdraw {{ margin: 0; padding: {padding}px; font-family: {font}; font-size: {size}px; line-height: 1.2; color: {fg}; }}
+pre.textdraw .element.highlight {{ background: {highlight}; }}
+</style>
+</head>
+<body>
+<pre class="textdraw">"#,
+        bg = BACKGROUND_COLOR,
+        fg = FOREGROUND_COLOR,
+        font = FONT_FAMILY,
+        size = options.font_size,
+        padding = options.padding,
+        highlight = HIGHLIGHT_COLOR,
+    );
+
+    if !canvas.is_empty() {
+        write_grid(&mut out, canvas);
+    }
+
+    let _ = write!(
+        out,
+        "</pre>\n<script>\n{}\n</script>\n</body>\n</html>\n",
+        HOVER_SCRIPT
+    );
+    out
+}
+
+/// Write the rendered grid, one line per canvas row
+fn write_grid(out: &mut String, canvas: &CanvasState) {
+    let render_map = canvas.build_owned_render_map();
+    let elements: HashMap<usize, (&str, &str)> = canvas
+        .elements()
+        .iter()
+        .map(|e| (e.id(), (e.name(), e.type_name())))
+        .collect();
+
+    let (min_x, min_y, max_x, max_y) = canvas.bounds();
+    for y in min_y..=max_y {
+        let mut cells: Vec<(char, Option<usize>)> = (min_x..=max_x)
+            .map(|x| match render_map.get(&(x, y)) {
+                Some(&(ch, id)) => (ch, Some(id)),
+                None => (' ', None),
+            })
+            .collect();
+
+        // Drop trailing blank cells so copied text has no trailing whitespace
+        while cells
+            .last()
+            .is_some_and(|&(ch, id)| ch == ' ' && id.is_none())
+        {
+            cells.pop();
+        }
+
+        // Group consecutive cells drawn by the same element into one span
+        for run in cells.chunk_by(|a, b| a.1 == b.1) {
+            let text: String = run.iter().map(|&(ch, _)| ch).collect();
+            let owner = run[0].1.and_then(|id| Some((id, elements.get(&id)?)));
+            match owner {
+                Some((id, (name, type_name))) => {
+                    let _ = write!(
+                        out,
+                        r#"<span class="element {}" data-element-id="{}" title="{}">{}</span>"#,
+                        type_name.to_lowercase(),
+                        id,
+                        escape(name),
+                        escape(&text),
+                    );
+                }
+                None => out.push_str(&escape(&text)),
+            }
+        }
+        out.push('\n');
+    }
+}
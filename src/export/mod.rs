@@ -0,0 +1,101 @@
+//! Exporting diagrams to formats that can be embedded in docs and web pages.
+
+mod html;
+mod svg;
+
+use crate::state::CanvasState;
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use std::fs;
+
+const FOREGROUND_COLOR: &str = "#24292f";
+const BACKGROUND_COLOR: &str = "#ffffff";
+const FONT_FAMILY: &str = "ui-monospace, SFMono-Regular, Menlo, Consolas, monospace";
+
+/// Output format for `textdraw export`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    Svg,
+    Html,
+}
+
+/// Layout options shared by the exporters
+#[derive(Debug, Clone, Copy)]
+pub struct ExportOptions {
+    /// Font size in pixels
+    pub font_size: u16,
+    /// Space around the diagram in pixels
+    pub padding: u16,
+}
+
+impl Default for ExportOptions {
+    /// Same as the `textdraw export` defaults
+    fn default() -> Self {
+        Self {
+            font_size: 14,
+            padding: 16,
+        }
+    }
+}
+
+impl ExportOptions {
+    /// Width of one canvas cell in pixels (monospace glyphs are ~0.6em wide)
+    pub fn cell_width(&self) -> f32 {
+        self.font_size as f32 * 0.6
+    }
+
+    /// Height of one canvas cell in pixels
+    pub fn cell_height(&self) -> f32 {
+        self.font_size as f32 * 1.2
+    }
+}
+
+/// Export a diagram file, writing to `output` or stdout. With `layers`, only those
+/// layers are exported; otherwise the ones visible in the editor.
+pub fn export_file(
+    file_path: &str,
+    format: ExportFormat,
+    output: Option<&str>,
+    layers: &[String],
+    options: ExportOptions,
+) -> Result<()> {
+    let mut canvas = CanvasState::default();
+    canvas.load_from_file(file_path)?;
+    if !layers.is_empty() {
+        canvas.show_only_layers(layers)?;
+    }
+
+    let content = export(&canvas, format, options);
+
+    match output {
+        Some(path) => fs::write(path, content)
+            .with_context(|| format!("Failed to write to file: {}", path))?,
+        None => print!("{}", content),
+    }
+
+    Ok(())
+}
+
+/// Export the canvas contents in the given format
+pub fn export(canvas: &CanvasState, format: ExportFormat, options: ExportOptions) -> String {
+    match format {
+        ExportFormat::Svg => svg::render(canvas, options),
+        ExportFormat::Html => html::render(canvas, options),
+    }
+}
+
+/// Escape text for use in SVG/HTML content and attributes
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,434 @@
+//! SVG export drawn from element geometry (rects, polylines and text) rather than
+//! from rendered characters, so diagrams stay crisp at any zoom level.
+
+use super::{BACKGROUND_COLOR, ExportOptions, FONT_FAMILY, FOREGROUND_COLOR, escape};
+use crate::elements::{
+    ArrowElement, BorderStyle, Element, HeadStyle, LineElement, LineStyle, RectangleElement,
+    Segment, TableElement, TextElement,
+};
+use crate::state::CanvasState;
+use std::fmt::Write;
+
+/// Gap between the two strokes of a double border, in pixels
+const DOUBLE_GAP: f32 = 1.5;
+
+/// Arrowhead markers (see `head_marker`): ID, the x where the marker meets the end of
+/// the line (pointed heads end there, round ones are centered on it) and the shape,
+/// with `{color}` standing for the foreground color
+const HEAD_MARKERS: &[(&str, u8, &str)] = &[
+    (
+        "arrowhead",
+        9,
+        r#"<path d="M 0 0 L 10 5 L 0 10 z" fill="{color}"/>"#,
+    ),
+    (
+        "arrowhead-ascii",
+        9,
+        r#"<path d="M 1 1 L 9 5 L 1 9" fill="none" stroke="{color}" stroke-width="1.5"/>"#,
+    ),
+    (
+        "arrowhead-dot",
+        5,
+        r#"<circle cx="5" cy="5" r="4" fill="{color}"/>"#,
+    ),
+    (
+        "arrowhead-diamond",
+        5,
+        r#"<path d="M 0 5 L 5 0 L 10 5 L 5 10 z" fill="{color}"/>"#,
+    ),
+];
+
+/// Maps canvas cells to SVG pixel positions
+struct Grid {
+    origin_x: i32,
+    origin_y: i32,
+    cell_width: f32,
+    cell_height: f32,
+    padding: f32,
+}
+
+impl Grid {
+    /// Horizontal center of a cell column
+    fn x(&self, x: i32) -> f32 {
+        self.left(x) + self.cell_width / 2.0
+    }
+
+    /// Vertical center of a cell row
+    fn y(&self, y: i32) -> f32 {
+        self.padding + (y - self.origin_y) as f32 * self.cell_height + self.cell_height / 2.0
+    }
+
+    /// Left edge of a cell column (where text starts)
+    fn left(&self, x: i32) -> f32 {
+        self.padding + (x - self.origin_x) as f32 * self.cell_width
+    }
+}
+
+/// Render the canvas as a standalone SVG document
+pub fn render(canvas: &CanvasState, options: ExportOptions) -> String {
+    let (min_x, min_y, max_x, max_y) = canvas.bounds();
+    let (cols, rows) = if canvas.is_empty() {
+        (0, 0)
+    } else {
+        (max_x - min_x + 1, max_y - min_y + 1)
+    };
+
+    let grid = Grid {
+        origin_x: min_x,
+        origin_y: min_y,
+        cell_width: options.cell_width(),
+        cell_height: options.cell_height(),
+        padding: options.padding as f32,
+    };
+    let width = cols as f32 * grid.cell_width + 2.0 * grid.padding;
+    let height = rows as f32 * grid.cell_height + 2.0 * grid.padding;
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+        w = num(width),
+        h = num(height),
+    );
+    out.push_str("<defs>");
+    for (id, ref_x, shape) in HEAD_MARKERS {
+        let _ = write!(
+            out,
+            r#"<marker id="{}" viewBox="0 0 10 10" refX="{}" refY="5" markerWidth="6" markerHeight="6" orient="auto-start-reverse">{}</marker>"#,
+            id,
+            ref_x,
+            shape.replace("{color}", FOREGROUND_COLOR),
+        );
+    }
+    out.push_str("</defs>\n");
+    let _ = writeln!(
+        out,
+        r#"<rect width="100%" height="100%" fill="{}"/>"#,
+        BACKGROUND_COLOR
+    );
+    let _ = writeln!(
+        out,
+        r#"<g fill="none" stroke="{}" stroke-linecap="square" font-family="{}" font-size="{}">"#,
+        FOREGROUND_COLOR, FONT_FAMILY, options.font_size
+    );
+
+    for element in canvas.draw_order() {
+        match element {
+            Element::Rectangle(rect) => render_rectangle(&mut out, &grid, rect),
+            Element::Line(line) => render_line(&mut out, &grid, line),
+            Element::Arrow(arrow) => render_arrow(&mut out, &grid, arrow),
+            Element::Text(text) => render_text(&mut out, &grid, text),
+            Element::Table(table) => render_table(&mut out, &grid, table),
+        }
+    }
+
+    out.push_str("</g>\n</svg>\n");
+    out
+}
+
+fn render_rectangle(out: &mut String, grid: &Grid, rect: &RectangleElement) {
+    let left = rect.start.x;
+    let top = rect.start.y;
+    let right = left + rect.width as i32;
+    let bottom = top + rect.height as i32;
+    if rect.fill {
+        let _ = writeln!(
+            out,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}" stroke="none"/>"#,
+            num(grid.x(left)),
+            num(grid.y(top)),
+            num(grid.x(right) - grid.x(left)),
+            num(grid.y(bottom) - grid.y(top)),
+            BACKGROUND_COLOR,
+        );
+    }
+    draw_box(out, grid, (left, top), (right, bottom), rect.border_style);
+
+    for (pos, line) in rect.label_lines() {
+        draw_text(out, grid, pos.x, pos.y, &line.text);
+    }
+}
+
+fn render_line(out: &mut String, grid: &Grid, line: &LineElement) {
+    draw_polyline(out, grid, &line.segments, line.line_style, "");
+}
+
+fn render_arrow(out: &mut String, grid: &Grid, arrow: &ArrowElement) {
+    let mut markers = String::new();
+    if let Some(id) = head_marker(arrow.head_start) {
+        let _ = write!(markers, r#" marker-start="url(#{})""#, id);
+    }
+    if let Some(id) = head_marker(arrow.head_end) {
+        let _ = write!(markers, r#" marker-end="url(#{})""#, id);
+    }
+    draw_polyline(out, grid, &arrow.segments, arrow.line_style, &markers);
+}
+
+/// Marker drawing an arrowhead character, if it has one
+fn head_marker(head: char) -> Option<&'static str> {
+    match HeadStyle::of(head) {
+        HeadStyle::None => None,
+        HeadStyle::Triangle => Some("arrowhead"),
+        HeadStyle::Ascii => Some("arrowhead-ascii"),
+        HeadStyle::Dot => Some("arrowhead-dot"),
+        HeadStyle::Diamond => Some("arrowhead-diamond"),
+    }
+}
+
+fn render_text(out: &mut String, grid: &Grid, text: &TextElement) {
+    draw_text(out, grid, text.position.x, text.position.y, &text.text);
+}
+
+fn render_table(out: &mut String, grid: &Grid, table: &TableElement) {
+    let x = table.start.x;
+    let y = table.start.y;
+    let row_step = TableElement::CELL_HEIGHT as i32 + 1;
+
+    // Column boundaries, matching the layout used by TableElement::render_points
+    let mut col_edges = vec![x];
+    for col in 0..table.cols {
+        let last = *col_edges.last().unwrap_or(&x);
+        col_edges.push(last + table.get_column_width(col) as i32 + 1);
+    }
+    let right = *col_edges.last().unwrap_or(&x);
+    let bottom = y + table.rows as i32 * row_step;
+
+    // Header row and body are outlined separately so each gets its own border style
+    let header_bottom = y + row_step;
+    draw_box(
+        out,
+        grid,
+        (x, y),
+        (right, header_bottom),
+        table.header_border,
+    );
+    for &edge in &col_edges[1..col_edges.len().saturating_sub(1)] {
+        draw_segment(
+            out,
+            grid,
+            (edge, y),
+            (edge, header_bottom),
+            table.header_border,
+        );
+    }
+
+    if table.rows > 1 {
+        draw_box(
+            out,
+            grid,
+            (x, header_bottom),
+            (right, bottom),
+            table.body_border,
+        );
+        for row in 2..table.rows {
+            let row_y = y + row as i32 * row_step;
+            draw_segment(out, grid, (x, row_y), (right, row_y), table.body_border);
+        }
+        for &edge in &col_edges[1..col_edges.len().saturating_sub(1)] {
+            draw_segment(
+                out,
+                grid,
+                (edge, header_bottom),
+                (edge, bottom),
+                table.body_border,
+            );
+        }
+    }
+
+    for (row, cells) in table.cells.iter().enumerate().take(table.rows) {
+        for (col, cell) in cells.iter().enumerate().take(table.cols) {
+            draw_text(
+                out,
+                grid,
+                col_edges[col] + 1,
+                y + row as i32 * row_step + 1,
+                cell,
+            );
+        }
+    }
+}
+
+/// Draw a rectangle outline with corners at the centers of the given cells
+fn draw_box(out: &mut String, grid: &Grid, min: (i32, i32), max: (i32, i32), style: BorderStyle) {
+    let x = grid.x(min.0);
+    let y = grid.y(min.1);
+    let width = grid.x(max.0) - x;
+    let height = grid.y(max.1) - y;
+
+    let mut rect = |inset: f32, stroke_width: f32| {
+        let radius = if style == BorderStyle::Rounded {
+            grid.cell_width / 2.0
+        } else {
+            0.0
+        };
+        let _ = writeln!(
+            out,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" rx="{}" stroke-width="{}"/>"#,
+            num(x + inset),
+            num(y + inset),
+            num(width - 2.0 * inset),
+            num(height - 2.0 * inset),
+            num(radius),
+            num(stroke_width),
+        );
+    };
+
+    match style {
+        BorderStyle::None => {}
+        BorderStyle::Double => {
+            rect(-DOUBLE_GAP, 1.0);
+            rect(DOUBLE_GAP, 1.0);
+        }
+        _ => rect(0.0, stroke_width(style)),
+    }
+}
+
+/// Draw a straight horizontal or vertical line between the centers of two cells
+fn draw_segment(
+    out: &mut String,
+    grid: &Grid,
+    from: (i32, i32),
+    to: (i32, i32),
+    style: BorderStyle,
+) {
+    let (x1, y1) = (grid.x(from.0), grid.y(from.1));
+    let (x2, y2) = (grid.x(to.0), grid.y(to.1));
+
+    let mut line = |dx: f32, dy: f32, stroke_width: f32| {
+        let _ = writeln!(
+            out,
+            r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke-width="{}"/>"#,
+            num(x1 + dx),
+            num(y1 + dy),
+            num(x2 + dx),
+            num(y2 + dy),
+            num(stroke_width),
+        );
+    };
+
+    match style {
+        BorderStyle::None => {}
+        BorderStyle::Double => {
+            // Offset perpendicular to the line
+            let (dx, dy) = if from.1 == to.1 {
+                (0.0, DOUBLE_GAP)
+            } else {
+                (DOUBLE_GAP, 0.0)
+            };
+            line(-dx, -dy, 1.0);
+            line(dx, dy, 1.0);
+        }
+        _ => line(0.0, 0.0, stroke_width(style)),
+    }
+}
+
+/// Draw connected segments as a polyline through the cell centers. Double lines are two
+/// thin polylines offset to either side, like double borders.
+fn draw_polyline(
+    out: &mut String,
+    grid: &Grid,
+    segments: &[Segment],
+    style: LineStyle,
+    extra_attrs: &str,
+) {
+    let Some(first) = segments.first() else {
+        return;
+    };
+
+    let mut points = vec![(grid.x(first.start.x), grid.y(first.start.y))];
+    for segment in segments {
+        let end = segment.end();
+        points.push((grid.x(end.x), grid.y(end.y)));
+    }
+
+    let mut polyline = |points: &[(f32, f32)], stroke_width: f32, attrs: &str| {
+        let points: Vec<String> = points
+            .iter()
+            .map(|&(x, y)| format!("{},{}", num(x), num(y)))
+            .collect();
+        let _ = writeln!(
+            out,
+            r#"<polyline points="{}" stroke-width="{}" stroke-linejoin="round"{}/>"#,
+            points.join(" "),
+            num(stroke_width),
+            attrs,
+        );
+    };
+
+    let single = stroke_width(BorderStyle::Single);
+    match style {
+        LineStyle::Bold => polyline(&points, stroke_width(BorderStyle::Bold), extra_attrs),
+        LineStyle::Dashed => polyline(
+            &points,
+            single,
+            &format!(r#" stroke-dasharray="4 3"{}"#, extra_attrs),
+        ),
+        LineStyle::Single => polyline(&points, single, extra_attrs),
+        LineStyle::Double => {
+            polyline(&offset_points(&points, -DOUBLE_GAP), 1.0, "");
+            polyline(&offset_points(&points, DOUBLE_GAP), 1.0, "");
+            // Arrowheads go on an unstroked center line, so there is one per end
+            if !extra_attrs.is_empty() {
+                polyline(
+                    &points,
+                    single,
+                    &format!(r#" stroke="none"{}"#, extra_attrs),
+                );
+            }
+        }
+    }
+}
+
+/// Shift a path of horizontal and vertical steps sideways by `gap` pixels (to the left
+/// of the direction of travel for a negative gap), keeping its corners square
+fn offset_points(points: &[(f32, f32)], gap: f32) -> Vec<(f32, f32)> {
+    let sign = |d: f32| {
+        if d == 0.0 { 0.0 } else { d.signum() }
+    };
+    // Unit normal of the step from `a` to `b`
+    let normal = |a: (f32, f32), b: (f32, f32)| (-sign(b.1 - a.1), sign(b.0 - a.0));
+    (0..points.len())
+        .map(|i| {
+            let before = (i > 0).then(|| normal(points[i - 1], points[i]));
+            let after = points.get(i + 1).map(|&next| normal(points[i], next));
+            let (nx, ny) = match (before, after) {
+                // A corner moves along both normals
+                (Some(a), Some(b)) if a.0 * b.0 + a.1 * b.1 == 0.0 => (a.0 + b.0, a.1 + b.1),
+                (Some(n), _) | (None, Some(n)) => n,
+                (None, None) => (0.0, 0.0),
+            };
+            (points[i].0 + nx * gap, points[i].1 + ny * gap)
+        })
+        .collect()
+}
+
+/// Draw text starting at the left edge of a cell, vertically centered in its row
+fn draw_text(out: &mut String, grid: &Grid, x: i32, y: i32, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    let _ = writeln!(
+        out,
+        r#"<text x="{}" y="{}" dominant-baseline="central" fill="{}" stroke="none" xml:space="preserve">{}</text>"#,
+        num(grid.left(x)),
+        num(grid.y(y)),
+        FOREGROUND_COLOR,
+        escape(text),
+    );
+}
+
+fn stroke_width(style: BorderStyle) -> f32 {
+    match style {
+        BorderStyle::Bold => 3.0,
+        _ => 1.5,
+    }
+}
+
+/// Format a pixel value with at most two decimals and no trailing zeros
+fn num(value: f32) -> String {
+    let formatted = format!("{:.2}", value);
+    formatted
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
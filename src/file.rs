@@ -1,23 +1,45 @@
 use crate::elements::Element;
-use crate::state::CanvasState;
-use anyhow::{Context, Result};
+use crate::state::{CanvasState, Group, Layer};
+use anyhow::{Context, Result, bail};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::fs;
 use std::path::Path;
 
+/// Current version of the on-disk format
+///
+/// 1. Unsigned (u16) coordinates. Files from this era have no `format` field.
+/// 2. Signed (i32) coordinates, so elements can live left of / above the origin.
+/// 3. Groups, stored next to the elements.
+/// 4. Layers. Each element names the layer it is drawn on.
+const FORMAT_VERSION: u64 = 4;
+
 /// Serializable format for saving/loading diagrams
 #[derive(Serialize, Deserialize)]
 pub struct DiagramFile {
     pub version: String,
+    pub format: u64,
     pub elements: Vec<Element>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<Group>,
+    #[serde(default)]
+    pub layers: Vec<Layer>,
     pub next_id: usize,
 }
 
 impl DiagramFile {
-    pub fn new(elements: Vec<Element>, next_id: usize) -> Self {
+    pub fn new(
+        elements: Vec<Element>,
+        groups: Vec<Group>,
+        layers: Vec<Layer>,
+        next_id: usize,
+    ) -> Self {
         Self {
             version: env!("CARGO_PKG_VERSION").to_string(),
+            format: FORMAT_VERSION,
             elements,
+            groups,
+            layers,
             next_id,
         }
     }
@@ -39,21 +61,54 @@ impl DiagramFile {
         let json = fs::read_to_string(path.as_ref())
             .context(format!("Failed to read file: {}", path.as_ref().display()))?;
 
-        let diagram: DiagramFile =
+        let mut value: Value =
             serde_json::from_str(&json).context("Failed to parse diagram file")?;
+        migrate(&mut value)?;
+
+        let diagram: DiagramFile =
+            serde_json::from_value(value).context("Failed to parse diagram file")?;
 
         Ok(diagram)
     }
 }
 
-/// Render a diagram file to stdout without entering TUI mode
-pub fn render_file(file_path: &str) -> Result<()> {
+/// Upgrade a diagram saved by an older version to the current format
+fn migrate(value: &mut Value) -> Result<()> {
+    let Some(object) = value.as_object_mut() else {
+        bail!("Failed to parse diagram file: expected a JSON object");
+    };
+
+    let format = object.get("format").and_then(Value::as_u64).unwrap_or(1);
+    if format > FORMAT_VERSION {
+        bail!(
+            "Diagram uses format {} but this version of textdraw only supports up to {}",
+            format,
+            FORMAT_VERSION
+        );
+    }
+
+    // 1 -> 2: coordinates became signed. Every u16 value is a valid i32, so
+    // the elements can be read as they are.
+    // 2 -> 3: groups were added. Older diagrams have none.
+    // 3 -> 4: layers were added. Older diagrams get a single layer when loaded, and
+    // their elements default to it.
+
+    object.insert("format".to_string(), FORMAT_VERSION.into());
+    Ok(())
+}
+
+/// Render a diagram file to stdout without entering TUI mode. With `layers`, only
+/// those layers are rendered; otherwise the ones visible in the editor.
+pub fn render_file(file_path: &str, layers: &[String]) -> Result<()> {
     // Load the file
     let mut canvas = CanvasState::default();
     canvas.load_from_file(file_path)?;
+    if !layers.is_empty() {
+        canvas.show_only_layers(layers)?;
+    }
 
-    // Check if canvas has any elements
-    if canvas.is_empty() {
+    // Check if canvas has any elements to show
+    if canvas.draw_order().is_empty() {
         println!("(empty diagram)");
         return Ok(());
     }
@@ -0,0 +1,409 @@
+//! Importing plain-text ASCII art as editable elements.
+//!
+//! Recognition runs in three passes over the character grid, each consuming the cells it
+//! claims: rectangles first (so their edges aren't mistaken for lines), then line and
+//! arrow paths, and finally whatever is left becomes text.
+
+#[cfg(test)]
+mod tests;
+
+use crate::elements::{
+    ArrowElement, BorderStyle, Element, LineElement, RectangleElement, Segment, TextElement,
+};
+use crate::state::CanvasState;
+use crate::types::{Coord, Direction};
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+/// Tab stops used when expanding tabs in the input
+const TAB_WIDTH: usize = 4;
+
+type Pos = (i32, i32);
+
+/// Import a plain text file and save the recognized elements as a diagram.
+/// Returns the number of elements created.
+pub fn import_file(input: &str, output: &str) -> Result<usize> {
+    let text =
+        fs::read_to_string(input).with_context(|| format!("Failed to read file: {}", input))?;
+    let canvas = import_text(&text);
+    canvas.save_to_file(output)?;
+    Ok(canvas.elements().len())
+}
+
+/// Default output path for an import: the input path with a `.textdraw` extension
+pub fn default_output_path(input: &str) -> String {
+    Path::new(input)
+        .with_extension("textdraw")
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Convert ASCII art into a canvas of rectangles, lines, arrows and text
+pub fn import_text(text: &str) -> CanvasState {
+    let grid = Grid::parse(text);
+    let mut consumed = HashSet::new();
+    let mut canvas = CanvasState::default();
+
+    for (min, max, style) in find_rectangles(&grid, &mut consumed) {
+        let id = canvas.get_next_id();
+        let mut rect = RectangleElement::new(
+            id,
+            Coord { x: min.0, y: min.1 },
+            (max.0 - min.0) as u16,
+            (max.1 - min.1) as u16,
+        );
+        rect.border_style = style;
+        canvas.add_element(Element::Rectangle(rect));
+    }
+
+    for path in find_paths(&grid, &mut consumed) {
+        let id = canvas.get_next_id();
+        canvas.add_element(path_to_element(&grid, id, path));
+        canvas.attach_connector(id);
+    }
+
+    for (pos, text) in find_texts(&grid, &consumed) {
+        let id = canvas.get_next_id();
+        canvas.add_element(Element::Text(TextElement::new(
+            id,
+            Coord { x: pos.0, y: pos.1 },
+            text,
+        )));
+    }
+
+    canvas
+}
+
+/// The input as a grid of characters, with blank cells outside the text
+struct Grid {
+    rows: Vec<Vec<char>>,
+}
+
+impl Grid {
+    fn parse(text: &str) -> Self {
+        let rows = text
+            .lines()
+            .map(|line| {
+                let mut row = Vec::new();
+                for ch in line.trim_end_matches('\r').chars() {
+                    if ch == '\t' {
+                        let spaces = TAB_WIDTH - row.len() % TAB_WIDTH;
+                        row.extend(std::iter::repeat_n(' ', spaces));
+                    } else {
+                        row.push(ch);
+                    }
+                }
+                row
+            })
+            .collect();
+        Self { rows }
+    }
+
+    fn get(&self, (x, y): Pos) -> char {
+        if x < 0 || y < 0 {
+            return ' ';
+        }
+        self.rows
+            .get(y as usize)
+            .and_then(|row| row.get(x as usize))
+            .copied()
+            .unwrap_or(' ')
+    }
+
+    /// All positions in row-major order
+    fn positions(&self) -> impl Iterator<Item = Pos> + '_ {
+        self.rows
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| (0..row.len()).map(move |x| (x as i32, y as i32)))
+    }
+}
+
+// ============================================================================
+// Rectangles
+// ============================================================================
+
+const TOP_RIGHT: &[char] = &['┐', '╮', '╗', '┓', '+'];
+const BOTTOM_LEFT: &[char] = &['└', '╰', '╚', '┗', '+'];
+const BOTTOM_RIGHT: &[char] = &['┘', '╯', '╝', '┛', '+'];
+
+/// Border style implied by a top-left corner character
+fn corner_style(ch: char) -> Option<BorderStyle> {
+    match ch {
+        '┌' | '+' => Some(BorderStyle::Single),
+        '╭' => Some(BorderStyle::Rounded),
+        '╔' => Some(BorderStyle::Double),
+        '┏' => Some(BorderStyle::Bold),
+        _ => None,
+    }
+}
+
+/// Characters allowed along a top or bottom edge, including junctions where lines attach
+const HORIZONTAL_EDGE: &[char] = &[
+    '─', '═', '━', '-', '┬', '┴', '┼', '╤', '╧', '╦', '╩', '┳', '┻', '+',
+];
+
+/// Characters allowed along a left or right edge, including junctions where lines attach
+const VERTICAL_EDGE: &[char] = &[
+    '│', '║', '┃', '|', '├', '┤', '┼', '╟', '╢', '╠', '╣', '┣', '┫', '+',
+];
+
+/// Find closed rectangles and mark their borders as consumed.
+/// Returns (top-left, bottom-right, style) for each one.
+fn find_rectangles(grid: &Grid, consumed: &mut HashSet<Pos>) -> Vec<(Pos, Pos, BorderStyle)> {
+    let mut rectangles = Vec::new();
+    for pos in grid.positions() {
+        let Some((max, style)) = rectangle_at(grid, pos) else {
+            continue;
+        };
+        for x in pos.0..=max.0 {
+            consumed.insert((x, pos.1));
+            consumed.insert((x, max.1));
+        }
+        for y in pos.1..=max.1 {
+            consumed.insert((pos.0, y));
+            consumed.insert((max.0, y));
+        }
+        rectangles.push((pos, max, style));
+    }
+    rectangles
+}
+
+/// Try to trace a rectangle whose top-left corner is at `top_left`.
+/// The smallest rectangle wins when `+` corners make several possible.
+fn rectangle_at(grid: &Grid, top_left: Pos) -> Option<(Pos, BorderStyle)> {
+    let style = corner_style(grid.get(top_left))?;
+    let (left, top) = top_left;
+
+    let mut right = left + 1;
+    loop {
+        let ch = grid.get((right, top));
+        if TOP_RIGHT.contains(&ch)
+            && let Some(bottom) = rectangle_bottom(grid, left, top, right)
+        {
+            return Some(((right, bottom), style));
+        }
+        if !HORIZONTAL_EDGE.contains(&ch) {
+            return None;
+        }
+        right += 1;
+    }
+}
+
+/// Walk down both sides of a rectangle until a matching bottom edge is found
+fn rectangle_bottom(grid: &Grid, left: i32, top: i32, right: i32) -> Option<i32> {
+    let mut bottom = top + 1;
+    loop {
+        let left_ch = grid.get((left, bottom));
+        let right_ch = grid.get((right, bottom));
+        if BOTTOM_LEFT.contains(&left_ch)
+            && BOTTOM_RIGHT.contains(&right_ch)
+            && (left + 1..right).all(|x| HORIZONTAL_EDGE.contains(&grid.get((x, bottom))))
+        {
+            return Some(bottom);
+        }
+        if !VERTICAL_EDGE.contains(&left_ch) || !VERTICAL_EDGE.contains(&right_ch) {
+            return None;
+        }
+        bottom += 1;
+    }
+}
+
+// ============================================================================
+// Lines and arrows
+// ============================================================================
+
+/// Directions a character can connect in. `+` can connect anywhere; which
+/// neighbors it actually joins is decided by the neighbors themselves.
+fn connections(ch: char) -> &'static [Direction] {
+    use Direction::*;
+    match ch {
+        '─' | '━' | '═' | '-' => &[Left, Right],
+        '│' | '┃' | '║' | '|' => &[Up, Down],
+        '┌' | '╭' | '┏' | '╔' => &[Right, Down],
+        '┐' | '╮' | '┓' | '╗' => &[Left, Down],
+        '└' | '╰' | '┗' | '╚' => &[Up, Right],
+        '┘' | '╯' | '┛' | '╝' => &[Up, Left],
+        '├' => &[Up, Down, Right],
+        '┤' => &[Up, Down, Left],
+        '┬' => &[Left, Right, Down],
+        '┴' => &[Left, Right, Up],
+        '┼' | '+' => &[Up, Down, Left, Right],
+        '▶' | '>' => &[Left],
+        '◀' | '<' => &[Right],
+        '▲' | '^' => &[Down],
+        '▼' | 'v' => &[Up],
+        _ => &[],
+    }
+}
+
+fn is_head(ch: char) -> bool {
+    matches!(ch, '▶' | '◀' | '▲' | '▼' | '>' | '<' | '^' | 'v')
+}
+
+fn step((x, y): Pos, direction: Direction) -> Pos {
+    match direction {
+        Direction::Up => (x, y - 1),
+        Direction::Down => (x, y + 1),
+        Direction::Left => (x - 1, y),
+        Direction::Right => (x + 1, y),
+    }
+}
+
+/// Follows connected line characters into paths
+struct PathTracer<'a> {
+    grid: &'a Grid,
+    consumed: &'a HashSet<Pos>,
+    visited: HashSet<Pos>,
+}
+
+impl PathTracer<'_> {
+    /// Directions in which a cell connects to a neighbor that connects back
+    fn links(&self, pos: Pos) -> Vec<Direction> {
+        if self.consumed.contains(&pos) {
+            return Vec::new();
+        }
+        connections(self.grid.get(pos))
+            .iter()
+            .copied()
+            .filter(|&dir| {
+                let next = step(pos, dir);
+                !self.consumed.contains(&next)
+                    && connections(self.grid.get(next)).contains(&dir.opposite())
+            })
+            .collect()
+    }
+
+    /// Follow a path from `start` in `direction` until it reaches an end or a junction.
+    /// Junctions are shared by every path that meets there, so they're never marked visited.
+    fn trace(&mut self, start: Pos, mut direction: Direction) -> Vec<Pos> {
+        let mut path = vec![start];
+        if self.links(start).len() <= 2 {
+            self.visited.insert(start);
+        }
+
+        let mut pos = start;
+        loop {
+            pos = step(pos, direction);
+            path.push(pos);
+
+            let links = self.links(pos);
+            if links.len() != 2 || !self.visited.insert(pos) {
+                if links.len() < 2 {
+                    self.visited.insert(pos);
+                }
+                return path;
+            }
+            let came_from = direction.opposite();
+            direction = links
+                .into_iter()
+                .find(|&d| d != came_from)
+                .unwrap_or(direction);
+        }
+    }
+}
+
+/// Find line and arrow paths, starting from their loose ends and then from junctions.
+/// Returns each path as the list of cells it passes through.
+fn find_paths(grid: &Grid, consumed: &mut HashSet<Pos>) -> Vec<Vec<Pos>> {
+    let mut tracer = PathTracer {
+        grid,
+        consumed,
+        visited: HashSet::new(),
+    };
+    let mut paths = Vec::new();
+
+    let ends: Vec<Pos> = grid
+        .positions()
+        .filter(|&pos| tracer.links(pos).len() == 1)
+        .collect();
+    for start in ends {
+        if tracer.visited.contains(&start) {
+            continue;
+        }
+        let direction = tracer.links(start)[0];
+        paths.push(tracer.trace(start, direction));
+    }
+
+    let junctions: Vec<Pos> = grid
+        .positions()
+        .filter(|&pos| tracer.links(pos).len() > 2)
+        .collect();
+    for start in junctions {
+        for direction in tracer.links(start) {
+            let next = step(start, direction);
+            if tracer.visited.contains(&next) || tracer.links(next).len() > 2 {
+                continue;
+            }
+            paths.push(tracer.trace(start, direction));
+        }
+    }
+
+    let visited = tracer.visited;
+    let paths: Vec<Vec<Pos>> = paths.into_iter().filter(|path| path.len() >= 2).collect();
+    for path in &paths {
+        consumed.extend(path.iter().copied());
+    }
+    consumed.extend(visited);
+    paths
+}
+
+/// Build a line, or an arrow if either end of the path is an arrowhead
+fn path_to_element(grid: &Grid, id: usize, mut path: Vec<Pos>) -> Element {
+    let head_at_start = path.first().is_some_and(|&pos| is_head(grid.get(pos)));
+    let head_at_end = path.last().is_some_and(|&pos| is_head(grid.get(pos)));
+
+    // Arrows always have a head at the end; a single head at the start is flipped round
+    if head_at_start && !head_at_end {
+        path.reverse();
+    }
+
+    let points: Vec<Coord> = path.iter().map(|&(x, y)| Coord { x, y }).collect();
+    let segments = Segment::path(&points);
+    if !head_at_start && !head_at_end {
+        return Element::Line(LineElement::new(id, segments));
+    }
+
+    let mut arrow = ArrowElement::new(id, segments);
+    if head_at_start
+        && head_at_end
+        && let Some(first) = arrow.segments.first()
+    {
+        arrow.head_start = ArrowElement::head_char(first.direction.opposite());
+    }
+    Element::Arrow(arrow)
+}
+
+// ============================================================================
+// Text
+// ============================================================================
+
+/// Group the remaining characters into text runs. Words separated by a single space
+/// stay together; wider gaps or consumed cells start a new run.
+fn find_texts(grid: &Grid, consumed: &HashSet<Pos>) -> Vec<(Pos, String)> {
+    let is_free = |pos: Pos| !consumed.contains(&pos) && grid.get(pos) != ' ';
+    let is_word_gap = |(x, y): Pos| grid.get((x, y)) == ' ' && is_free((x + 1, y));
+    let mut texts = Vec::new();
+
+    for (y, row) in grid.rows.iter().enumerate() {
+        let y = y as i32;
+        let mut x = 0;
+        while x < row.len() as i32 {
+            if !is_free((x, y)) {
+                x += 1;
+                continue;
+            }
+
+            let start = x;
+            let mut text = String::new();
+            while is_free((x, y)) || is_word_gap((x, y)) {
+                text.push(grid.get((x, y)));
+                x += 1;
+            }
+            texts.push(((start, y), text));
+        }
+    }
+    texts
+}
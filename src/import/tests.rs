@@ -0,0 +1,69 @@
+use super::import_text;
+use crate::elements::{BorderStyle, Element};
+use crate::types::Direction;
+
+#[test]
+fn test_import_ascii_rectangle_with_label() {
+    let canvas = import_text("+-----+\n| API |\n+-----+\n");
+
+    let elements = canvas.elements();
+    assert_eq!(elements.len(), 2);
+    let Element::Rectangle(rect) = &elements[0] else {
+        panic!("expected a rectangle, got {:?}", elements[0]);
+    };
+    assert_eq!((rect.start.x, rect.start.y), (0, 0));
+    assert_eq!((rect.width, rect.height), (6, 2));
+    assert_eq!(rect.border_style, BorderStyle::Single);
+
+    let Element::Text(text) = &elements[1] else {
+        panic!("expected text, got {:?}", elements[1]);
+    };
+    assert_eq!(text.text, "API");
+    assert_eq!((text.position.x, text.position.y), (2, 1));
+}
+
+#[test]
+fn test_import_bent_arrow() {
+    let canvas = import_text("──┐\n  │\n  ▼\n");
+
+    let elements = canvas.elements();
+    assert_eq!(elements.len(), 1);
+    let Element::Arrow(arrow) = &elements[0] else {
+        panic!("expected an arrow, got {:?}", elements[0]);
+    };
+    assert_eq!(arrow.segments.len(), 2);
+    assert_eq!(arrow.segments[0].direction, Direction::Right);
+    assert_eq!(arrow.segments[1].direction, Direction::Down);
+    assert_eq!(arrow.head_start, ' ');
+    assert_eq!(arrow.head_end, '▼');
+}
+
+#[test]
+fn test_import_reverses_arrow_with_head_at_start() {
+    let canvas = import_text("<---\n");
+
+    let Element::Arrow(arrow) = &canvas.elements()[0] else {
+        panic!("expected an arrow");
+    };
+    assert_eq!(
+        (arrow.segments[0].start.x, arrow.segments[0].start.y),
+        (3, 0)
+    );
+    assert_eq!(arrow.segments[0].direction, Direction::Left);
+    assert_eq!(arrow.head_end, '◀');
+}
+
+#[test]
+fn test_import_keeps_hyphenated_words_as_text() {
+    let canvas = import_text("a well-known  value\n");
+
+    let texts: Vec<&str> = canvas
+        .elements()
+        .iter()
+        .map(|e| match e {
+            Element::Text(text) => text.text.as_str(),
+            other => panic!("expected only text, got {:?}", other),
+        })
+        .collect();
+    assert_eq!(texts, vec!["a well-known", "value"]);
+}
@@ -6,10 +6,10 @@ use ratatui::layout::Rect;
 
 /// Check if a coordinate is inside a rectangle
 fn is_inside(coord: Coord, rect: Rect) -> bool {
-    coord.x >= rect.x
-        && coord.x < rect.x + rect.width
-        && coord.y >= rect.y
-        && coord.y < rect.y + rect.height
+    coord.x >= rect.x as i32
+        && coord.x < (rect.x + rect.width) as i32
+        && coord.y >= rect.y as i32
+        && coord.y < (rect.y + rect.height) as i32
 }
 
 /// Static mapping of panel types to their layout accessor functions
@@ -0,0 +1,1011 @@
+//! Key bindings for every remappable action, grouped by the context they apply in.
+//!
+//! Handlers don't match on key codes directly; they resolve the key event in their
+//! context (`keymap.resolve(Context::Selection, key)`) and act on the returned `Action`.
+//! The same table drives the help modal and the statusbar hints, so those always show
+//! the live bindings. Text input (typing into cells, labels, the command line or
+//! property inputs) is not remappable and is handled raw.
+
+#[cfg(test)]
+mod tests;
+
+use crate::events::KeyEvent;
+use crate::tools::Tool;
+use anyhow::{Context as _, Result, anyhow, bail};
+use crossterm::event::{KeyCode, KeyModifiers};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Where a binding applies. Handlers resolve keys in their own context first, and
+/// unhandled keys fall through to `Global`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Context {
+    /// Application-wide shortcuts, handled by `GlobalHandler`
+    Global,
+    /// Canvas navigation and keyboard drawing. Keys that are also bound globally (`l`,
+    /// Space) only act on the canvas while a drawing is in progress.
+    Canvas,
+    /// Select tool with elements selected
+    Selection,
+    /// Edit Table mode, moving between cells
+    TableEdit,
+    /// Edit Vertices mode, reshaping a line or arrow
+    VertexEdit,
+    /// Command line (`:`)
+    Command,
+    /// Properties panel
+    Panel,
+    /// Layers panel
+    Layers,
+    /// Outline panel. Typing filters the list, so only non-character keys are bound.
+    Outline,
+    /// Tools modal, help modal and confirmation dialogs
+    Modal,
+}
+
+impl Context {
+    pub fn all() -> &'static [Context] {
+        &[
+            Context::Global,
+            Context::Canvas,
+            Context::Selection,
+            Context::TableEdit,
+            Context::VertexEdit,
+            Context::Command,
+            Context::Panel,
+            Context::Layers,
+            Context::Outline,
+            Context::Modal,
+        ]
+    }
+
+    /// Name of the `[keymap.<name>]` config section
+    pub fn name(&self) -> &'static str {
+        match self {
+            Context::Global => "global",
+            Context::Canvas => "canvas",
+            Context::Selection => "selection",
+            Context::TableEdit => "table_edit",
+            Context::VertexEdit => "vertex_edit",
+            Context::Command => "command",
+            Context::Panel => "panel",
+            Context::Layers => "layers",
+            Context::Outline => "outline",
+            Context::Modal => "modal",
+        }
+    }
+
+    /// Section title in the help modal
+    pub fn title(&self) -> &'static str {
+        match self {
+            Context::Global => "General",
+            Context::Canvas => "Canvas",
+            Context::Selection => "Selection",
+            Context::TableEdit => "Table Editing",
+            Context::VertexEdit => "Vertex Editing",
+            Context::Command => "Command Mode",
+            Context::Panel => "Properties Panel",
+            Context::Layers => "Layers Panel",
+            Context::Outline => "Outline Panel",
+            Context::Modal => "Modals",
+        }
+    }
+}
+
+/// Something a key can do. What a shared action like `Up` or `Cancel` means depends on
+/// the context it is resolved in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    Undo,
+    Redo,
+    Copy,
+    Cut,
+    Paste,
+    Duplicate,
+    Help,
+    Properties,
+    Layers,
+    Outline,
+    Tools,
+    Command,
+    Save,
+    Open,
+    SelectTool(Tool),
+    Up,
+    Down,
+    Left,
+    Right,
+    PanUp,
+    PanDown,
+    PanLeft,
+    PanRight,
+    Edit,
+    Delete,
+    Group,
+    Ungroup,
+    Raise,
+    Lower,
+    BringToFront,
+    SendToBack,
+    NewLayer,
+    ToggleVisible,
+    MoveToLayer,
+    ToggleSelect,
+    Submit,
+    Cancel,
+    Complete,
+    Anchor,
+    Bend,
+    NextVertex,
+    PrevVertex,
+    InsertBend,
+    Reverse,
+    ToggleLock,
+    Yes,
+    No,
+}
+
+impl Action {
+    /// Name used for the action in the config file
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Undo => "undo",
+            Action::Redo => "redo",
+            Action::Copy => "copy",
+            Action::Cut => "cut",
+            Action::Paste => "paste",
+            Action::Duplicate => "duplicate",
+            Action::Help => "help",
+            Action::Properties => "properties",
+            Action::Layers => "layers",
+            Action::Outline => "outline",
+            Action::Tools => "tools",
+            Action::Command => "command",
+            Action::Save => "save",
+            Action::Open => "open",
+            Action::SelectTool(Tool::Select) => "select",
+            Action::SelectTool(Tool::Line) => "line",
+            Action::SelectTool(Tool::Rectangle) => "rectangle",
+            Action::SelectTool(Tool::Arrow) => "arrow",
+            Action::SelectTool(Tool::Connector) => "connector",
+            Action::SelectTool(Tool::Text) => "text",
+            Action::SelectTool(Tool::Table) => "table",
+            Action::Up => "up",
+            Action::Down => "down",
+            Action::Left => "left",
+            Action::Right => "right",
+            Action::PanUp => "pan_up",
+            Action::PanDown => "pan_down",
+            Action::PanLeft => "pan_left",
+            Action::PanRight => "pan_right",
+            Action::Edit => "edit",
+            Action::Delete => "delete",
+            Action::Submit => "submit",
+            Action::Cancel => "cancel",
+            Action::Complete => "complete",
+            Action::Anchor => "anchor",
+            Action::Bend => "bend",
+            Action::NextVertex => "next_vertex",
+            Action::PrevVertex => "prev_vertex",
+            Action::InsertBend => "insert_bend",
+            Action::Reverse => "reverse",
+            Action::Group => "group",
+            Action::Ungroup => "ungroup",
+            Action::Raise => "raise",
+            Action::Lower => "lower",
+            Action::BringToFront => "bring_to_front",
+            Action::SendToBack => "send_to_back",
+            Action::NewLayer => "new_layer",
+            Action::ToggleVisible => "toggle_visible",
+            Action::MoveToLayer => "move_to_layer",
+            Action::ToggleSelect => "toggle_select",
+            Action::ToggleLock => "toggle_lock",
+            Action::Yes => "yes",
+            Action::No => "no",
+        }
+    }
+}
+
+/// Default bindings: context, action, keys and the description shown in the help
+const DEFAULT_BINDINGS: &[(Context, Action, &[&str], &str)] = &[
+    // Global
+    (
+        Context::Global,
+        Action::SelectTool(Tool::Select),
+        &["s"],
+        "Select tool",
+    ),
+    (
+        Context::Global,
+        Action::SelectTool(Tool::Line),
+        &["l"],
+        "Line tool",
+    ),
+    (
+        Context::Global,
+        Action::SelectTool(Tool::Rectangle),
+        &["r"],
+        "Rectangle tool",
+    ),
+    (
+        Context::Global,
+        Action::SelectTool(Tool::Arrow),
+        &["a"],
+        "Arrow tool",
+    ),
+    (
+        Context::Global,
+        Action::SelectTool(Tool::Connector),
+        &["c"],
+        "Connector tool (auto-routed arrow)",
+    ),
+    (
+        Context::Global,
+        Action::SelectTool(Tool::Text),
+        &["t"],
+        "Text tool",
+    ),
+    (
+        Context::Global,
+        Action::SelectTool(Tool::Table),
+        &[],
+        "Table tool",
+    ),
+    (Context::Global, Action::Undo, &["u", "Ctrl+z"], "Undo"),
+    (Context::Global, Action::Redo, &["Ctrl+r", "Ctrl+y"], "Redo"),
+    (
+        Context::Global,
+        Action::Copy,
+        &["Ctrl+c"],
+        "Copy selected elements",
+    ),
+    (
+        Context::Global,
+        Action::Cut,
+        &["Ctrl+x"],
+        "Cut selected elements",
+    ),
+    (
+        Context::Global,
+        Action::Paste,
+        &["Ctrl+v"],
+        "Paste at cursor",
+    ),
+    (
+        Context::Global,
+        Action::Duplicate,
+        &["Ctrl+d"],
+        "Duplicate selected elements",
+    ),
+    (
+        Context::Global,
+        Action::Tools,
+        &["Space"],
+        "Toggle tools modal",
+    ),
+    (
+        Context::Global,
+        Action::Properties,
+        &["p"],
+        "Toggle properties",
+    ),
+    (Context::Global, Action::Layers, &["L"], "Toggle layers"),
+    (Context::Global, Action::Outline, &["o"], "Toggle outline"),
+    (
+        Context::Global,
+        Action::Command,
+        &[":"],
+        "Enter command mode",
+    ),
+    (
+        Context::Global,
+        Action::Save,
+        &["Ctrl+s"],
+        "Quick save command",
+    ),
+    (
+        Context::Global,
+        Action::Open,
+        &["Ctrl+o"],
+        "Quick open command",
+    ),
+    (Context::Global, Action::Cancel, &["Esc"], "Cancel"),
+    (Context::Global, Action::Help, &["?"], "Toggle help"),
+    (Context::Global, Action::Quit, &["q"], "Quit"),
+    // Canvas
+    (Context::Canvas, Action::Up, &["Up", "k"], "Move cursor up"),
+    (
+        Context::Canvas,
+        Action::Down,
+        &["Down", "j"],
+        "Move cursor down",
+    ),
+    (
+        Context::Canvas,
+        Action::Left,
+        &["Left", "h"],
+        "Move cursor left",
+    ),
+    (
+        Context::Canvas,
+        Action::Right,
+        &["Right", "l"],
+        "Move cursor right",
+    ),
+    (
+        Context::Canvas,
+        Action::Anchor,
+        &["Enter"],
+        "Start/finish drawing or select at cursor",
+    ),
+    (
+        Context::Canvas,
+        Action::Bend,
+        &["Space"],
+        "Add a bend point while drawing",
+    ),
+    (
+        Context::Canvas,
+        Action::PanUp,
+        &["Shift+Up"],
+        "Pan canvas up",
+    ),
+    (
+        Context::Canvas,
+        Action::PanDown,
+        &["Shift+Down"],
+        "Pan canvas down",
+    ),
+    (
+        Context::Canvas,
+        Action::PanLeft,
+        &["Shift+Left"],
+        "Pan canvas left",
+    ),
+    (
+        Context::Canvas,
+        Action::PanRight,
+        &["Shift+Right"],
+        "Pan canvas right",
+    ),
+    // Selection
+    (
+        Context::Selection,
+        Action::Up,
+        &["Up"],
+        "Move selected elements up",
+    ),
+    (
+        Context::Selection,
+        Action::Down,
+        &["Down"],
+        "Move selected elements down",
+    ),
+    (
+        Context::Selection,
+        Action::Left,
+        &["Left"],
+        "Move selected elements left",
+    ),
+    (
+        Context::Selection,
+        Action::Right,
+        &["Right"],
+        "Move selected elements right",
+    ),
+    (
+        Context::Selection,
+        Action::Delete,
+        &["Backspace", "Delete"],
+        "Delete selected elements",
+    ),
+    (
+        Context::Selection,
+        Action::Edit,
+        &["Enter", "e"],
+        "Edit table cells, rectangle label or line bends, or enter a group",
+    ),
+    (
+        Context::Selection,
+        Action::Group,
+        &["Ctrl+g"],
+        "Group selected elements",
+    ),
+    (
+        Context::Selection,
+        Action::Ungroup,
+        // Terminals that can't report Shift with Ctrl send Ctrl+Shift+G as Ctrl+G
+        &["Ctrl+Shift+g", "Alt+g"],
+        "Ungroup selected groups",
+    ),
+    (
+        Context::Selection,
+        Action::Raise,
+        &["]"],
+        "Raise selected elements one step",
+    ),
+    (
+        Context::Selection,
+        Action::Lower,
+        &["["],
+        "Lower selected elements one step",
+    ),
+    (
+        Context::Selection,
+        Action::BringToFront,
+        &["}"],
+        "Bring selected elements to front",
+    ),
+    (
+        Context::Selection,
+        Action::SendToBack,
+        &["{"],
+        "Send selected elements to back",
+    ),
+    // Table editing
+    (Context::TableEdit, Action::Up, &["Up"], "Select cell above"),
+    (
+        Context::TableEdit,
+        Action::Down,
+        &["Down"],
+        "Select cell below",
+    ),
+    (
+        Context::TableEdit,
+        Action::Left,
+        &["Left"],
+        "Select cell to the left",
+    ),
+    (
+        Context::TableEdit,
+        Action::Right,
+        &["Right"],
+        "Select cell to the right",
+    ),
+    (
+        Context::TableEdit,
+        Action::Edit,
+        &["Enter"],
+        "Edit selected cell",
+    ),
+    (
+        Context::TableEdit,
+        Action::Cancel,
+        &["Esc", "q"],
+        "Exit table editing",
+    ),
+    // Vertex editing
+    (
+        Context::VertexEdit,
+        Action::Up,
+        &["Up", "k"],
+        "Move vertex up",
+    ),
+    (
+        Context::VertexEdit,
+        Action::Down,
+        &["Down", "j"],
+        "Move vertex down",
+    ),
+    (
+        Context::VertexEdit,
+        Action::Left,
+        &["Left", "h"],
+        "Move vertex left",
+    ),
+    (
+        Context::VertexEdit,
+        Action::Right,
+        &["Right", "l"],
+        "Move vertex right",
+    ),
+    (
+        Context::VertexEdit,
+        Action::NextVertex,
+        &["Tab", "]"],
+        "Select next vertex",
+    ),
+    (
+        Context::VertexEdit,
+        Action::PrevVertex,
+        &["["],
+        "Select previous vertex",
+    ),
+    (
+        Context::VertexEdit,
+        Action::InsertBend,
+        &["i", "+"],
+        "Insert a bend after the vertex",
+    ),
+    (
+        Context::VertexEdit,
+        Action::Delete,
+        &["x", "Del"],
+        "Remove the bend",
+    ),
+    (
+        Context::VertexEdit,
+        Action::Reverse,
+        &["r"],
+        "Reverse direction",
+    ),
+    (
+        Context::VertexEdit,
+        Action::Submit,
+        &["Enter"],
+        "Finish editing",
+    ),
+    (
+        Context::VertexEdit,
+        Action::Cancel,
+        &["Esc"],
+        "Discard changes",
+    ),
+    // Command mode
+    (Context::Command, Action::Submit, &["Enter"], "Run command"),
+    (
+        Context::Command,
+        Action::Cancel,
+        &["Esc"],
+        "Leave command mode",
+    ),
+    (
+        Context::Command,
+        Action::Up,
+        &["Up"],
+        "Previous command in history",
+    ),
+    (
+        Context::Command,
+        Action::Down,
+        &["Down"],
+        "Next command in history",
+    ),
+    (
+        Context::Command,
+        Action::Complete,
+        &["Tab"],
+        "Complete command or file name",
+    ),
+    // Properties panel
+    (
+        Context::Panel,
+        Action::Up,
+        &["Up", "k"],
+        "Focus previous property",
+    ),
+    (
+        Context::Panel,
+        Action::Down,
+        &["Down", "j", "Tab"],
+        "Focus next property",
+    ),
+    (
+        Context::Panel,
+        Action::Edit,
+        &["Enter"],
+        "Edit focused property",
+    ),
+    // Layers panel
+    (
+        Context::Layers,
+        Action::Up,
+        &["Up", "k"],
+        "Focus layer above",
+    ),
+    (
+        Context::Layers,
+        Action::Down,
+        &["Down", "j"],
+        "Focus layer below",
+    ),
+    (
+        Context::Layers,
+        Action::Raise,
+        &["Shift+Up", "K"],
+        "Move layer up",
+    ),
+    (
+        Context::Layers,
+        Action::Lower,
+        &["Shift+Down", "J"],
+        "Move layer down",
+    ),
+    (
+        Context::Layers,
+        Action::Submit,
+        &["Enter"],
+        "Draw on focused layer",
+    ),
+    (
+        Context::Layers,
+        Action::NewLayer,
+        &["n"],
+        "Add a layer above the focused one",
+    ),
+    (Context::Layers, Action::Edit, &["r"], "Rename layer"),
+    (
+        Context::Layers,
+        Action::ToggleVisible,
+        &["v"],
+        "Show or hide layer",
+    ),
+    (
+        Context::Layers,
+        Action::ToggleLock,
+        &["l"],
+        "Lock or unlock layer",
+    ),
+    (
+        Context::Layers,
+        Action::MoveToLayer,
+        &["m"],
+        "Move selected elements to layer",
+    ),
+    // Outline panel
+    (
+        Context::Outline,
+        Action::Up,
+        &["Up"],
+        "Focus previous element",
+    ),
+    (
+        Context::Outline,
+        Action::Down,
+        &["Down"],
+        "Focus next element",
+    ),
+    (
+        Context::Outline,
+        Action::PanUp,
+        &["PageUp"],
+        "Scroll up a page",
+    ),
+    (
+        Context::Outline,
+        Action::PanDown,
+        &["PageDown"],
+        "Scroll down a page",
+    ),
+    (
+        Context::Outline,
+        Action::Submit,
+        &["Enter"],
+        "Select element and jump to it",
+    ),
+    (
+        Context::Outline,
+        Action::ToggleSelect,
+        &["Tab"],
+        "Add to or remove from selection",
+    ),
+    (
+        Context::Outline,
+        Action::Edit,
+        &["Ctrl+n"],
+        "Rename element",
+    ),
+    (
+        Context::Outline,
+        Action::Cancel,
+        &["Esc"],
+        "Clear filter, or close outline",
+    ),
+    // Modals
+    (
+        Context::Modal,
+        Action::Up,
+        &["Up", "k"],
+        "Previous tool / scroll help up",
+    ),
+    (
+        Context::Modal,
+        Action::Down,
+        &["Down", "j"],
+        "Next tool / scroll help down",
+    ),
+    (
+        Context::Modal,
+        Action::Submit,
+        &["Enter"],
+        "Close tools modal",
+    ),
+    (
+        Context::Modal,
+        Action::ToggleLock,
+        &["Tab", "x"],
+        "Toggle tool lock",
+    ),
+    (Context::Modal, Action::Yes, &["y"], "Confirm dialog"),
+    (Context::Modal, Action::No, &["n", "Esc"], "Dismiss dialog"),
+];
+
+/// A key with modifiers, e.g. `Ctrl+z` or `Shift+Up`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    /// Parse a key like `q`, `Space`, `Ctrl+z` or `Shift+Left`
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = text;
+        // A lone "+" is a key, not a separator
+        while let Some((modifier, key)) = rest.split_once('+')
+            && !key.is_empty()
+        {
+            modifiers |= match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" => KeyModifiers::CONTROL,
+                "shift" => KeyModifiers::SHIFT,
+                "alt" => KeyModifiers::ALT,
+                _ => bail!("unknown modifier `{}` in key {:?}", modifier, text),
+            };
+            rest = key;
+        }
+
+        let code = match rest.to_ascii_lowercase().as_str() {
+            "space" => KeyCode::Char(' '),
+            "enter" => KeyCode::Enter,
+            "esc" => KeyCode::Esc,
+            "tab" => KeyCode::Tab,
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            _ => {
+                let mut chars = rest.chars();
+                match (chars.next(), chars.next()) {
+                    // Terminals report Ctrl+letter as the lowercase letter
+                    (Some(c), None) if modifiers.contains(KeyModifiers::CONTROL) => {
+                        KeyCode::Char(c.to_ascii_lowercase())
+                    }
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => bail!("unknown key {:?}", text),
+                }
+            }
+        };
+
+        Ok(Self { code, modifiers })
+    }
+
+    pub fn matches(&self, key_event: &KeyEvent) -> bool {
+        // Ctrl+Shift+letter arrives as the uppercase letter or with the Shift modifier,
+        // and Shift tells it apart from Ctrl+letter
+        if let (KeyCode::Char(c), KeyCode::Char(pressed)) = (self.code, key_event.code)
+            && self.modifiers.contains(KeyModifiers::CONTROL)
+        {
+            let mut modifiers = key_event.modifiers;
+            if pressed.is_ascii_uppercase() {
+                modifiers |= KeyModifiers::SHIFT;
+            }
+            return c == pressed.to_ascii_lowercase() && self.modifiers == modifiers;
+        }
+        if self.code != key_event.code {
+            return false;
+        }
+        // Shift is part of the character itself (`?`, `Q`), so ignore it for characters
+        let relevant = |m: KeyModifiers| match self.code {
+            KeyCode::Char(_) => m - KeyModifiers::SHIFT,
+            _ => m,
+        };
+        relevant(self.modifiers) == relevant(key_event.modifiers)
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ctrl = self.modifiers.contains(KeyModifiers::CONTROL);
+        if ctrl {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) if ctrl => write!(f, "{}", c.to_ascii_uppercase()),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::Backspace => write!(f, "⌫"),
+            KeyCode::Delete => write!(f, "Del"),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+/// One action's keys in one context
+#[derive(Debug, Clone)]
+pub struct Binding {
+    pub context: Context,
+    pub action: Action,
+    pub keys: Vec<KeyBinding>,
+    pub description: &'static str,
+}
+
+impl Binding {
+    /// Keys joined for display, e.g. `u/Ctrl+Z`
+    pub fn label(&self) -> String {
+        let keys: Vec<String> = self.keys.iter().map(KeyBinding::to_string).collect();
+        keys.join("/")
+    }
+}
+
+/// Live key bindings: the defaults with the overrides from the config file applied
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<Binding>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = DEFAULT_BINDINGS
+            .iter()
+            .map(|&(context, action, keys, description)| Binding {
+                context,
+                action,
+                keys: keys
+                    .iter()
+                    .map(|key| KeyBinding::parse(key).expect("invalid default key binding"))
+                    .collect(),
+                description,
+            })
+            .collect();
+        Self { bindings }
+    }
+}
+
+impl Keymap {
+    /// Build the keymap from the `[keymap.*]` sections of the config file. Each entry
+    /// replaces all default keys of that action in that context.
+    pub fn from_config(config: &KeymapConfig) -> Result<Self> {
+        let mut keymap = Self::default();
+
+        for &context in Context::all() {
+            for (name, keys) in config.section(context) {
+                let section = format!("[keymap.{}]", context.name());
+                let binding = keymap
+                    .bindings
+                    .iter_mut()
+                    .find(|b| b.context == context && b.action.name() == name)
+                    .ok_or_else(|| anyhow!("unknown action `{}` in {}", name, section))?;
+                binding.keys = keys
+                    .as_slice()
+                    .iter()
+                    .map(|key| KeyBinding::parse(key))
+                    .collect::<Result<_>>()
+                    .with_context(|| format!("invalid key for `{}` in {}", name, section))?;
+            }
+        }
+
+        keymap.validate()?;
+        Ok(keymap)
+    }
+
+    /// Reject keys bound to more than one action in the same context
+    fn validate(&self) -> Result<()> {
+        for (i, binding) in self.bindings.iter().enumerate() {
+            for key in &binding.keys {
+                let earlier = self.bindings[..i]
+                    .iter()
+                    .find(|b| b.context == binding.context && b.keys.contains(key));
+                if let Some(other) = earlier {
+                    bail!(
+                        "key {} is bound to both `{}` and `{}` in [keymap.{}]",
+                        key,
+                        other.action.name(),
+                        binding.action.name(),
+                        binding.context.name()
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The action bound to the key in the given context, if any
+    pub fn resolve(&self, context: Context, key_event: &KeyEvent) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|b| b.context == context && b.keys.iter().any(|k| k.matches(key_event)))
+            .map(|b| b.action)
+    }
+
+    /// Bindings of a context, in help order
+    pub fn bindings(&self, context: Context) -> impl Iterator<Item = &Binding> {
+        self.bindings.iter().filter(move |b| b.context == context)
+    }
+
+    /// Keys of an action for display, e.g. `u/Ctrl+Z` (empty if it has none)
+    pub fn label(&self, context: Context, action: Action) -> String {
+        self.bindings(context)
+            .find(|b| b.action == action)
+            .map(Binding::label)
+            .unwrap_or_default()
+    }
+
+    /// First key of an action, for short hints like the statusbar's
+    pub fn key(&self, context: Context, action: Action) -> Option<KeyBinding> {
+        self.bindings(context)
+            .find(|b| b.action == action)
+            .and_then(|b| b.keys.first().copied())
+    }
+
+    /// First key that selects the given tool, for the tools modal
+    pub fn tool_key(&self, tool: Tool) -> Option<KeyBinding> {
+        self.key(Context::Global, Action::SelectTool(tool))
+    }
+}
+
+/// Keys given for an action in the config file: one key or a list
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Keys {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Keys {
+    fn as_slice(&self) -> &[String] {
+        match self {
+            Keys::One(key) => std::slice::from_ref(key),
+            Keys::Many(keys) => keys,
+        }
+    }
+}
+
+/// The `[keymap.*]` sections of the config file: action name -> keys, per context
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KeymapConfig {
+    global: HashMap<String, Keys>,
+    canvas: HashMap<String, Keys>,
+    selection: HashMap<String, Keys>,
+    table_edit: HashMap<String, Keys>,
+    vertex_edit: HashMap<String, Keys>,
+    command: HashMap<String, Keys>,
+    panel: HashMap<String, Keys>,
+    layers: HashMap<String, Keys>,
+    outline: HashMap<String, Keys>,
+    modal: HashMap<String, Keys>,
+}
+
+impl KeymapConfig {
+    fn section(&self, context: Context) -> &HashMap<String, Keys> {
+        match context {
+            Context::Global => &self.global,
+            Context::Canvas => &self.canvas,
+            Context::Selection => &self.selection,
+            Context::TableEdit => &self.table_edit,
+            Context::VertexEdit => &self.vertex_edit,
+            Context::Command => &self.command,
+            Context::Panel => &self.panel,
+            Context::Layers => &self.layers,
+            Context::Outline => &self.outline,
+            Context::Modal => &self.modal,
+        }
+    }
+}
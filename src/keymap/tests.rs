@@ -0,0 +1,139 @@
+use super::{Action, Context, KeyBinding, Keymap, KeymapConfig};
+use crate::events::{EventHandler, EventResult, GlobalHandler, KeyEvent};
+use crate::state::AppState;
+use crate::tools::Tool;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+    KeyEvent { code, modifiers }
+}
+
+fn keymap(toml: &str) -> Keymap {
+    let config: KeymapConfig = toml::from_str(toml).unwrap();
+    Keymap::from_config(&config).unwrap()
+}
+
+#[test]
+fn test_parse_and_display_keys() {
+    let cases = [
+        ("q", KeyCode::Char('q'), KeyModifiers::NONE, "q"),
+        (
+            "Ctrl+Z",
+            KeyCode::Char('z'),
+            KeyModifiers::CONTROL,
+            "Ctrl+Z",
+        ),
+        ("shift+up", KeyCode::Up, KeyModifiers::SHIFT, "Shift+↑"),
+        ("Space", KeyCode::Char(' '), KeyModifiers::NONE, "Space"),
+        ("Del", KeyCode::Delete, KeyModifiers::NONE, "Del"),
+        ("+", KeyCode::Char('+'), KeyModifiers::NONE, "+"),
+        (
+            "Ctrl++",
+            KeyCode::Char('+'),
+            KeyModifiers::CONTROL,
+            "Ctrl++",
+        ),
+    ];
+    for (text, code, modifiers, display) in cases {
+        let binding = KeyBinding::parse(text).unwrap();
+        assert_eq!(binding, KeyBinding { code, modifiers }, "{}", text);
+        assert_eq!(binding.to_string(), display);
+    }
+
+    assert!(KeyBinding::parse("Hyper+q").is_err());
+    assert!(KeyBinding::parse("qq").is_err());
+}
+
+#[test]
+fn test_resolve_depends_on_context() {
+    let keymap = Keymap::default();
+    let up = key(KeyCode::Up, KeyModifiers::NONE);
+    let q = key(KeyCode::Char('q'), KeyModifiers::NONE);
+
+    assert_eq!(keymap.resolve(Context::Selection, &up), Some(Action::Up));
+    assert_eq!(keymap.resolve(Context::Canvas, &up), Some(Action::Up));
+    assert_eq!(keymap.resolve(Context::Command, &q), None);
+    assert_eq!(
+        keymap.resolve(Context::Canvas, &key(KeyCode::Up, KeyModifiers::SHIFT)),
+        Some(Action::PanUp)
+    );
+    assert_eq!(keymap.resolve(Context::Global, &q), Some(Action::Quit));
+    assert_eq!(keymap.resolve(Context::TableEdit, &q), Some(Action::Cancel));
+    assert_eq!(
+        keymap.resolve(
+            Context::Global,
+            &key(KeyCode::Char('r'), KeyModifiers::CONTROL)
+        ),
+        Some(Action::Redo)
+    );
+    // Shift is part of the character for `?`
+    assert_eq!(
+        keymap.resolve(
+            Context::Global,
+            &key(KeyCode::Char('?'), KeyModifiers::SHIFT)
+        ),
+        Some(Action::Help)
+    );
+}
+
+#[test]
+fn test_config_replaces_default_keys() {
+    let keymap = keymap(
+        r#"
+[global]
+rectangle = "b"
+undo = ["z", "Ctrl+z"]
+
+[selection]
+left = "h"
+"#,
+    );
+
+    let b = key(KeyCode::Char('b'), KeyModifiers::NONE);
+    let r = key(KeyCode::Char('r'), KeyModifiers::NONE);
+    assert_eq!(
+        keymap.resolve(Context::Global, &b),
+        Some(Action::SelectTool(Tool::Rectangle))
+    );
+    assert_eq!(keymap.resolve(Context::Global, &r), None);
+    assert_eq!(keymap.label(Context::Global, Action::Undo), "z/Ctrl+Z");
+    assert_eq!(keymap.label(Context::Selection, Action::Left), "h");
+    assert_eq!(
+        keymap.resolve(Context::Selection, &key(KeyCode::Left, KeyModifiers::NONE)),
+        None
+    );
+}
+
+#[test]
+fn test_handlers_use_remapped_keys() {
+    let mut state = AppState::new();
+    state.keymap = keymap("[global]\nrectangle = \"b\"\n");
+    let mut handler = GlobalHandler;
+
+    let result = handler.handle_key_event(&mut state, &key(KeyCode::Char('b'), KeyModifiers::NONE));
+    assert_eq!(result, EventResult::Consumed);
+    assert_eq!(state.tool.selected_tool, Tool::Rectangle);
+
+    // The old key no longer does anything
+    let result = handler.handle_key_event(&mut state, &key(KeyCode::Char('r'), KeyModifiers::NONE));
+    assert_eq!(result, EventResult::Ignored);
+}
+
+#[test]
+fn test_ctrl_shift_letter_is_distinct_from_ctrl_letter() {
+    let keymap = Keymap::default();
+    let ctrl_g = key(KeyCode::Char('g'), KeyModifiers::CONTROL);
+    // Terminals report Shift with the uppercase letter
+    let ctrl_shift_g = key(
+        KeyCode::Char('G'),
+        KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+    );
+    assert_eq!(
+        keymap.resolve(Context::Selection, &ctrl_g),
+        Some(Action::Group)
+    );
+    assert_eq!(
+        keymap.resolve(Context::Selection, &ctrl_shift_g),
+        Some(Action::Ungroup)
+    );
+}
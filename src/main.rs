@@ -1,9 +1,13 @@
 mod cli;
 mod components;
+mod config;
 mod elements;
 mod events;
+mod export;
 mod file;
+mod import;
 mod input;
+mod keymap;
 mod state;
 mod tools;
 mod types;
@@ -17,15 +21,45 @@ use crossterm::execute;
 use events::GlobalHandler;
 use ratatui::DefaultTerminal;
 use state::AppState;
+use std::time::Duration;
 use ui::UI;
 
+/// How long the event loop waits for input before running periodic work
+const TICK_RATE: Duration = Duration::from_millis(500);
+
 fn main() -> Result<()> {
     let cli = cli::Cli::parse();
 
+    // Handle subcommands
+    match cli.command {
+        Some(cli::Command::Export(args)) => {
+            let options = export::ExportOptions {
+                font_size: args.font_size,
+                padding: args.padding,
+            };
+            return export::export_file(
+                &args.file,
+                args.format,
+                args.output.as_deref(),
+                &args.layers,
+                options,
+            );
+        }
+        Some(cli::Command::Import(args)) => {
+            let output = args
+                .output
+                .unwrap_or_else(|| import::default_output_path(&args.file));
+            let count = import::import_file(&args.file, &output)?;
+            println!("Imported {} elements into {}", count, output);
+            return Ok(());
+        }
+        None => {}
+    }
+
     // Handle render mode
     if cli.render {
         if let Some(file_path) = cli.file {
-            return file::render_file(&file_path);
+            return file::render_file(&file_path, &cli.layers);
         } else {
             eprintln!("Error: --render requires a file argument");
             std::process::exit(1);
@@ -33,11 +67,15 @@ fn main() -> Result<()> {
     }
 
     // Normal TUI mode
+    // Load the config first so errors in it are reported before the terminal is taken over
+    let config = config::Config::load()?;
+    ui::set_theme(config.theme.clone());
+
     // Enable mouse capture
     execute!(std::io::stdout(), EnableMouseCapture)?;
 
     let terminal = ratatui::init();
-    let result = run(terminal, cli.file);
+    let result = run(terminal, cli.file, &config);
     ratatui::restore();
 
     // Disable mouse capture
@@ -46,8 +84,8 @@ fn main() -> Result<()> {
     result
 }
 
-fn run(mut terminal: DefaultTerminal, file: Option<String>) -> Result<()> {
-    let mut state = AppState::new();
+fn run(mut terminal: DefaultTerminal, file: Option<String>, config: &config::Config) -> Result<()> {
+    let mut state = AppState::with_config(config);
     let mut ui = UI::new();
     let mut global_handler = GlobalHandler;
 
@@ -59,6 +97,9 @@ fn run(mut terminal: DefaultTerminal, file: Option<String>) -> Result<()> {
         }
     }
 
+    // Offer to recover changes if an earlier session crashed before saving
+    state.offer_recovery();
+
     // Main render and event loop.
     //
     // ratatui follows immediate mode rendering where the entire UI is redrawn every frame.
@@ -67,17 +108,14 @@ fn run(mut terminal: DefaultTerminal, file: Option<String>) -> Result<()> {
     //
     // Flow:
     // 1. Render the current state
-    // 2. Block waiting for an event (keyboard, mouse, etc.)
-    // 3. Handle the event and mutate state
-    // 4. Loop back to re-render with updated state
-    //
-    // Important: Since we use blocking event reads (event::read()), the UI is only redrawn
-    // after an event occurs. This means if state is mutated outside of event handling,
-    // the UI will be out of sync until the next event. For our case, all state changes
-    // happen through events, so this simple blocking approach works well.
+    // 2. Wait for an event (keyboard, mouse, etc.), at most one tick
+    // 3. If one arrived, handle it and mutate state
+    // 4. Run periodic work (autosave)
+    // 5. Loop back to re-render with updated state
     //
-    // Alternative: We could use polling (event::poll()) with separate render/event loops
-    // to support async state updates, but that adds complexity we don't currently need.
+    // Polling with a timeout (event::poll()) instead of blocking on event::read() gives
+    // the loop a regular tick, so work that isn't triggered by an event, like writing
+    // the swap file, still happens while the user is idle.
     loop {
         // Render phase: Draw UI based on current state (read-only)
         terminal.draw(|frame| {
@@ -86,19 +124,30 @@ fn run(mut terminal: DefaultTerminal, file: Option<String>) -> Result<()> {
             ui.render(frame, &state);
         })?;
 
-        // Event phase: Block until next event, then handle it (mutates state)
-        let event = crossterm::event::read()?;
+        // Event phase: Wait up to one tick for the next event, then handle it (mutates state)
+        if crossterm::event::poll(TICK_RATE)? {
+            let event = crossterm::event::read()?;
+
+            // Build handler list from UI components + global handler
+            let mut event_handlers = ui.component_event_handlers();
+            event_handlers.push(&mut global_handler);
 
-        // Build handler list from UI components + global handler
-        let mut event_handlers = ui.component_event_handlers();
-        event_handlers.push(&mut global_handler);
+            let should_quit = events::handle_event(event, &mut event_handlers, &mut state)?;
 
-        let should_quit = events::handle_event(event, &mut event_handlers, &mut state)?;
+            // Record an undo step if the event changed the canvas
+            state.commit_history();
 
-        if should_quit {
-            break;
+            if should_quit {
+                break;
+            }
         }
+
+        // Tick phase: Write unsaved changes to the swap file
+        state.autosave();
     }
 
+    // Quitting means the changes were saved or deliberately discarded
+    state.remove_swap()?;
+
     Ok(())
 }
@@ -1,6 +1,7 @@
 use crate::elements::Element;
 use crate::file::DiagramFile;
-use crate::types::RenderMap;
+use crate::state::{Group, Layer};
+use crate::types::{RenderMap, ZOrder};
 use anyhow::Result;
 use std::collections::HashMap;
 use std::path::Path;
@@ -8,6 +9,21 @@ use std::path::Path;
 /// Represents the drawing canvas with element-based storage
 pub struct CanvasState {
     elements: Vec<Element>,
+    groups: Vec<Group>,
+    layers: Vec<Layer>,
+    // Where new elements go; not part of the diagram itself
+    active_layer: usize,
+    next_id: usize,
+    // Bumped on every mutation so history can detect changes
+    revision: u64,
+}
+
+/// Point-in-time copy of the canvas contents, used for undo/redo
+#[derive(Debug, Clone, PartialEq)]
+pub struct CanvasSnapshot {
+    elements: Vec<Element>,
+    groups: Vec<Group>,
+    layers: Vec<Layer>,
     next_id: usize,
 }
 
@@ -15,14 +31,20 @@ impl CanvasState {
     pub fn new() -> Self {
         Self {
             elements: Vec::new(),
+            groups: Vec::new(),
+            layers: Layer::default_layers(),
+            active_layer: 0,
             next_id: 0,
+            revision: 0,
         }
     }
 
     /// Add an element to the canvas and return its ID
-    pub fn add_element(&mut self, element: Element) -> usize {
+    pub fn add_element(&mut self, mut element: Element) -> usize {
         let id = element.id();
+        element.set_layer(self.active_layer());
         self.elements.push(element);
+        self.revision += 1;
         id
     }
 
@@ -30,7 +52,7 @@ impl CanvasState {
     /// Returns HashMap of (x, y) -> char
     pub fn build_render_map(&self) -> RenderMap {
         let mut render_map = HashMap::new();
-        for element in &self.elements {
+        for element in self.draw_order() {
             let points = element.render_points();
             for (x, y, ch) in points {
                 render_map.insert((x, y), ch);
@@ -39,17 +61,30 @@ impl CanvasState {
         render_map
     }
 
-    /// Find the topmost element at position (x, y)
+    /// Build a render map that also records which element drew each character
+    /// Returns HashMap of (x, y) -> (char, element_id)
+    pub fn build_owned_render_map(&self) -> HashMap<(i32, i32), (char, usize)> {
+        let mut render_map = HashMap::new();
+        for element in self.draw_order() {
+            let id = element.id();
+            for (x, y, ch) in element.render_points() {
+                render_map.insert((x, y), (ch, id));
+            }
+        }
+        render_map
+    }
+
+    /// Find the topmost selectable element at position (x, y)
     pub fn find_element_at(&self, x: i32, y: i32) -> Option<usize> {
-        for element in self.elements.iter().rev() {
-            if element.contains_point(x, y) {
+        for element in self.draw_order().into_iter().rev() {
+            if element.contains_point(x, y) && self.is_selectable(element) {
                 return Some(element.id());
             }
         }
         None
     }
 
-    /// Find all elements that are fully contained within the given rectangle
+    /// Find all selectable elements that are fully contained within the given rectangle
     pub fn find_elements_fully_inside_rect(
         &self,
         x1: i32,
@@ -59,7 +94,7 @@ impl CanvasState {
     ) -> Vec<usize> {
         self.elements
             .iter()
-            .filter(|e| e.is_fully_inside_rect(x1, y1, x2, y2))
+            .filter(|e| e.is_fully_inside_rect(x1, y1, x2, y2) && self.is_selectable(e))
             .map(|e| e.id())
             .collect()
     }
@@ -69,25 +104,110 @@ impl CanvasState {
         self.elements.iter().find(|e| e.id() == id)
     }
 
-    /// Get mutable reference to an element by ID
+    /// Get mutable reference to an element by ID.
+    /// Counts as a modification of the canvas.
     pub fn get_element_mut(&mut self, id: usize) -> Option<&mut Element> {
+        self.revision += 1;
         self.elements.iter_mut().find(|e| e.id() == id)
     }
 
-    /// Remove element by ID
+    /// Remove element by ID, detaching any connectors attached to it
     pub fn remove_element(&mut self, id: usize) -> Option<Element> {
         if let Some(pos) = self.elements.iter().position(|e| e.id() == id) {
-            Some(self.elements.remove(pos))
+            self.revision += 1;
+            let element = self.elements.remove(pos);
+            self.detach_connectors_from(id);
+            self.remove_from_groups(id);
+            Some(element)
         } else {
             None
         }
     }
 
+    /// Change where the given elements are drawn. Elements later in the list are drawn
+    /// on top and are found first by `find_element_at`; the given elements keep their
+    /// order among themselves. Returns false if nothing moved.
+    pub fn reorder(&mut self, ids: &[usize], order: ZOrder) -> bool {
+        let picked: Vec<bool> = self
+            .elements
+            .iter()
+            .map(|e| ids.contains(&e.id()))
+            .collect();
+        let mut indices: Vec<usize> = (0..self.elements.len()).collect();
+        match order {
+            ZOrder::Raise => {
+                for i in (0..indices.len().saturating_sub(1)).rev() {
+                    if picked[indices[i]] && !picked[indices[i + 1]] {
+                        indices.swap(i, i + 1);
+                    }
+                }
+            }
+            ZOrder::Lower => {
+                for i in 1..indices.len() {
+                    if picked[indices[i]] && !picked[indices[i - 1]] {
+                        indices.swap(i - 1, i);
+                    }
+                }
+            }
+            ZOrder::Front => indices.sort_by_key(|&i| picked[i]),
+            ZOrder::Back => indices.sort_by_key(|&i| !picked[i]),
+        }
+
+        if indices
+            .iter()
+            .enumerate()
+            .all(|(position, &i)| position == i)
+        {
+            return false;
+        }
+        let mut elements: Vec<Option<Element>> = self.elements.drain(..).map(Some).collect();
+        self.elements = indices
+            .into_iter()
+            .filter_map(|i| elements[i].take())
+            .collect();
+        self.revision += 1;
+        true
+    }
+
     /// Get all elements
     pub fn elements(&self) -> &[Element] {
         &self.elements
     }
 
+    /// Get all groups
+    pub fn groups(&self) -> &[Group] {
+        &self.groups
+    }
+
+    /// Get mutable access to the groups.
+    /// Counts as a modification of the canvas.
+    pub(super) fn groups_mut(&mut self) -> &mut Vec<Group> {
+        self.revision += 1;
+        &mut self.groups
+    }
+
+    /// Get all layers, bottom first
+    pub fn layers(&self) -> &[Layer] {
+        &self.layers
+    }
+
+    /// Get mutable access to the layers.
+    /// Counts as a modification of the canvas.
+    pub(super) fn layers_mut(&mut self) -> &mut Vec<Layer> {
+        self.revision += 1;
+        &mut self.layers
+    }
+
+    /// ID of the layer chosen for new elements (see `active_layer`, which also covers
+    /// the layer having been removed by undo)
+    pub(super) fn active_layer_id(&self) -> usize {
+        self.active_layer
+    }
+
+    pub fn set_active_layer(&mut self, id: usize) {
+        self.active_layer = id;
+    }
+
     /// Increment and return next ID
     pub fn get_next_id(&mut self) -> usize {
         let id = self.next_id;
@@ -95,6 +215,30 @@ impl CanvasState {
         id
     }
 
+    /// Current revision, incremented on every mutation
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Capture the current contents for undo/redo
+    pub fn snapshot(&self) -> CanvasSnapshot {
+        CanvasSnapshot {
+            elements: self.elements.clone(),
+            groups: self.groups.clone(),
+            layers: self.layers.clone(),
+            next_id: self.next_id,
+        }
+    }
+
+    /// Replace the current contents with a previously captured snapshot
+    pub fn restore(&mut self, snapshot: CanvasSnapshot) {
+        self.elements = snapshot.elements;
+        self.groups = snapshot.groups;
+        self.layers = snapshot.layers;
+        self.next_id = snapshot.next_id;
+        self.revision += 1;
+    }
+
     /// Check if canvas is empty
     pub fn is_empty(&self) -> bool {
         self.elements.is_empty()
@@ -103,7 +247,8 @@ impl CanvasState {
     /// Get the bounding box of all elements (min_x, min_y, max_x, max_y)
     /// Returns (0, 0, 0, 0) if canvas is empty
     pub fn bounds(&self) -> (i32, i32, i32, i32) {
-        if self.elements.is_empty() {
+        let elements = self.draw_order();
+        if elements.is_empty() {
             return (0, 0, 0, 0);
         }
 
@@ -112,12 +257,12 @@ impl CanvasState {
         let mut max_x = i32::MIN;
         let mut max_y = i32::MIN;
 
-        for element in &self.elements {
+        for element in elements {
             let bounds = element.bounds();
-            min_x = min_x.min(bounds.min.x as i32);
-            min_y = min_y.min(bounds.min.y as i32);
-            max_x = max_x.max(bounds.max.x as i32);
-            max_y = max_y.max(bounds.max.y as i32);
+            min_x = min_x.min(bounds.min.x);
+            min_y = min_y.min(bounds.min.y);
+            max_x = max_x.max(bounds.max.x);
+            max_y = max_y.max(bounds.max.y);
         }
 
         (min_x, min_y, max_x, max_y)
@@ -125,7 +270,12 @@ impl CanvasState {
 
     /// Save the canvas to a file
     pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
-        let diagram = DiagramFile::new(self.elements.clone(), self.next_id);
+        let diagram = DiagramFile::new(
+            self.elements.clone(),
+            self.groups.clone(),
+            self.layers.clone(),
+            self.next_id,
+        );
         diagram.save(path)
     }
 
@@ -134,7 +284,14 @@ impl CanvasState {
         let diagram = DiagramFile::load(path)?;
 
         self.elements = diagram.elements;
+        self.groups = diagram.groups;
+        self.layers = diagram.layers;
+        if self.layers.is_empty() {
+            self.layers = Layer::default_layers();
+        }
+        self.active_layer = self.layers[0].id;
         self.next_id = diagram.next_id;
+        self.revision += 1;
 
         Ok(())
     }
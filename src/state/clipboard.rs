@@ -0,0 +1,128 @@
+use crate::elements::{Attachment, Element};
+use crate::state::{CanvasState, Group, Member};
+use std::collections::HashMap;
+
+/// Internal clipboard holding copies of elements and of the groups among them.
+/// Lives on `AppState`, so its contents survive opening another file.
+pub struct ClipboardState {
+    elements: Vec<Element>,
+    groups: Vec<Group>,
+}
+
+impl ClipboardState {
+    pub fn new() -> Self {
+        Self {
+            elements: Vec::new(),
+            groups: Vec::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Replace clipboard contents with copies of the given elements
+    pub fn copy(&mut self, ids: &[usize], canvas: &CanvasState) {
+        self.elements = clone_elements(ids, canvas);
+        self.groups = clone_groups(ids, canvas);
+    }
+
+    /// Paste clipboard contents with their top-left corner at (x, y).
+    /// Returns the IDs of the pasted elements.
+    pub fn paste_at(&self, x: i32, y: i32, canvas: &mut CanvasState) -> Vec<usize> {
+        let Some((min_x, min_y)) = top_left(&self.elements) else {
+            return Vec::new();
+        };
+        paste_elements(&self.elements, &self.groups, x - min_x, y - min_y, canvas)
+    }
+}
+
+/// Clone the given elements, keeping their canvas (z-order) order
+pub fn clone_elements(ids: &[usize], canvas: &CanvasState) -> Vec<Element> {
+    canvas
+        .elements()
+        .iter()
+        .filter(|e| ids.contains(&e.id()))
+        .cloned()
+        .collect()
+}
+
+/// Clone the groups made up entirely of the given elements (nested ones included),
+/// so copies keep acting as one unit
+pub fn clone_groups(ids: &[usize], canvas: &CanvasState) -> Vec<Group> {
+    canvas
+        .groups()
+        .iter()
+        .filter(|g| {
+            canvas
+                .member_elements(Member::Group(g.id))
+                .iter()
+                .all(|id| ids.contains(id))
+        })
+        .cloned()
+        .collect()
+}
+
+/// Add copies of the elements and their groups to the canvas, offset by (dx, dy), with
+/// fresh IDs and names. Connectors stay attached to copied elements and are detached from
+/// anything left behind. Returns the IDs of the new elements.
+pub fn paste_elements(
+    elements: &[Element],
+    groups: &[Group],
+    dx: i32,
+    dy: i32,
+    canvas: &mut CanvasState,
+) -> Vec<usize> {
+    let new_ids: HashMap<usize, usize> = elements
+        .iter()
+        .map(|element| element.id())
+        .chain(groups.iter().map(|group| group.id))
+        .map(|id| (id, canvas.get_next_id()))
+        .collect();
+
+    let pasted = elements
+        .iter()
+        .map(|element| {
+            let mut copy = element.duplicate(new_ids[&element.id()]);
+            copy.translate(dx, dy);
+            if let Some(slots) = copy.attachments_mut() {
+                for slot in slots {
+                    *slot = slot.and_then(|attachment| {
+                        Some(Attachment {
+                            element_id: *new_ids.get(&attachment.element_id)?,
+                            ..attachment
+                        })
+                    });
+                }
+            }
+            canvas.add_element(copy)
+        })
+        .collect();
+
+    let copies: Vec<Group> = groups
+        .iter()
+        .map(|group| Group {
+            id: new_ids[&group.id],
+            name: format!("Group {}", new_ids[&group.id] + 1),
+            members: group
+                .members
+                .iter()
+                .map(|&member| match member {
+                    Member::Element(id) => Member::Element(new_ids[&id]),
+                    Member::Group(id) => Member::Group(new_ids[&id]),
+                })
+                .collect(),
+        })
+        .collect();
+    if !copies.is_empty() {
+        canvas.groups_mut().extend(copies);
+    }
+    pasted
+}
+
+/// Top-left corner of the combined bounds of the elements
+fn top_left(elements: &[Element]) -> Option<(i32, i32)> {
+    let min_x = elements.iter().map(|e| e.bounds().min.x).min()?;
+    let min_y = elements.iter().map(|e| e.bounds().min.y).min()?;
+    Some((min_x, min_y))
+}
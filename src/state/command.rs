@@ -1,4 +1,49 @@
+use crate::export::ExportFormat;
 use crate::state::AppState;
+use crate::state::completion;
+use clap::ValueEnum;
+use std::path::PathBuf;
+
+/// Maximum number of commands kept in the history file
+const MAX_HISTORY: usize = 100;
+
+/// History file in the textdraw state directory
+const HISTORY_FILE: &str = "command_history";
+
+/// Which elements `:select` selects
+#[derive(Debug, Clone, PartialEq)]
+pub enum SelectFilter {
+    All,
+    /// Elements of a type, e.g. `type=Rectangle` (case-insensitive)
+    Type(String),
+    /// Elements with the given name (case-insensitive)
+    Name(String),
+}
+
+/// Edge or center line that `:align` lines selected elements up on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Alignment {
+    Left,
+    Center,
+    Right,
+    Top,
+    Middle,
+    Bottom,
+}
+
+impl Alignment {
+    fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "left" => Some(Alignment::Left),
+            "center" => Some(Alignment::Center),
+            "right" => Some(Alignment::Right),
+            "top" => Some(Alignment::Top),
+            "middle" => Some(Alignment::Middle),
+            "bottom" => Some(Alignment::Bottom),
+            _ => None,
+        }
+    }
+}
 
 /// Actions that can be triggered by command execution
 #[derive(Debug, Clone, PartialEq)]
@@ -7,17 +52,63 @@ pub enum CommandAction {
     Save(String),
     /// Save to current file (no path specified)
     SaveCurrent,
-    /// Open a file
+    /// Open a file, asking first if there are unsaved changes
     Open(String),
-    /// Show a message (for quit or unknown commands)
+    /// Open a file, discarding unsaved changes
+    ForceOpen(String),
+    /// Quit, asking first if there are unsaved changes
+    Quit,
+    /// Quit, discarding unsaved changes
+    ForceQuit,
+    /// Save (to the given file or the current one), then quit
+    SaveAndQuit(Option<String>),
+    /// Save only if there are unsaved changes, then quit
+    Exit(Option<String>),
+    /// Start an empty diagram, asking first if there are unsaved changes
+    New,
+    /// Start an empty diagram, discarding unsaved changes
+    ForceNew,
+    /// Export the canvas to a file
+    Export(ExportFormat, String),
+    /// Move the cursor to a canvas position and center the view on it
+    Goto(i32, i32),
+    /// Replace the selection with the matching elements
+    Select(SelectFilter),
+    /// Delete the selected elements
+    Delete,
+    /// Set a property on the selected elements
+    Set(String, String),
+    /// Rename the selected element
+    Rename(String),
+    /// Line up the selected elements
+    Align(Alignment),
+    /// Show a message (for unknown commands)
     Message(String),
     /// No action (empty command)
     None,
 }
 
+/// Candidates offered by Tab completion, cycled through by pressing Tab again
+#[derive(Debug, Clone)]
+pub struct Completion {
+    /// Buffer up to the word being completed
+    base: String,
+    pub candidates: Vec<String>,
+    pub index: usize,
+}
+
 pub struct CommandState {
     pub buffer: String,
     pub active: bool,
+    // Earlier commands, oldest first
+    pub history: Vec<String>,
+    // Position while browsing the history with Up/Down
+    history_index: Option<usize>,
+    // What was typed before browsing the history, restored when going past the newest
+    draft: String,
+    // Where the history is persisted (None keeps it in memory only)
+    history_file: Option<PathBuf>,
+    pub completion: Option<Completion>,
 }
 
 impl CommandState {
@@ -25,28 +116,51 @@ impl CommandState {
         Self {
             buffer: String::new(),
             active: false,
+            history: Vec::new(),
+            history_index: None,
+            draft: String::new(),
+            history_file: None,
+            completion: None,
         }
     }
 
+    /// Persist the history in the textdraw state directory, loading earlier sessions'
+    pub fn load_history(&mut self) {
+        if let Some(path) = super::swap::state_dir().map(|dir| dir.join(HISTORY_FILE)) {
+            self.load_history_from(path);
+        }
+    }
+
+    /// Persist the history in the given file, loading what is in it already
+    pub fn load_history_from(&mut self, path: PathBuf) {
+        if let Ok(text) = std::fs::read_to_string(&path) {
+            self.history = text.lines().map(str::to_string).collect();
+        }
+        self.history_file = Some(path);
+    }
+
     pub fn enter(&mut self) {
-        self.active = true;
-        self.buffer.clear();
+        self.enter_with("");
     }
 
     pub fn enter_with(&mut self, command: &str) {
         self.active = true;
         self.buffer = command.to_string();
+        self.history_index = None;
+        self.completion = None;
     }
 
     pub fn exit(&mut self) {
         self.active = false;
         self.buffer.clear();
+        self.completion = None;
     }
 
-    /// Exit command mode without clearing - used after execution to keep status message
+    /// Exit command mode after execution, remembering the command in the history
     pub fn finish(&mut self) {
-        self.active = false;
-        self.buffer.clear();
+        let command = self.buffer.trim().to_string();
+        self.exit();
+        self.push_history(command);
     }
 
     pub fn is_active(&self) -> bool {
@@ -56,12 +170,91 @@ impl CommandState {
     pub fn add_char(&mut self, c: char) {
         if self.active {
             self.buffer.push(c);
+            self.completion = None;
         }
     }
 
     pub fn backspace(&mut self) {
         if self.active {
             self.buffer.pop();
+            self.completion = None;
+        }
+    }
+
+    /// Add a command to the history (skipping empty commands and repeats) and save it
+    fn push_history(&mut self, command: String) {
+        if command.is_empty() || self.history.last() == Some(&command) {
+            return;
+        }
+        self.history.push(command);
+        if self.history.len() > MAX_HISTORY {
+            self.history.remove(0);
+        }
+
+        if let Some(path) = &self.history_file {
+            if let Some(dir) = path.parent() {
+                let _ = std::fs::create_dir_all(dir);
+            }
+            // Losing the history isn't worth interrupting the user for
+            let _ = std::fs::write(path, self.history.join("\n") + "\n");
+        }
+    }
+
+    /// Replace the buffer with the previous (older) command in the history
+    pub fn history_prev(&mut self) {
+        let index = match self.history_index {
+            Some(0) => return,
+            Some(index) => index - 1,
+            None if self.history.is_empty() => return,
+            None => {
+                self.draft = self.buffer.clone();
+                self.history.len() - 1
+            }
+        };
+        self.history_index = Some(index);
+        self.buffer = self.history[index].clone();
+        self.completion = None;
+    }
+
+    /// Replace the buffer with the next (newer) command, or what was typed before
+    pub fn history_next(&mut self) {
+        let Some(index) = self.history_index else {
+            return;
+        };
+        if index + 1 < self.history.len() {
+            self.history_index = Some(index + 1);
+            self.buffer = self.history[index + 1].clone();
+        } else {
+            self.history_index = None;
+            self.buffer = std::mem::take(&mut self.draft);
+        }
+        self.completion = None;
+    }
+
+    /// Complete the command name or file path being typed. A single candidate is
+    /// inserted right away; with several, repeated Tabs cycle through them.
+    pub fn complete(&mut self) {
+        if let Some(completion) = &mut self.completion {
+            completion.index = (completion.index + 1) % completion.candidates.len();
+            self.buffer = format!(
+                "{}{}",
+                completion.base, completion.candidates[completion.index]
+            );
+            return;
+        }
+
+        let (base, candidates) = completion::complete(&self.buffer);
+        match candidates.len() {
+            0 => {}
+            1 => self.buffer = format!("{}{}", base, candidates[0]),
+            _ => {
+                self.buffer = format!("{}{}", base, candidates[0]);
+                self.completion = Some(Completion {
+                    base,
+                    candidates,
+                    index: 0,
+                });
+            }
         }
     }
 
@@ -79,74 +272,168 @@ impl CommandState {
             return CommandAction::None;
         }
 
+        // Everything after the command name is the filename
+        let filename = (parts.len() > 1).then(|| with_extension(&parts[1..].join(" ")));
+        let args = &parts[1..];
+
         match parts[0] {
-            "save" | "s" | "w" => {
-                // :save filename or :w filename
-                if parts.len() > 1 {
-                    let filename = parts[1..].join(" ");
-                    let full_path = if filename.ends_with(".textdraw") {
-                        filename
-                    } else {
-                        format!("{}.textdraw", filename)
-                    };
-                    CommandAction::Save(full_path)
-                } else {
-                    CommandAction::SaveCurrent
+            // :save filename or :w filename
+            "save" | "s" | "w" => match filename {
+                Some(path) => CommandAction::Save(path),
+                None => CommandAction::SaveCurrent,
+            },
+            // :open filename or :e filename (add ! to discard unsaved changes)
+            "open" | "o" | "e" | "open!" | "o!" | "e!" => {
+                let force = parts[0].ends_with('!');
+                match filename {
+                    Some(path) if force => CommandAction::ForceOpen(path),
+                    Some(path) => CommandAction::Open(path),
+                    None => CommandAction::Message("No filename specified".to_string()),
                 }
             }
-            "open" | "o" | "e" => {
-                // :open filename or :e filename
-                if parts.len() > 1 {
-                    let filename = parts[1..].join(" ");
-                    let full_path = if filename.ends_with(".textdraw") {
-                        filename
-                    } else {
-                        format!("{}.textdraw", filename)
-                    };
-                    CommandAction::Open(full_path)
-                } else {
-                    CommandAction::Message("No filename specified".to_string())
+            "q" | "quit" => CommandAction::Quit,
+            "q!" | "quit!" => CommandAction::ForceQuit,
+            "wq" => CommandAction::SaveAndQuit(filename),
+            "x" | "exit" => CommandAction::Exit(filename),
+            "new" => CommandAction::New,
+            "new!" => CommandAction::ForceNew,
+            "export" => parse_export(args),
+            "goto" => match args {
+                [x, y] => match (x.parse(), y.parse()) {
+                    (Ok(x), Ok(y)) => CommandAction::Goto(x, y),
+                    _ => usage("goto <x> <y>"),
+                },
+                _ => usage("goto <x> <y>"),
+            },
+            "select" => parse_select(&args.join(" ")),
+            "delete" => CommandAction::Delete,
+            "set" => match args.join(" ").split_once('=') {
+                Some((key, value)) if !key.trim().is_empty() => {
+                    CommandAction::Set(key.trim().to_string(), value.trim().to_string())
                 }
-            }
-            "q" | "quit" => {
-                // Handle quit - you might want to add a confirmation if unsaved
-                // For now, just show a message
-                CommandAction::Message("Use 'q' key to quit".to_string())
-            }
+                _ => usage("set <property>=<value>"),
+            },
+            "rename" if !args.is_empty() => CommandAction::Rename(args.join(" ")),
+            "rename" => usage("rename <name>"),
+            "align" => match args {
+                [edge] => match Alignment::from_str(&edge.to_lowercase()) {
+                    Some(alignment) => CommandAction::Align(alignment),
+                    None => usage("align left|center|right|top|middle|bottom"),
+                },
+                _ => usage("align left|center|right|top|middle|bottom"),
+            },
             _ => CommandAction::Message(format!("Unknown command: {}", parts[0])),
         }
     }
 }
 
+fn usage(text: &str) -> CommandAction {
+    CommandAction::Message(format!("Usage: :{}", text))
+}
+
+/// `:export svg|html <file>`
+fn parse_export(args: &[&str]) -> CommandAction {
+    let [format, path @ ..] = args else {
+        return usage("export svg|html <file>");
+    };
+    if path.is_empty() {
+        return usage("export svg|html <file>");
+    }
+    match ExportFormat::from_str(format, true) {
+        Ok(format) => CommandAction::Export(format, path.join(" ")),
+        Err(_) => CommandAction::Message(format!("Unknown export format: {}", format)),
+    }
+}
+
+/// `:select all`, `:select type=<Type>` or `:select name=<name>`
+fn parse_select(arg: &str) -> CommandAction {
+    if arg == "all" {
+        return CommandAction::Select(SelectFilter::All);
+    }
+    match arg.split_once('=') {
+        Some(("type", value)) => CommandAction::Select(SelectFilter::Type(value.to_string())),
+        Some(("name", value)) => CommandAction::Select(SelectFilter::Name(value.to_string())),
+        _ => usage("select all|type=<Type>|name=<name>"),
+    }
+}
+
+/// Add the .textdraw extension unless the filename already has it
+fn with_extension(filename: &str) -> String {
+    if filename.ends_with(".textdraw") {
+        filename.to_string()
+    } else {
+        format!("{}.textdraw", filename)
+    }
+}
+
 /// Executes command actions on the app state
 pub struct CommandExecutor;
 
 impl CommandExecutor {
-    pub fn execute(action: CommandAction, state: &mut AppState) {
+    /// Execute the action. Returns true if the app should quit.
+    pub fn execute(action: CommandAction, state: &mut AppState) -> bool {
         match action {
             CommandAction::Save(path) => {
-                if let Err(e) = state.file.save_to_file(&state.canvas, &path) {
-                    state.file.status_message = Some(format!("Error: {}", e));
-                }
+                Self::save(state, Some(path));
             }
             CommandAction::SaveCurrent => {
-                if let Some(current) = state.file.current_file.clone() {
-                    if let Err(e) = state.file.save_to_file(&state.canvas, &current) {
-                        state.file.status_message = Some(format!("Error: {}", e));
-                    }
-                } else {
-                    state.file.status_message = Some("No filename specified".to_string());
+                Self::save(state, None);
+            }
+            CommandAction::Open(path) => state.request_open(path),
+            CommandAction::ForceOpen(path) => state.open_file(&path),
+            CommandAction::Quit => return state.request_quit(),
+            CommandAction::ForceQuit => return true,
+            CommandAction::SaveAndQuit(path) => return Self::save(state, path),
+            CommandAction::Exit(path) => {
+                return !state.file.is_dirty() || Self::save(state, path);
+            }
+            CommandAction::New => state.request_new(),
+            CommandAction::ForceNew => state.new_diagram(),
+            CommandAction::Export(format, path) => {
+                let message = match state.export_to(format, &path) {
+                    Ok(()) => format!("Exported to {}", path),
+                    Err(e) => format!("Error: {}", e),
+                };
+                state.file.status_message = Some(message);
+            }
+            CommandAction::Goto(x, y) => state.goto(x, y),
+            CommandAction::Select(filter) => {
+                let count = state.select_matching(&filter);
+                state.file.status_message = Some(format!("Selected {} elements", count));
+            }
+            CommandAction::Delete => state.delete_selected_elements(),
+            CommandAction::Set(name, value) => {
+                if let Err(e) = state.set_selected_property(&name, &value) {
+                    state.file.status_message = Some(format!("Error: {}", e));
                 }
             }
-            CommandAction::Open(path) => {
-                if let Err(e) = state.file.load_from_file(&mut state.canvas, &path) {
+            CommandAction::Rename(name) => {
+                if let Err(e) = state.rename_selected(&name) {
                     state.file.status_message = Some(format!("Error: {}", e));
                 }
             }
+            CommandAction::Align(alignment) => state.align_selected(alignment),
             CommandAction::Message(msg) => {
                 state.file.status_message = Some(msg);
             }
             CommandAction::None => {}
         }
+        false
+    }
+
+    /// Save to the given path, or the current file if there is none.
+    /// Errors are shown as a status message. Returns true if the diagram was saved.
+    fn save(state: &mut AppState, path: Option<String>) -> bool {
+        let Some(path) = path.or_else(|| state.file.current_file.clone()) else {
+            state.file.status_message = Some("No filename specified".to_string());
+            return false;
+        };
+        match state.file.save_to_file(&state.canvas, &path) {
+            Ok(()) => true,
+            Err(e) => {
+                state.file.status_message = Some(format!("Error: {}", e));
+                false
+            }
+        }
     }
 }
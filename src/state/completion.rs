@@ -0,0 +1,71 @@
+//! Tab completion for command mode.
+
+use std::path::Path;
+
+/// Command names offered for completion (aliases and `!` variants are left out)
+const COMMANDS: &[&str] = &[
+    "align", "delete", "exit", "export", "goto", "new", "open", "quit", "rename", "save", "select",
+    "set", "wq",
+];
+
+/// Commands whose argument is a diagram file
+const FILE_COMMANDS: &[&str] = &[
+    "save", "s", "w", "open", "o", "e", "open!", "o!", "e!", "wq", "x", "exit",
+];
+
+/// Completions for the command line. Returns the part of the buffer that stays as it
+/// is and the candidates for the rest, sorted.
+pub fn complete(buffer: &str) -> (String, Vec<String>) {
+    match buffer.split_once(' ') {
+        None => {
+            let candidates = COMMANDS
+                .iter()
+                .filter(|name| name.starts_with(buffer))
+                // Ready for the argument
+                .map(|name| format!("{} ", name))
+                .collect();
+            (String::new(), candidates)
+        }
+        Some((command, arg)) if FILE_COMMANDS.contains(&command) => {
+            let (dir, prefix) = match arg.rfind('/') {
+                Some(i) => arg.split_at(i + 1),
+                None => ("", arg),
+            };
+            let candidates = complete_path(dir, prefix);
+            (format!("{} {}", command, dir), candidates)
+        }
+        Some(_) => (buffer.to_string(), Vec::new()),
+    }
+}
+
+/// Directories (with a trailing `/`) and `.textdraw` files in `dir` starting with
+/// `prefix`. Hidden entries are only offered when the prefix starts with a dot.
+fn complete_path(dir: &str, prefix: &str) -> Vec<String> {
+    let path = if dir.is_empty() {
+        Path::new(".")
+    } else {
+        Path::new(dir)
+    };
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return Vec::new();
+    };
+
+    let mut candidates: Vec<String> = entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let name = entry.file_name().into_string().ok()?;
+            if !name.starts_with(prefix) || (name.starts_with('.') && !prefix.starts_with('.')) {
+                return None;
+            }
+            if entry.file_type().ok()?.is_dir() {
+                Some(format!("{}/", name))
+            } else if name.ends_with(".textdraw") {
+                Some(name)
+            } else {
+                None
+            }
+        })
+        .collect();
+    candidates.sort();
+    candidates
+}
@@ -0,0 +1,139 @@
+//! Keeping lines and arrows attached to the rectangles at their ends.
+
+use crate::elements::{Attachment, Element, route};
+use crate::state::CanvasState;
+use crate::types::Coord;
+
+impl CanvasState {
+    /// Attach the ends of a line or arrow to the rectangles they start or end next to
+    pub fn attach_connector(&mut self, id: usize) {
+        let Some((start, end)) = self.get_element(id).and_then(Element::endpoints) else {
+            return;
+        };
+        let start_attachment = self.find_attachment(id, start);
+        let end_attachment = self.find_attachment(id, end);
+        if start_attachment.is_none() && end_attachment.is_none() {
+            return;
+        }
+
+        if let Some([start_slot, end_slot]) =
+            self.get_element_mut(id).and_then(Element::attachments_mut)
+        {
+            *start_slot = start_attachment;
+            *end_slot = end_attachment;
+        }
+    }
+
+    /// Keep connectors consistent after the given elements were moved together.
+    /// Connectors moved without the element at one of their ends are detached from it,
+    /// and connectors that stayed put are re-routed to follow the moved elements.
+    pub fn update_connectors_after_move(&mut self, moved: &[usize]) {
+        for id in self.connector_ids() {
+            if moved.contains(&id) {
+                self.detach_connector(id, |attachment| !moved.contains(&attachment.element_id));
+            } else if self.is_attached_to_any(id, moved) {
+                self.reroute_connector(id);
+            }
+        }
+    }
+
+    /// Re-route connectors attached to an element whose position or size changed
+    pub fn reroute_connectors_to(&mut self, element_id: usize) {
+        for id in self.connector_ids() {
+            if self.is_attached_to_any(id, &[element_id]) {
+                self.reroute_connector(id);
+            }
+        }
+    }
+
+    /// Detach connectors from an element that was removed, leaving them where they are
+    pub fn detach_connectors_from(&mut self, element_id: usize) {
+        for id in self.connector_ids() {
+            self.detach_connector(id, |attachment| attachment.element_id == element_id);
+        }
+    }
+
+    /// Attachment for a connector end at `point`, to the topmost visible rectangle it
+    /// touches
+    fn find_attachment(&self, connector_id: usize, point: Coord) -> Option<Attachment> {
+        self.draw_order()
+            .into_iter()
+            .rev()
+            .filter(|e| e.id() != connector_id && matches!(e, Element::Rectangle(_)))
+            .find_map(|e| Attachment::find(e.id(), &e.bounds(), point))
+    }
+
+    /// IDs of all lines and arrows with at least one attached end
+    fn connector_ids(&self) -> Vec<usize> {
+        self.elements()
+            .iter()
+            .filter(|e| e.attachments().iter().any(Option::is_some))
+            .map(Element::id)
+            .collect()
+    }
+
+    fn is_attached_to_any(&self, connector_id: usize, element_ids: &[usize]) -> bool {
+        self.get_element(connector_id).is_some_and(|e| {
+            e.attachments()
+                .iter()
+                .flatten()
+                .any(|a| element_ids.contains(&a.element_id))
+        })
+    }
+
+    /// Clear the attachments of a connector that match `should_detach`
+    fn detach_connector(&mut self, id: usize, should_detach: impl Fn(&Attachment) -> bool) {
+        let matches = self
+            .get_element(id)
+            .is_some_and(|e| e.attachments().iter().flatten().any(&should_detach));
+        if !matches {
+            return;
+        }
+
+        if let Some(slots) = self.get_element_mut(id).and_then(Element::attachments_mut) {
+            for slot in slots {
+                if slot.as_ref().is_some_and(&should_detach) {
+                    *slot = None;
+                }
+            }
+        }
+    }
+
+    /// Redraw a connector between the current anchors of its attached ends
+    fn reroute_connector(&mut self, id: usize) {
+        let Some(connector) = self.get_element(id) else {
+            return;
+        };
+        let Some((mut start, mut end)) = connector.endpoints() else {
+            return;
+        };
+        let [start_attachment, end_attachment] = connector.attachments();
+
+        let anchor = |attachment: Option<Attachment>| {
+            let attachment = attachment?;
+            let target = self.get_element(attachment.element_id)?;
+            Some((
+                attachment.anchor(&target.bounds()),
+                attachment.side.outward(),
+            ))
+        };
+        let start_anchor = anchor(start_attachment);
+        let end_anchor = anchor(end_attachment);
+        if let Some((point, _)) = start_anchor {
+            start = point;
+        }
+        if let Some((point, _)) = end_anchor {
+            end = point;
+        }
+
+        let segments = route(
+            start,
+            start_anchor.map(|(_, out)| out),
+            end,
+            end_anchor.map(|(_, out)| out),
+        );
+        if let Some(connector) = self.get_element_mut(id) {
+            connector.set_segments(segments);
+        }
+    }
+}
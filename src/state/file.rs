@@ -4,6 +4,8 @@ use std::path::Path;
 pub struct FileState {
     pub current_file: Option<String>,
     pub status_message: Option<String>,
+    // Canvas changed since the last save or load
+    pub dirty: bool,
 }
 
 impl FileState {
@@ -11,9 +13,19 @@ impl FileState {
         Self {
             current_file: None,
             status_message: None,
+            dirty: false,
         }
     }
 
+    /// Record that the canvas has changes that aren't saved yet
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
     pub fn clear_status_message(&mut self) {
         self.status_message = None;
     }
@@ -28,6 +40,7 @@ impl FileState {
     ) -> anyhow::Result<()> {
         canvas.save_to_file(&path)?;
         self.current_file = Some(path.as_ref().display().to_string());
+        self.dirty = false;
         self.status_message = Some(format!("Saved to {}", path.as_ref().display()));
         Ok(())
     }
@@ -50,6 +63,7 @@ impl FileState {
     ) -> anyhow::Result<()> {
         canvas.load_from_file(&path)?;
         self.current_file = Some(path.as_ref().display().to_string());
+        self.dirty = false;
         if show_message {
             self.status_message = Some(format!("Loaded from {}", path.as_ref().display()));
         }
@@ -0,0 +1,139 @@
+//! Groups of elements that are selected and moved as one unit.
+//!
+//! Groups are kept next to the elements rather than being elements themselves. Each
+//! group lists the elements and the groups directly inside it, so groups nest. The
+//! selection always holds element IDs; selecting a group selects all of its elements.
+
+use crate::state::CanvasState;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Group {
+    pub id: usize,
+    pub name: String,
+    pub members: Vec<Member>,
+}
+
+/// Something directly inside a group
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Member {
+    Element(usize),
+    Group(usize),
+}
+
+impl CanvasState {
+    /// Get reference to a group by ID
+    pub fn get_group(&self, id: usize) -> Option<&Group> {
+        self.groups().iter().find(|g| g.id == id)
+    }
+
+    /// The group directly containing an element or group, if any
+    pub fn parent_group(&self, member: Member) -> Option<usize> {
+        self.groups()
+            .iter()
+            .find(|g| g.members.contains(&member))
+            .map(|g| g.id)
+    }
+
+    /// All elements of an element or group, including those of nested groups
+    pub fn member_elements(&self, member: Member) -> Vec<usize> {
+        match member {
+            Member::Element(id) => vec![id],
+            Member::Group(id) => self
+                .get_group(id)
+                .map(|group| {
+                    group
+                        .members
+                        .iter()
+                        .flat_map(|&m| self.member_elements(m))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    /// What gets selected for an element, seen from inside `scope` (a group being
+    /// edited, or `None` for the whole canvas): the element itself, or the group
+    /// directly inside `scope` that holds it. `None` if the element is outside `scope`.
+    pub fn unit_of(&self, element_id: usize, scope: Option<usize>) -> Option<Member> {
+        let mut member = Member::Element(element_id);
+        loop {
+            match self.parent_group(member) {
+                parent if parent == scope => return Some(member),
+                Some(parent) => member = Member::Group(parent),
+                None => return None,
+            }
+        }
+    }
+
+    /// The distinct units (see `unit_of`) of the given elements, in order
+    pub fn units_of(&self, element_ids: &[usize], scope: Option<usize>) -> Vec<Member> {
+        let mut units = Vec::new();
+        for &id in element_ids {
+            if let Some(unit) = self.unit_of(id, scope)
+                && !units.contains(&unit)
+            {
+                units.push(unit);
+            }
+        }
+        units
+    }
+
+    /// Put the given units of `parent` (or of the top level) into a new group nested
+    /// where the first of them was. Returns the new group's ID.
+    pub fn create_group(&mut self, members: Vec<Member>, parent: Option<usize>) -> usize {
+        let id = self.get_next_id();
+        let group = Group {
+            id,
+            name: format!("Group {}", id + 1),
+            members: members.clone(),
+        };
+        let groups = self.groups_mut();
+        if let Some(parent) = groups.iter_mut().find(|g| Some(g.id) == parent) {
+            let position = parent
+                .members
+                .iter()
+                .position(|m| members.contains(m))
+                .unwrap_or(parent.members.len());
+            parent.members.retain(|m| !members.contains(m));
+            parent
+                .members
+                .insert(position.min(parent.members.len()), Member::Group(id));
+        }
+        groups.push(group);
+        id
+    }
+
+    /// Dissolve a group, moving its members up into the group that contained it
+    pub fn ungroup(&mut self, id: usize) {
+        let Some(index) = self.groups().iter().position(|g| g.id == id) else {
+            return;
+        };
+        let groups = self.groups_mut();
+        let group = groups.remove(index);
+        if let Some(parent) = groups
+            .iter_mut()
+            .find(|g| g.members.contains(&Member::Group(id)))
+            && let Some(position) = parent.members.iter().position(|&m| m == Member::Group(id))
+        {
+            parent.members.splice(position..=position, group.members);
+        }
+    }
+
+    /// Remove a deleted element from its group, dropping groups left empty
+    pub(super) fn remove_from_groups(&mut self, element_id: usize) {
+        let mut member = Member::Element(element_id);
+        while let Some(parent) = self.parent_group(member) {
+            let groups = self.groups_mut();
+            let Some(index) = groups.iter().position(|g| g.id == parent) else {
+                break;
+            };
+            groups[index].members.retain(|&m| m != member);
+            if !groups[index].members.is_empty() {
+                break;
+            }
+            groups.remove(index);
+            member = Member::Group(parent);
+        }
+    }
+}
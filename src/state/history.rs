@@ -0,0 +1,154 @@
+use crate::state::{CanvasSnapshot, CanvasState};
+
+/// Maximum number of undo steps kept in memory
+const MAX_UNDO_STEPS: usize = 200;
+
+/// Kind of edit being committed, used to coalesce consecutive edits into one undo step
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditKind {
+    /// A one-off change that always gets its own undo step
+    Discrete,
+    /// Keyboard nudge of the given selection
+    Nudge(Vec<usize>),
+    /// Typing into a single table cell
+    TableCell {
+        table_id: usize,
+        row: usize,
+        col: usize,
+    },
+    /// Typing into a rectangle's label
+    Label { rect_id: usize },
+    /// Reshaping a line or arrow in Edit Vertices mode
+    Vertices { element_id: usize },
+    /// Repeated changes to one property of the given selection (e.g. Up/Down in a
+    /// numeric input)
+    Property {
+        element_ids: Vec<usize>,
+        name: String,
+    },
+}
+
+impl EditKind {
+    fn coalesces(&self) -> bool {
+        !matches!(self, EditKind::Discrete)
+    }
+}
+
+/// Snapshot-based undo/redo history for the canvas.
+///
+/// Changes are detected through `CanvasState::revision` and committed once per event,
+/// so every mutation path (tools, selection, properties, table editing) is covered
+/// without each of them having to record history explicitly.
+pub struct HistoryState {
+    undo_stack: Vec<CanvasSnapshot>,
+    redo_stack: Vec<CanvasSnapshot>,
+    // Canvas contents as of the last commit
+    committed: CanvasSnapshot,
+    committed_revision: u64,
+    // Kind of the last committed edit, for coalescing
+    last_kind: Option<EditKind>,
+    // Kind hint for the edit currently in progress
+    pending_kind: Option<EditKind>,
+}
+
+impl HistoryState {
+    pub fn new(canvas: &CanvasState) -> Self {
+        Self {
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            committed: canvas.snapshot(),
+            committed_revision: canvas.revision(),
+            last_kind: None,
+            pending_kind: None,
+        }
+    }
+
+    /// Drop all history and start fresh from the current canvas (e.g. after loading a file)
+    pub fn reset(&mut self, canvas: &CanvasState) {
+        *self = Self::new(canvas);
+    }
+
+    /// Describe the edit about to happen so it can be coalesced with the previous one
+    pub fn set_pending_kind(&mut self, kind: EditKind) {
+        self.pending_kind = Some(kind);
+    }
+
+    /// Whether there is an undo step (shown as a hint in the status bar)
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Record an undo step if the canvas changed since the last commit.
+    /// Returns true if the canvas had changed.
+    pub fn commit(&mut self, canvas: &CanvasState) -> bool {
+        let kind = self.pending_kind.take().unwrap_or(EditKind::Discrete);
+
+        if canvas.revision() == self.committed_revision {
+            return false;
+        }
+
+        // The revision also moves on edits that left everything as it was (e.g.
+        // renaming an element to its current name)
+        let snapshot = canvas.snapshot();
+        if snapshot == self.committed {
+            self.committed_revision = canvas.revision();
+            return false;
+        }
+
+        let coalesce = kind.coalesces() && self.last_kind.as_ref() == Some(&kind);
+        let previous = std::mem::replace(&mut self.committed, snapshot);
+        if !coalesce {
+            self.undo_stack.push(previous);
+            if self.undo_stack.len() > MAX_UNDO_STEPS {
+                self.undo_stack.remove(0);
+            }
+        }
+
+        self.redo_stack.clear();
+        self.committed_revision = canvas.revision();
+        self.last_kind = Some(kind);
+        true
+    }
+
+    /// Restore the canvas to the previous undo step. Returns false if there is nothing to undo.
+    pub fn undo(&mut self, canvas: &mut CanvasState) -> bool {
+        // Flush any change that hasn't been committed yet so it can be undone too
+        self.commit(canvas);
+
+        let Some(previous) = self.undo_stack.pop() else {
+            return false;
+        };
+
+        let current = std::mem::replace(&mut self.committed, previous.clone());
+        self.redo_stack.push(current);
+        self.restore(canvas, previous);
+        true
+    }
+
+    /// Re-apply the last undone step. Returns false if there is nothing to redo.
+    pub fn redo(&mut self, canvas: &mut CanvasState) -> bool {
+        // A fresh uncommitted change invalidates the redo stack
+        self.commit(canvas);
+
+        let Some(next) = self.redo_stack.pop() else {
+            return false;
+        };
+
+        let current = std::mem::replace(&mut self.committed, next.clone());
+        self.undo_stack.push(current);
+        self.restore(canvas, next);
+        true
+    }
+
+    fn restore(&mut self, canvas: &mut CanvasState, snapshot: CanvasSnapshot) {
+        canvas.restore(snapshot);
+        self.committed_revision = canvas.revision();
+        // Never coalesce across an undo/redo boundary
+        self.last_kind = None;
+        self.pending_kind = None;
+    }
+}
@@ -0,0 +1,191 @@
+//! Named layers that elements are drawn on.
+//!
+//! Every element belongs to exactly one layer (`Element::layer`). Layers are drawn
+//! bottom to top in the order of the list, and elements within a layer in the order of
+//! the element list. Hidden layers are not drawn or exported; elements on hidden or
+//! locked layers can't be selected.
+
+use crate::elements::Element;
+use crate::state::{CanvasState, Member};
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Layer {
+    pub id: usize,
+    pub name: String,
+    #[serde(default = "default_visible")]
+    pub visible: bool,
+    #[serde(default)]
+    pub locked: bool,
+}
+
+fn default_visible() -> bool {
+    true
+}
+
+impl Layer {
+    pub fn new(id: usize, name: impl Into<String>) -> Self {
+        Self {
+            id,
+            name: name.into(),
+            visible: true,
+            locked: false,
+        }
+    }
+
+    /// The layer every diagram starts with
+    pub fn default_layers() -> Vec<Layer> {
+        vec![Layer::new(0, "Layer 1")]
+    }
+}
+
+impl CanvasState {
+    /// Get reference to a layer by ID
+    pub fn get_layer(&self, id: usize) -> Option<&Layer> {
+        self.layers().iter().find(|l| l.id == id)
+    }
+
+    /// The layer new elements are added to
+    pub fn active_layer(&self) -> usize {
+        self.get_layer(self.active_layer_id())
+            .or(self.layers().first())
+            .map(|l| l.id)
+            .unwrap_or_default()
+    }
+
+    /// Position of an element's layer in the drawing order. Elements of a layer that
+    /// doesn't exist are drawn with the bottom layer.
+    fn layer_position(&self, element: &Element) -> usize {
+        self.layers()
+            .iter()
+            .position(|l| l.id == element.layer())
+            .unwrap_or_default()
+    }
+
+    fn element_layer(&self, element: &Element) -> Option<&Layer> {
+        self.layers().get(self.layer_position(element))
+    }
+
+    /// Whether the element's layer is shown
+    pub fn is_visible(&self, element: &Element) -> bool {
+        self.element_layer(element).is_none_or(|l| l.visible)
+    }
+
+    /// Whether the element can be selected: its layer is shown and not locked
+    pub fn is_selectable(&self, element: &Element) -> bool {
+        self.element_layer(element)
+            .is_none_or(|l| l.visible && !l.locked)
+    }
+
+    /// The elements of an element or group that can be selected. Groups may span
+    /// layers, and members on hidden or locked layers are left out.
+    pub fn selectable_members(&self, member: Member) -> Vec<usize> {
+        self.member_elements(member)
+            .into_iter()
+            .filter(|&id| self.get_element(id).is_some_and(|e| self.is_selectable(e)))
+            .collect()
+    }
+
+    /// Visible elements from bottom to top
+    pub fn draw_order(&self) -> Vec<&Element> {
+        let mut elements: Vec<&Element> = self
+            .elements()
+            .iter()
+            .filter(|e| self.is_visible(e))
+            .collect();
+        elements.sort_by_key(|e| self.layer_position(e));
+        elements
+    }
+
+    /// Add a layer directly above `below` (or on top) and return its ID
+    pub fn add_layer(&mut self, below: Option<usize>) -> usize {
+        let id = self
+            .layers()
+            .iter()
+            .map(|l| l.id + 1)
+            .max()
+            .unwrap_or_default();
+        let name = format!("Layer {}", self.layers().len() + 1);
+        let layers = self.layers_mut();
+        let position = below
+            .and_then(|below| layers.iter().position(|l| l.id == below))
+            .map_or(layers.len(), |position| position + 1);
+        layers.insert(position, Layer::new(id, name));
+        id
+    }
+
+    /// Move a layer one step up (towards the top) or down. Returns false if it is
+    /// already at that end.
+    pub fn move_layer(&mut self, id: usize, up: bool) -> bool {
+        let Some(position) = self.layers().iter().position(|l| l.id == id) else {
+            return false;
+        };
+        let other = if up {
+            position + 1
+        } else {
+            match position.checked_sub(1) {
+                Some(other) => other,
+                None => return false,
+            }
+        };
+        if other >= self.layers().len() {
+            return false;
+        }
+        self.layers_mut().swap(position, other);
+        true
+    }
+
+    /// Show or hide a layer
+    pub fn toggle_layer_visible(&mut self, id: usize) {
+        if let Some(layer) = self.layers_mut().iter_mut().find(|l| l.id == id) {
+            layer.visible = !layer.visible;
+        }
+    }
+
+    /// Lock or unlock a layer
+    pub fn toggle_layer_locked(&mut self, id: usize) {
+        if let Some(layer) = self.layers_mut().iter_mut().find(|l| l.id == id) {
+            layer.locked = !layer.locked;
+        }
+    }
+
+    pub fn rename_layer(&mut self, id: usize, name: String) -> Result<()> {
+        let name = name.trim();
+        if name.is_empty() {
+            bail!("Layer name cannot be empty");
+        }
+        if self.layers().iter().any(|l| l.id != id && l.name == name) {
+            bail!("A layer named {} already exists", name);
+        }
+        if let Some(layer) = self.layers_mut().iter_mut().find(|l| l.id == id) {
+            layer.name = name.to_string();
+        }
+        Ok(())
+    }
+
+    /// Move elements to another layer
+    pub fn move_to_layer(&mut self, element_ids: &[usize], layer: usize) {
+        for &id in element_ids {
+            if self.get_element(id).is_some_and(|e| e.layer() != layer)
+                && let Some(element) = self.get_element_mut(id)
+            {
+                element.set_layer(layer);
+            }
+        }
+    }
+
+    /// Show only the named layers (for rendering and export), whether or not they
+    /// were hidden in the editor
+    pub fn show_only_layers(&mut self, names: &[String]) -> Result<()> {
+        for name in names {
+            if !self.layers().iter().any(|l| &l.name == name) {
+                bail!("Unknown layer: {}", name);
+            }
+        }
+        for layer in self.layers_mut() {
+            layer.visible = names.contains(&layer.name);
+        }
+        Ok(())
+    }
+}
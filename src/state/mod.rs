@@ -1,18 +1,38 @@
 mod canvas;
+mod clipboard;
 mod command;
+mod completion;
+mod connectors;
 mod file;
+mod groups;
+mod history;
+mod layers;
 mod selection;
+mod swap;
 mod tool;
 
-pub use canvas::CanvasState;
-pub use command::{CommandExecutor, CommandState};
+pub use canvas::{CanvasSnapshot, CanvasState};
+pub use clipboard::ClipboardState;
+pub use command::{Alignment, CommandExecutor, CommandState, SelectFilter};
 pub use file::FileState;
+pub use groups::{Group, Member};
+pub use history::{EditKind, HistoryState};
+pub use layers::Layer;
 pub use selection::SelectionState;
+pub use swap::SwapState;
 pub use tool::ToolState;
 
-use crate::types::Panel;
+use crate::config::Config;
+use crate::elements::{Element, PropertiesSpec, PropertyValue};
+use crate::export::{self, ExportFormat, ExportOptions};
+use crate::keymap::Keymap;
+use crate::tools::Tool;
+use crate::types::{Panel, ResizeHandle, SelectionMode, ZOrder};
 use crate::ui::UILayout;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// Offset applied to duplicated elements so they don't cover the originals
+const DUPLICATE_OFFSET: (i32, i32) = (2, 1);
 
 /// Edit Table mode state
 #[derive(Debug, Clone)]
@@ -26,48 +46,121 @@ pub struct EditTableState {
     pub original_content: String, // Store original content for cancel (Esc)
 }
 
+/// Edit Label mode state (typing into a rectangle's label)
+#[derive(Debug, Clone)]
+pub struct EditLabelState {
+    pub rect_id: usize,
+    pub cursor_pos: usize,      // Character index into the label
+    pub original_label: String, // Store original label for cancel (Esc)
+}
+
+/// Edit Vertices mode state (moving the start, end and bends of a line or arrow)
+#[derive(Debug, Clone)]
+pub struct EditVerticesState {
+    pub element_id: usize,
+    pub vertex: usize,     // Index of the selected vertex
+    pub dragging: bool,    // Selected vertex follows the mouse
+    pub original: Element, // Store original element for cancel (Esc)
+}
+
+/// Action waiting for the user to confirm that unsaved changes may be discarded
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfirmAction {
+    Quit,
+    Open(String),
+    /// Start an empty diagram
+    New,
+    /// Recover unsaved changes from a swap file left by an earlier session
+    Recover(PathBuf),
+}
+
 /// Main application state
 pub struct AppState {
-    pub cursor_x: u16,
-    pub cursor_y: u16,
+    pub cursor_x: i32,
+    pub cursor_y: i32,
+    // Canvas coordinate shown at the top-left corner of the canvas area
+    pub viewport_x: i32,
+    pub viewport_y: i32,
     pub active_panel: Panel,
     pub layout: UILayout,
     pub show_help: bool,
     pub show_properties: bool,
+    pub show_layers: bool,
+    pub show_outline: bool,
     pub show_tools_modal: bool,
     pub command: CommandState,
+    // Keys for tools and global actions
+    pub keymap: Keymap,
     pub tool: ToolState,
     pub file: FileState,
+    // Autosave to a swap file for crash recovery
+    pub swap: SwapState,
     // Drawing canvas
     pub canvas: CanvasState,
+    // Undo/redo history of canvas changes
+    pub history: HistoryState,
+    // Copied elements for cut/copy/paste
+    pub clipboard: ClipboardState,
     // Selection state (for Select tool)
     pub selection_state: SelectionState,
     // Edit Table mode state
     pub editing_table: Option<EditTableState>,
+    // Edit Label mode state
+    pub editing_label: Option<EditLabelState>,
+    // Edit Vertices mode state
+    pub editing_vertices: Option<EditVerticesState>,
+    // Group being edited: clicks select the elements and groups inside it
+    pub editing_group: Option<usize>,
+    // Action awaiting confirmation in the unsaved changes modal
+    pub confirm: Option<ConfirmAction>,
     // Track if user has taken any action (for welcome screen)
     pub has_user_action: bool,
 }
 
 impl AppState {
     pub fn new() -> Self {
+        let canvas = CanvasState::default();
+        let history = HistoryState::new(&canvas);
         Self {
             cursor_x: 0,
             cursor_y: 0,
+            viewport_x: 0,
+            viewport_y: 0,
             active_panel: Panel::Canvas,
             layout: UILayout::default(),
             show_help: false,
             show_properties: true, // Default to showing properties
+            show_layers: false,
+            show_outline: false,
             show_tools_modal: false,
             command: CommandState::new(),
+            keymap: Keymap::default(),
             tool: ToolState::new(),
             file: FileState::new(),
-            canvas: CanvasState::default(),
+            swap: SwapState::new(),
+            canvas,
+            history,
+            clipboard: ClipboardState::new(),
             selection_state: SelectionState::new(),
             editing_table: None,
+            editing_label: None,
+            editing_vertices: None,
+            editing_group: None,
+            confirm: None,
             has_user_action: false,
         }
     }
 
+    /// Create the app state with the keymap and defaults from the config file
+    pub fn with_config(config: &Config) -> Self {
+        let mut state = Self::new();
+        state.keymap = config.keymap.clone();
+        state.tool.defaults = config.defaults.clone();
+        state.tool.tool_locked = config.defaults.tool_lock;
+        state.command.load_history();
+        state
+    }
+
     // ============================================================================
     // Command Mode
     // ============================================================================
@@ -96,14 +189,28 @@ impl AppState {
         self.command.backspace();
     }
 
-    pub fn execute_command(&mut self) {
+    pub fn command_history_prev(&mut self) {
+        self.command.history_prev();
+    }
+
+    pub fn command_history_next(&mut self) {
+        self.command.history_next();
+    }
+
+    pub fn complete_command(&mut self) {
+        self.command.complete();
+    }
+
+    /// Execute the command in the buffer. Returns true if the app should quit.
+    pub fn execute_command(&mut self) -> bool {
         let action = self.command.parse();
-        CommandExecutor::execute(action, self);
+        let should_quit = CommandExecutor::execute(action, self);
         self.command.finish();
+        should_quit
     }
 
     // ============================================================================
-    // Help Modal & Properties Panel
+    // Help Modal, Properties, Layers & Outline Panels
     // ============================================================================
     pub fn toggle_help(&mut self) {
         self.show_help = !self.show_help;
@@ -113,6 +220,14 @@ impl AppState {
         self.show_properties = !self.show_properties;
     }
 
+    pub fn toggle_layers(&mut self) {
+        self.show_layers = !self.show_layers;
+    }
+
+    pub fn toggle_outline(&mut self) {
+        self.show_outline = !self.show_outline;
+    }
+
     pub fn toggle_tools_modal(&mut self) {
         self.show_tools_modal = !self.show_tools_modal;
     }
@@ -125,7 +240,7 @@ impl AppState {
         self.tool.toggle_tool_lock();
     }
 
-    pub fn select_tool(&mut self, tool: crate::tools::Tool) {
+    pub fn select_tool(&mut self, tool: Tool) {
         let should_deselect = self.tool.select_tool(tool);
         // Deselect when switching away from Select tool
         if should_deselect && self.is_in_selection_mode() {
@@ -178,37 +293,59 @@ impl AppState {
         self.selection_state.get_move_offset()
     }
 
+    /// Get resize handle positions around the selected rectangle or table
+    pub fn get_resize_handles(&self) -> Vec<(i32, i32)> {
+        self.selection_state.get_resize_handles(&self.canvas)
+    }
+
+    /// Get the element being resized at its new size
+    pub fn get_resize_preview(&self) -> Option<Element> {
+        self.selection_state.get_resize_preview(&self.canvas)
+    }
+
     pub fn is_in_selection_mode(&self) -> bool {
         self.selection_state.is_in_selection_mode()
     }
 
     // Selection operations
 
-    pub fn start_selection(&mut self, x: u16, y: u16) {
+    pub fn start_selection(&mut self, x: i32, y: i32) {
         self.selection_state.start_selection(x, y);
     }
 
-    pub fn update_selection(&mut self, x: u16, y: u16) {
+    pub fn update_selection(&mut self, x: i32, y: i32) {
         self.selection_state.update_selection(x, y);
     }
 
-    pub fn finish_selection(&mut self, x: u16, y: u16) {
-        self.selection_state.finish_selection(x, y, &self.canvas);
+    pub fn finish_selection(&mut self, x: i32, y: i32) {
+        // Clicking anything outside the group being edited leaves it
+        if let Some(group_id) = self.group_scope()
+            && self.selection_state.is_click(x, y)
+            && self
+                .canvas
+                .find_element_at(x, y)
+                .is_none_or(|id| self.canvas.unit_of(id, Some(group_id)).is_none())
+        {
+            self.editing_group = None;
+        }
+        self.selection_state
+            .finish_selection(x, y, self.group_scope(), &self.canvas);
         // Exit edit mode if table is no longer selected
         self.check_edit_table_selection();
     }
 
     /// Toggle selection of element at position (for Shift+Click additive selection)
     pub fn toggle_selection_at(&mut self, x: i32, y: i32) {
-        self.selection_state.toggle_selection_at(x, y, &self.canvas);
+        self.selection_state
+            .toggle_selection_at(x, y, self.group_scope(), &self.canvas);
     }
 
-    pub fn start_move_selection(&mut self, x: u16, y: u16) {
+    pub fn start_move_selection(&mut self, x: i32, y: i32) {
         self.selection_state
             .start_move_selection(x, y, &self.canvas);
     }
 
-    pub fn update_move_selection(&mut self, x: u16, y: u16) {
+    pub fn update_move_selection(&mut self, x: i32, y: i32) {
         self.selection_state.update_move_selection(x, y);
     }
 
@@ -216,26 +353,429 @@ impl AppState {
         self.selection_state.finish_move_selection(&mut self.canvas);
     }
 
+    /// Resize handle of the selected rectangle or table at the given position
+    pub fn resize_handle_at(&self, x: i32, y: i32) -> Option<ResizeHandle> {
+        self.selection_state.resize_handle_at(x, y, &self.canvas)
+    }
+
+    pub fn start_resize(&mut self, handle: ResizeHandle, x: i32, y: i32) {
+        self.selection_state.start_resize(handle, x, y);
+    }
+
+    pub fn update_resize(&mut self, x: i32, y: i32) {
+        self.selection_state.update_resize(x, y);
+    }
+
+    pub fn finish_resize(&mut self) {
+        self.selection_state.finish_resize(&mut self.canvas);
+    }
+
     pub fn deselect(&mut self) {
         self.selection_state.deselect();
-        // Exit edit table mode when deselecting
+        // Exit edit table/label mode when deselecting
         if self.is_editing_table() {
             self.exit_edit_table_mode();
         }
+        if self.is_editing_label() {
+            self.exit_edit_label_mode();
+        }
+        if self.is_editing_vertices() {
+            self.exit_edit_vertices_mode();
+        }
+    }
+
+    // ============================================================================
+    // Groups
+    // ============================================================================
+
+    /// The group being edited, if it still exists (undo can remove it)
+    pub fn group_scope(&self) -> Option<usize> {
+        self.editing_group
+            .filter(|&id| self.canvas.get_group(id).is_some())
+    }
+
+    /// The selected elements and groups, as seen from the group being edited
+    pub fn selected_units(&self) -> Vec<Member> {
+        self.canvas
+            .units_of(&self.selection_state.selected_ids, self.group_scope())
+    }
+
+    /// Group the selected elements and groups. Returns false if fewer than two are
+    /// selected.
+    pub fn group_selected(&mut self) -> bool {
+        let units = self.selected_units();
+        if units.len() < 2 {
+            return false;
+        }
+        self.canvas.create_group(units, self.group_scope());
+        true
+    }
+
+    /// Dissolve the selected groups, keeping their elements selected. Returns false if
+    /// no group is selected.
+    pub fn ungroup_selected(&mut self) -> bool {
+        let mut ungrouped = false;
+        for unit in self.selected_units() {
+            if let Member::Group(id) = unit {
+                self.canvas.ungroup(id);
+                ungrouped = true;
+            }
+        }
+        ungrouped
+    }
+
+    /// Edit the inside of the selected group, so clicks pick its members. Returns false
+    /// unless exactly one group is selected.
+    pub fn enter_selected_group(&mut self) -> bool {
+        let [Member::Group(id)] = self.selected_units()[..] else {
+            return false;
+        };
+        self.deselect();
+        self.editing_group = Some(id);
+        true
+    }
+
+    /// Stop editing the current group, selecting it as a whole. Returns false if no
+    /// group is being edited.
+    pub fn exit_group(&mut self) -> bool {
+        let Some(id) = self.group_scope() else {
+            return false;
+        };
+        self.deselect();
+        self.editing_group = self.canvas.parent_group(Member::Group(id));
+        self.select_elements(self.canvas.selectable_members(Member::Group(id)));
+        true
+    }
+
+    // ============================================================================
+    // Layers
+    // ============================================================================
+
+    /// Add a layer above the given one and draw on it from now on. Returns its ID.
+    pub fn add_layer(&mut self, below: Option<usize>) -> usize {
+        let id = self.canvas.add_layer(below);
+        self.canvas.set_active_layer(id);
+        id
+    }
+
+    /// Draw new elements on the given layer
+    pub fn set_active_layer(&mut self, id: usize) {
+        self.canvas.set_active_layer(id);
+    }
+
+    /// Move a layer one step up or down in the drawing order
+    pub fn move_layer(&mut self, id: usize, up: bool) {
+        self.canvas.move_layer(id, up);
+    }
+
+    /// Show or hide a layer, deselecting elements that get hidden
+    pub fn toggle_layer_visible(&mut self, id: usize) {
+        self.canvas.toggle_layer_visible(id);
+        self.prune_selection();
+    }
+
+    /// Lock or unlock a layer, deselecting elements that get locked
+    pub fn toggle_layer_locked(&mut self, id: usize) {
+        self.canvas.toggle_layer_locked(id);
+        self.prune_selection();
+    }
+
+    /// Rename a layer, showing an error in the status bar if the name is taken
+    pub fn rename_layer(&mut self, id: usize, name: String) {
+        if let Err(e) = self.canvas.rename_layer(id, name) {
+            self.file.status_message = Some(format!("Error: {}", e));
+        }
+    }
+
+    /// Move the selected elements to another layer
+    pub fn move_selected_to_layer(&mut self, id: usize) {
+        self.canvas
+            .move_to_layer(&self.selection_state.selected_ids, id);
+        self.prune_selection();
     }
 
     /// Move selected elements by offset (used for arrow key movement)
     pub fn move_selected_elements(&mut self, dx: i32, dy: i32) {
+        // Consecutive nudges of the same selection form a single undo step
+        let selected_ids = self.selection_state.selected_ids.clone();
+        self.history.set_pending_kind(EditKind::Nudge(selected_ids));
         self.selection_state
             .move_selected_elements(dx, dy, &mut self.canvas);
     }
 
+    /// Move the selected elements up or down in the drawing order
+    pub fn reorder_selected(&mut self, order: ZOrder) {
+        self.canvas
+            .reorder(&self.selection_state.selected_ids, order);
+    }
+
     /// Delete selected elements
     pub fn delete_selected_elements(&mut self) {
         self.selection_state
             .delete_selected_elements(&mut self.canvas);
     }
 
+    /// Line up the selected elements (`:align`)
+    pub fn align_selected(&mut self, alignment: Alignment) {
+        self.selection_state
+            .align_selected_elements(alignment, &mut self.canvas);
+    }
+
+    /// Select all elements matching the filter (`:select`), skipping hidden and locked
+    /// layers. Returns how many matched.
+    pub fn select_matching(&mut self, filter: &SelectFilter) -> usize {
+        let ids: Vec<usize> = self
+            .canvas
+            .elements()
+            .iter()
+            .filter(|element| self.canvas.is_selectable(element))
+            .filter(|element| match filter {
+                SelectFilter::All => true,
+                SelectFilter::Type(name) => element.type_name().eq_ignore_ascii_case(name),
+                SelectFilter::Name(name) => element.name().eq_ignore_ascii_case(name),
+            })
+            .map(Element::id)
+            .collect();
+        let count = ids.len();
+        self.select_elements(ids);
+        count
+    }
+
+    /// Set a property on every selected element that has it (`:set`), parsing the
+    /// value according to the property's field type
+    pub fn set_selected_property(&mut self, name: &str, value: &str) -> anyhow::Result<()> {
+        let ids = self.selection_state.selected_ids.clone();
+        if ids.is_empty() {
+            anyhow::bail!("No elements selected");
+        }
+
+        let mut found = false;
+        for id in ids {
+            let Some(element) = self.canvas.get_element(id) else {
+                continue;
+            };
+            let Some(field_type) = element
+                .properties_spec()
+                .get_field(name)
+                .map(|field| field.field_type.clone())
+            else {
+                continue;
+            };
+            found = true;
+            let value = field_type.parse_value(value)?;
+            if let Some(element) = self.canvas.get_element_mut(id) {
+                element.set_property(name, value)?;
+            }
+            self.canvas.reroute_connectors_to(id);
+        }
+
+        if !found {
+            anyhow::bail!("Selected elements have no property `{}`", name);
+        }
+        Ok(())
+    }
+
+    /// Properties shown in the panel for the selection: all of them for a single
+    /// element, otherwise the fields every selected element has. Names stay per
+    /// element, so they are left out of a multi-selection.
+    pub fn selected_properties_spec(&self) -> Option<PropertiesSpec> {
+        let mut elements = self
+            .selection_state
+            .selected_ids
+            .iter()
+            .filter_map(|&id| self.canvas.get_element(id));
+        let first = elements.next()?.properties_spec();
+        let Some(second) = elements.next() else {
+            return Some(first);
+        };
+        let spec = elements.fold(
+            first.intersect(&second.properties_spec()),
+            |spec, element| spec.intersect(&element.properties_spec()),
+        );
+        Some(spec.without("name"))
+    }
+
+    /// Value of a property across the selection, or `None` when the selected elements
+    /// have different values (shown as mixed in the panel)
+    pub fn selected_property_value(&self, name: &str) -> Option<PropertyValue> {
+        let mut values = self
+            .selection_state
+            .selected_ids
+            .iter()
+            .filter_map(|&id| self.canvas.get_element(id)?.get_property(name));
+        let first = values.next()?;
+        values.all(|value| value == first).then_some(first)
+    }
+
+    /// Apply a property value from the panel to every selected element that has it.
+    /// Elements that reject the value are left unchanged.
+    pub fn apply_selected_property(&mut self, name: &str, value: PropertyValue) {
+        let ids = self.selection_state.selected_ids.clone();
+        // Repeated changes to the same property coalesce into one undo step
+        self.history.set_pending_kind(EditKind::Property {
+            element_ids: ids.clone(),
+            name: name.to_string(),
+        });
+        for id in ids {
+            if let Some(element) = self.canvas.get_element_mut(id) {
+                let _ = element.set_property(name, value.clone());
+            }
+            self.canvas.reroute_connectors_to(id);
+        }
+    }
+
+    /// Rename the selected element (`:rename`)
+    pub fn rename_selected(&mut self, name: &str) -> anyhow::Result<()> {
+        let &[id] = self.selection_state.selected_ids.as_slice() else {
+            anyhow::bail!("Select one element to rename");
+        };
+        self.rename_element(id, name)
+    }
+
+    /// Rename any element (outline panel)
+    pub fn rename_element(&mut self, id: usize, name: &str) -> anyhow::Result<()> {
+        match self.canvas.get_element_mut(id) {
+            Some(element) => element.set_property("name", PropertyValue::Text(name.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    /// Select only the given element and center the view on it (outline panel).
+    /// Elements on hidden or locked layers are only jumped to.
+    pub fn select_and_jump_to(&mut self, id: usize) {
+        let Some(element) = self.canvas.get_element(id) else {
+            return;
+        };
+        let bounds = element.bounds();
+        if self.canvas.is_selectable(element) {
+            self.select_elements(vec![id]);
+        }
+        self.goto(
+            (bounds.min.x + bounds.max.x) / 2,
+            (bounds.min.y + bounds.max.y) / 2,
+        );
+    }
+
+    /// Add an element to the selection, or remove it if it is already selected
+    /// (outline panel)
+    pub fn toggle_element_selection(&mut self, id: usize) {
+        let mut ids = self.selection_state.selected_ids.clone();
+        if let Some(position) = ids.iter().position(|&selected| selected == id) {
+            ids.remove(position);
+        } else if self
+            .canvas
+            .get_element(id)
+            .is_some_and(|e| self.canvas.is_selectable(e))
+        {
+            ids.push(id);
+        }
+        self.select_elements(ids);
+    }
+
+    // ============================================================================
+    // Clipboard
+    // ============================================================================
+
+    /// Copy selected elements to the clipboard
+    pub fn copy_selection(&mut self) {
+        if self.selection_state.selected_ids.is_empty() {
+            return;
+        }
+        self.clipboard
+            .copy(&self.selection_state.selected_ids, &self.canvas);
+    }
+
+    /// Copy selected elements to the clipboard and remove them from the canvas
+    pub fn cut_selection(&mut self) {
+        if self.selection_state.selected_ids.is_empty() {
+            return;
+        }
+        self.copy_selection();
+        self.delete_selected_elements();
+    }
+
+    /// Paste clipboard contents at the cursor and select the pasted elements
+    pub fn paste(&mut self) {
+        if self.clipboard.is_empty() {
+            return;
+        }
+        let pasted = self
+            .clipboard
+            .paste_at(self.cursor_x, self.cursor_y, &mut self.canvas);
+        self.select_elements(pasted);
+    }
+
+    /// Duplicate selected elements at a small offset, leaving the clipboard untouched
+    pub fn duplicate_selection(&mut self) {
+        if self.selection_state.selected_ids.is_empty() {
+            return;
+        }
+        let ids = &self.selection_state.selected_ids;
+        let elements = clipboard::clone_elements(ids, &self.canvas);
+        let groups = clipboard::clone_groups(ids, &self.canvas);
+        let (dx, dy) = DUPLICATE_OFFSET;
+        let duplicated = clipboard::paste_elements(&elements, &groups, dx, dy, &mut self.canvas);
+        self.select_elements(duplicated);
+    }
+
+    /// Switch to the Select tool and replace the selection with the given elements
+    fn select_elements(&mut self, ids: Vec<usize>) {
+        self.select_tool(Tool::Select);
+        self.deselect();
+        if !ids.is_empty() {
+            self.selection_state.selected_ids = ids;
+            self.selection_state.mode = SelectionMode::Selected;
+        }
+    }
+
+    // ============================================================================
+    // Undo / Redo
+    // ============================================================================
+
+    /// Record an undo step if the canvas changed (called once per handled event)
+    pub fn commit_history(&mut self) {
+        if self.history.commit(&self.canvas) {
+            self.file.mark_dirty();
+        }
+    }
+
+    /// Forget all history, e.g. after loading a different file
+    pub fn reset_history(&mut self) {
+        self.history.reset(&self.canvas);
+        // The canvas was replaced, so the group being edited is gone
+        self.editing_group = None;
+    }
+
+    pub fn undo(&mut self) {
+        if self.history.undo(&mut self.canvas) {
+            self.file.mark_dirty();
+            self.prune_selection();
+        }
+    }
+
+    pub fn redo(&mut self) {
+        if self.history.redo(&mut self.canvas) {
+            self.file.mark_dirty();
+            self.prune_selection();
+        }
+    }
+
+    /// Drop selected IDs that no longer exist on the canvas or can no longer be selected
+    /// (after undo/redo, or hiding or locking a layer)
+    fn prune_selection(&mut self) {
+        let canvas = &self.canvas;
+        self.selection_state.selected_ids.retain(|&id| {
+            canvas
+                .get_element(id)
+                .is_some_and(|e| canvas.is_selectable(e))
+        });
+        if self.selection_state.selected_ids.is_empty() {
+            self.deselect();
+        } else {
+            self.check_edit_table_selection();
+        }
+    }
+
     // ============================================================================
     // UI State
     // ============================================================================
@@ -244,18 +784,52 @@ impl AppState {
         self.active_panel = panel;
     }
 
-    pub fn update_cursor(&mut self, x: u16, y: u16) {
+    pub fn update_cursor(&mut self, x: i32, y: i32) {
         self.cursor_x = x;
         self.cursor_y = y;
     }
 
+    /// Move the cursor from the keyboard, scrolling the viewport to keep it visible
+    pub fn move_cursor(&mut self, dx: i32, dy: i32) {
+        self.update_cursor(self.cursor_x + dx, self.cursor_y + dy);
+
+        // Inside the canvas border
+        let width = (self.layout.canvas.width.saturating_sub(2) as i32).max(1);
+        let height = (self.layout.canvas.height.saturating_sub(2) as i32).max(1);
+        self.viewport_x = self
+            .viewport_x
+            .clamp(self.cursor_x - width + 1, self.cursor_x);
+        self.viewport_y = self
+            .viewport_y
+            .clamp(self.cursor_y - height + 1, self.cursor_y);
+    }
+
+    /// Scroll the viewport by the given offset (in canvas cells).
+    /// The cursor moves along so it stays at the same screen position.
+    pub fn pan_viewport(&mut self, dx: i32, dy: i32) {
+        self.viewport_x += dx;
+        self.viewport_y += dy;
+        self.cursor_x += dx;
+        self.cursor_y += dy;
+    }
+
+    /// Move the cursor to a canvas position and center the view on it (`:goto`)
+    pub fn goto(&mut self, x: i32, y: i32) {
+        // Inside the canvas border
+        let width = self.layout.canvas.width.saturating_sub(2) as i32;
+        let height = self.layout.canvas.height.saturating_sub(2) as i32;
+        self.viewport_x = x - width / 2;
+        self.viewport_y = y - height / 2;
+        self.update_cursor(x, y);
+    }
+
     /// Check if screen coordinates are inside the canvas bounds
-    pub fn is_inside_canvas(&self, column: u16, row: u16) -> bool {
+    pub fn is_inside_canvas(&self, column: i32, row: i32) -> bool {
         let canvas_area = self.layout.canvas;
-        column >= canvas_area.x
-            && column < canvas_area.x + canvas_area.width
-            && row >= canvas_area.y
-            && row < canvas_area.y + canvas_area.height
+        column >= canvas_area.x as i32
+            && column < (canvas_area.x + canvas_area.width) as i32
+            && row >= canvas_area.y as i32
+            && row < (canvas_area.y + canvas_area.height) as i32
     }
 
     // ============================================================================
@@ -264,7 +838,152 @@ impl AppState {
 
     /// Load a diagram from a file silently (for initial load)
     pub fn load_from_file_silent(&mut self, path: impl AsRef<Path>) -> anyhow::Result<()> {
-        self.file.load_from_file_silent(&mut self.canvas, path)
+        self.file.load_from_file_silent(&mut self.canvas, path)?;
+        self.reset_history();
+        Ok(())
+    }
+
+    /// Open a diagram, replacing the canvas. Errors are shown as a status message.
+    pub fn open_file(&mut self, path: &str) {
+        if let Err(e) = self.file.load_from_file(&mut self.canvas, path) {
+            self.file.status_message = Some(format!("Error: {}", e));
+        } else {
+            self.deselect();
+            self.reset_history();
+            // Changes to the previous diagram were saved or discarded, so its swap must
+            // not be offered back (e.g. `:e!` on the same file)
+            if let Err(e) = self.remove_swap() {
+                self.file.status_message = Some(format!("Error: {}", e));
+            }
+            self.offer_recovery();
+        }
+    }
+
+    /// Replace the canvas with an empty, unnamed diagram
+    pub fn new_diagram(&mut self) {
+        // The changes are discarded, so their swap file is no longer needed
+        if let Err(e) = self.swap.remove() {
+            self.file.status_message = Some(format!("Error: {}", e));
+            return;
+        }
+        self.canvas = CanvasState::default();
+        self.file.current_file = None;
+        self.file.dirty = false;
+        self.deselect();
+        self.reset_history();
+        self.file.status_message = Some("New diagram".to_string());
+    }
+
+    /// Export the canvas to a file (`:export`)
+    pub fn export_to(&self, format: ExportFormat, path: &str) -> anyhow::Result<()> {
+        let content = export::export(&self.canvas, format, ExportOptions::default());
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
+    // ============================================================================
+    // Autosave & Recovery
+    // ============================================================================
+
+    /// Write unsaved changes to the swap file (called periodically from the event loop)
+    pub fn autosave(&mut self) {
+        let result = self.swap.autosave(
+            &self.canvas,
+            self.file.current_file.as_deref(),
+            self.file.is_dirty(),
+        );
+        if let Err(e) = result {
+            self.file.status_message = Some(format!("Autosave failed: {}", e));
+        }
+    }
+
+    /// Remove this session's swap file (on exit)
+    pub fn remove_swap(&mut self) -> anyhow::Result<()> {
+        self.swap.remove()
+    }
+
+    /// Ask whether to recover if an earlier session left a newer swap file behind
+    pub fn offer_recovery(&mut self) {
+        if let Some(path) = swap::find_recoverable(self.file.current_file.as_deref()) {
+            self.confirm = Some(ConfirmAction::Recover(path));
+        }
+    }
+
+    /// Replace the canvas with the contents of a swap file, keeping the current file name
+    fn recover_from_swap(&mut self, path: PathBuf) {
+        if let Err(e) = self.canvas.load_from_file(&path) {
+            self.file.status_message = Some(format!("Error: {}", e));
+            return;
+        }
+        self.deselect();
+        self.reset_history();
+        self.file.mark_dirty();
+        self.file.status_message = Some("Recovered unsaved changes".to_string());
+        self.swap.adopt(path);
+    }
+
+    // ============================================================================
+    // Unsaved Changes
+    // ============================================================================
+
+    /// Quit unless there are unsaved changes, in which case ask first.
+    /// Returns true if the app should quit now.
+    pub fn request_quit(&mut self) -> bool {
+        if self.file.is_dirty() {
+            self.confirm = Some(ConfirmAction::Quit);
+            return false;
+        }
+        true
+    }
+
+    /// Open a diagram, asking first if there are unsaved changes
+    pub fn request_open(&mut self, path: String) {
+        if self.file.is_dirty() {
+            self.confirm = Some(ConfirmAction::Open(path));
+        } else {
+            self.open_file(&path);
+        }
+    }
+
+    /// Start an empty diagram, asking first if there are unsaved changes
+    pub fn request_new(&mut self) {
+        if self.file.is_dirty() {
+            self.confirm = Some(ConfirmAction::New);
+        } else {
+            self.new_diagram();
+        }
+    }
+
+    /// Carry out the action awaiting confirmation. Returns true if the app should quit.
+    pub fn confirm_pending_action(&mut self) -> bool {
+        match self.confirm.take() {
+            Some(ConfirmAction::Quit) => true,
+            Some(ConfirmAction::Open(path)) => {
+                self.open_file(&path);
+                false
+            }
+            Some(ConfirmAction::New) => {
+                self.new_diagram();
+                false
+            }
+            Some(ConfirmAction::Recover(path)) => {
+                self.recover_from_swap(path);
+                false
+            }
+            None => false,
+        }
+    }
+
+    pub fn cancel_pending_action(&mut self) {
+        // Declining recovery leaves the swap file alone, so a stray key can't destroy
+        // the only copy of the changes
+        if let Some(ConfirmAction::Recover(path)) = self.confirm.take() {
+            self.file.status_message = Some(format!("Unsaved changes kept in {}", path.display()));
+        }
+    }
+
+    pub fn is_confirming(&self) -> bool {
+        self.confirm.is_some()
     }
 
     // ============================================================================
@@ -323,6 +1042,59 @@ impl AppState {
             }
         }
     }
+
+    // ============================================================================
+    // Edit Label Mode
+    // ============================================================================
+
+    /// Enter Edit Label mode for the given rectangle, with the cursor at the end
+    pub fn enter_edit_label_mode(&mut self, rect_id: usize, label: &str) {
+        self.editing_label = Some(EditLabelState {
+            rect_id,
+            cursor_pos: label.chars().count(),
+            original_label: label.to_string(),
+        });
+    }
+
+    /// Exit Edit Label mode
+    pub fn exit_edit_label_mode(&mut self) {
+        self.editing_label = None;
+    }
+
+    /// Check if currently in Edit Label mode
+    pub fn is_editing_label(&self) -> bool {
+        self.editing_label.is_some()
+    }
+
+    // ============================================================================
+    // Edit Vertices Mode
+    // ============================================================================
+
+    /// Enter Edit Vertices mode for the given line or arrow, with its end selected
+    pub fn enter_edit_vertices_mode(&mut self, element_id: usize) {
+        let Some(element) = self.canvas.get_element(element_id) else {
+            return;
+        };
+        let Some(vertices) = element.vertices() else {
+            return;
+        };
+        self.editing_vertices = Some(EditVerticesState {
+            element_id,
+            vertex: vertices.len().saturating_sub(1),
+            dragging: false,
+            original: element.clone(),
+        });
+    }
+
+    /// Exit Edit Vertices mode
+    pub fn exit_edit_vertices_mode(&mut self) {
+        self.editing_vertices = None;
+    }
+
+    /// Check if currently in Edit Vertices mode
+    pub fn is_editing_vertices(&self) -> bool {
+        self.editing_vertices.is_some()
+    }
 }
 
 impl Default for AppState {
@@ -330,3 +1102,6 @@ impl Default for AppState {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests;
@@ -1,14 +1,17 @@
-use crate::state::CanvasState;
-use crate::types::SelectionMode;
+use crate::elements::Element;
+use crate::state::{Alignment, CanvasState};
+use crate::types::{Bounds, ResizeHandle, SelectionMode};
 
 pub struct SelectionState {
     pub mode: SelectionMode,
     pub selected_ids: Vec<usize>,         // IDs of selected elements
-    pub select_start: Option<(u16, u16)>, // For drag-select box
-    pub select_current: Option<(u16, u16)>,
+    pub select_start: Option<(i32, i32)>, // For drag-select box
+    pub select_current: Option<(i32, i32)>,
     pub has_dragged: bool,
-    pub move_start: Option<(u16, u16)>,
+    pub move_start: Option<(i32, i32)>,
     pub move_offset: (i32, i32),
+    pub resize_handle: Option<ResizeHandle>,
+    pub resize_to: (i32, i32),
 }
 
 impl SelectionState {
@@ -21,6 +24,8 @@ impl SelectionState {
             has_dragged: false,
             move_start: None,
             move_offset: (0, 0),
+            resize_handle: None,
+            resize_to: (0, 0),
         }
     }
 
@@ -32,6 +37,8 @@ impl SelectionState {
         self.has_dragged = false;
         self.move_start = None;
         self.move_offset = (0, 0);
+        self.resize_handle = None;
+        self.resize_to = (0, 0);
     }
 
     // Query/Read methods
@@ -47,19 +54,19 @@ impl SelectionState {
                 let (top, bottom) = if sy <= cy { (sy, cy) } else { (cy, sy) };
 
                 // Rounded corners
-                points.push((left as i32, top as i32, '╭'));
-                points.push((right as i32, top as i32, '╮'));
-                points.push((left as i32, bottom as i32, '╰'));
-                points.push((right as i32, bottom as i32, '╯'));
+                points.push((left, top, '╭'));
+                points.push((right, top, '╮'));
+                points.push((left, bottom, '╰'));
+                points.push((right, bottom, '╯'));
 
                 // Edges
                 for x in (left + 1)..right {
-                    points.push((x as i32, top as i32, '─'));
-                    points.push((x as i32, bottom as i32, '─'));
+                    points.push((x, top, '─'));
+                    points.push((x, bottom, '─'));
                 }
                 for y in (top + 1)..bottom {
-                    points.push((left as i32, y as i32, '│'));
-                    points.push((right as i32, y as i32, '│'));
+                    points.push((left, y, '│'));
+                    points.push((right, y, '│'));
                 }
             }
         }
@@ -81,27 +88,76 @@ impl SelectionState {
         }
     }
 
+    /// The selected element, if it is the only one selected and can be resized
+    fn resizable_element<'a>(&self, canvas: &'a CanvasState) -> Option<&'a Element> {
+        let &[id] = self.selected_ids.as_slice() else {
+            return None;
+        };
+        canvas
+            .get_element(id)
+            .filter(|element| element.is_resizable())
+    }
+
+    /// Resize handle positions to draw around the selected element
+    pub fn get_resize_handles(&self, canvas: &CanvasState) -> Vec<(i32, i32)> {
+        if self.mode != SelectionMode::Selected {
+            return vec![];
+        }
+        let Some(element) = self.resizable_element(canvas) else {
+            return vec![];
+        };
+        let bounds = element.bounds();
+        ResizeHandle::all()
+            .iter()
+            .map(|handle| {
+                let pos = handle.position(&bounds);
+                (pos.x, pos.y)
+            })
+            .collect()
+    }
+
+    /// Resize handle of the selected element at the given position
+    pub fn resize_handle_at(&self, x: i32, y: i32, canvas: &CanvasState) -> Option<ResizeHandle> {
+        if self.mode != SelectionMode::Selected {
+            return None;
+        }
+        let bounds = self.resizable_element(canvas)?.bounds();
+        ResizeHandle::all().iter().copied().find(|handle| {
+            let pos = handle.position(&bounds);
+            (pos.x, pos.y) == (x, y)
+        })
+    }
+
+    /// The element being resized, at its new size (for the live preview)
+    pub fn get_resize_preview(&self, canvas: &CanvasState) -> Option<Element> {
+        let handle = self.resize_handle?;
+        let mut element = self.resizable_element(canvas)?.clone();
+        let (x, y) = self.resize_to;
+        element.resize(handle, x, y);
+        Some(element)
+    }
+
     pub fn is_in_selection_mode(&self) -> bool {
         !matches!(self.mode, SelectionMode::Idle)
     }
 
     // State-only updates
 
-    pub fn start_selection(&mut self, x: u16, y: u16) {
+    pub fn start_selection(&mut self, x: i32, y: i32) {
         self.mode = SelectionMode::Selecting;
         self.select_start = Some((x, y));
         self.select_current = Some((x, y));
         self.has_dragged = false;
     }
 
-    pub fn update_selection(&mut self, x: u16, y: u16) {
+    pub fn update_selection(&mut self, x: i32, y: i32) {
         self.select_current = Some((x, y));
         self.has_dragged = true;
     }
 
-    pub fn update_move_selection(&mut self, x: u16, y: u16) {
+    pub fn update_move_selection(&mut self, x: i32, y: i32) {
         if let Some((start_x, start_y)) = self.move_start {
-            self.move_offset = (x as i32 - start_x as i32, y as i32 - start_y as i32);
+            self.move_offset = (x - start_x, y - start_y);
         }
     }
 
@@ -111,14 +167,22 @@ impl SelectionState {
 
     // Selection operations that need Canvas access
 
-    pub fn finish_selection(&mut self, x: u16, y: u16, canvas: &CanvasState) {
+    /// Whether the selection being finished at (x, y) is a click rather than a drag
+    pub fn is_click(&self, x: i32, y: i32) -> bool {
+        self.select_start
+            .is_some_and(|start| !self.has_dragged || start == (x, y))
+    }
+
+    /// Finish a click or drag selection. `scope` is the group being edited; elements
+    /// inside it are selected by the unit directly in it (see `CanvasState::unit_of`).
+    pub fn finish_selection(&mut self, x: i32, y: i32, scope: Option<usize>, canvas: &CanvasState) {
         if let Some((sx, sy)) = self.select_start {
-            if !self.has_dragged || (sx == x && sy == y) {
-                // Click - select single element at this position
-                self.select_element_at(x as i32, y as i32, canvas);
+            if self.is_click(x, y) {
+                // Click - select single element (or its group) at this position
+                self.select_element_at(x, y, scope, canvas);
             } else {
                 // Drag - select rectangle
-                self.select_rectangle(sx, sy, x, y, canvas);
+                self.select_rectangle(sx, sy, x, y, scope, canvas);
             }
         }
         self.select_start = None;
@@ -126,28 +190,41 @@ impl SelectionState {
         self.has_dragged = false;
     }
 
-    fn select_element_at(&mut self, x: i32, y: i32, canvas: &CanvasState) {
+    fn select_element_at(&mut self, x: i32, y: i32, scope: Option<usize>, canvas: &CanvasState) {
         // Find element at this position
-        if let Some(element_id) = canvas.find_element_at(x, y) {
-            self.selected_ids.clear();
-            self.selected_ids.push(element_id);
+        if let Some(unit) = canvas
+            .find_element_at(x, y)
+            .and_then(|id| canvas.unit_of(id, scope))
+        {
+            self.selected_ids = canvas.selectable_members(unit);
             self.mode = SelectionMode::Selected;
         } else {
             self.mode = SelectionMode::Idle;
         }
     }
 
-    fn select_rectangle(&mut self, x1: u16, y1: u16, x2: u16, y2: u16, canvas: &CanvasState) {
+    fn select_rectangle(
+        &mut self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        scope: Option<usize>,
+        canvas: &CanvasState,
+    ) {
         let (left, right) = if x1 <= x2 { (x1, x2) } else { (x2, x1) };
         let (top, bottom) = if y1 <= y2 { (y1, y2) } else { (y2, y1) };
 
-        // Find all elements that are fully contained within selection rectangle
-        let element_ids = canvas.find_elements_fully_inside_rect(
-            left as i32,
-            top as i32,
-            right as i32,
-            bottom as i32,
-        );
+        // Find all elements that are fully contained within selection rectangle, and
+        // keep the groups that are entirely inside
+        let inside = canvas.find_elements_fully_inside_rect(left, top, right, bottom);
+        let element_ids: Vec<usize> = canvas
+            .units_of(&inside, scope)
+            .into_iter()
+            .map(|unit| canvas.selectable_members(unit))
+            .filter(|ids| ids.iter().all(|id| inside.contains(id)))
+            .flatten()
+            .collect();
 
         if !element_ids.is_empty() {
             self.selected_ids = element_ids;
@@ -157,17 +234,32 @@ impl SelectionState {
         }
     }
 
-    /// Toggle selection of element at position (for Shift+Click additive selection)
-    pub fn toggle_selection_at(&mut self, x: i32, y: i32, canvas: &CanvasState) {
+    /// Toggle selection of element (or its group) at position (for Shift+Click
+    /// additive selection)
+    pub fn toggle_selection_at(
+        &mut self,
+        x: i32,
+        y: i32,
+        scope: Option<usize>,
+        canvas: &CanvasState,
+    ) {
         // Find element at this position
-        if let Some(element_id) = canvas.find_element_at(x, y) {
+        if let Some(unit) = canvas
+            .find_element_at(x, y)
+            .and_then(|id| canvas.unit_of(id, scope))
+        {
+            let element_ids = canvas.selectable_members(unit);
             // Check if already selected
-            if let Some(pos) = self.selected_ids.iter().position(|&id| id == element_id) {
+            if element_ids.iter().all(|id| self.selected_ids.contains(id)) {
                 // Remove from selection
-                self.selected_ids.remove(pos);
+                self.selected_ids.retain(|id| !element_ids.contains(id));
             } else {
                 // Add to selection
-                self.selected_ids.push(element_id);
+                for id in element_ids {
+                    if !self.selected_ids.contains(&id) {
+                        self.selected_ids.push(id);
+                    }
+                }
             }
 
             // Update mode based on whether we have selections
@@ -181,10 +273,10 @@ impl SelectionState {
 
     // Move operations
 
-    pub fn start_move_selection(&mut self, x: u16, y: u16, canvas: &CanvasState) {
+    pub fn start_move_selection(&mut self, x: i32, y: i32, canvas: &CanvasState) {
         // Check if clicking on or inside any selected element's bounds
-        let px = x as i32;
-        let py = y as i32;
+        let px = x;
+        let py = y;
 
         for element_id in &self.selected_ids {
             if let Some(element) = canvas.get_element(*element_id) {
@@ -199,15 +291,49 @@ impl SelectionState {
         }
     }
 
+    // Resize operations
+
+    pub fn start_resize(&mut self, handle: ResizeHandle, x: i32, y: i32) {
+        self.mode = SelectionMode::Resizing;
+        self.resize_handle = Some(handle);
+        self.resize_to = (x, y);
+    }
+
+    pub fn update_resize(&mut self, x: i32, y: i32) {
+        self.resize_to = (x, y);
+    }
+
+    pub fn finish_resize(&mut self, canvas: &mut CanvasState) {
+        if let Some(handle) = self.resize_handle.take()
+            && let Some(original) = self.resizable_element(canvas)
+        {
+            let handle_pos = handle.position(&original.bounds());
+            let (x, y) = self.resize_to;
+            // Skip clicks on a handle that didn't drag
+            if (handle_pos.x, handle_pos.y) != (x, y) {
+                let id = original.id();
+                if let Some(element) = canvas.get_element_mut(id) {
+                    element.resize(handle, x, y);
+                }
+                canvas.update_connectors_after_move(&[id]);
+            }
+        }
+
+        self.mode = SelectionMode::Selected;
+        self.resize_to = (0, 0);
+    }
+
     pub fn finish_move_selection(&mut self, canvas: &mut CanvasState) {
-        let dx = self.move_offset.0 as i16;
-        let dy = self.move_offset.1 as i16;
+        let (dx, dy) = self.move_offset;
 
-        // Move all selected elements by offset
-        for element_id in &self.selected_ids {
-            if let Some(element) = canvas.get_element_mut(*element_id) {
-                element.translate(dx, dy);
+        // Move all selected elements by offset (skip clicks that didn't drag)
+        if dx != 0 || dy != 0 {
+            for element_id in &self.selected_ids {
+                if let Some(element) = canvas.get_element_mut(*element_id) {
+                    element.translate(dx, dy);
+                }
             }
+            canvas.update_connectors_after_move(&self.selected_ids);
         }
 
         self.mode = SelectionMode::Selected;
@@ -223,9 +349,45 @@ impl SelectionState {
 
         for element_id in &self.selected_ids {
             if let Some(element) = canvas.get_element_mut(*element_id) {
-                element.translate(dx as i16, dy as i16);
+                element.translate(dx, dy);
+            }
+        }
+        canvas.update_connectors_after_move(&self.selected_ids);
+    }
+
+    /// Line up the selected elements on the outermost edge (or the center) of the
+    /// selection's bounding box
+    pub fn align_selected_elements(&mut self, alignment: Alignment, canvas: &mut CanvasState) {
+        let bounds: Vec<(usize, Bounds)> = self
+            .selected_ids
+            .iter()
+            .filter_map(|&id| Some((id, canvas.get_element(id)?.bounds())))
+            .collect();
+        if bounds.len() < 2 {
+            return;
+        }
+
+        let min_x = bounds.iter().map(|(_, b)| b.min.x).min().unwrap_or(0);
+        let max_x = bounds.iter().map(|(_, b)| b.max.x).max().unwrap_or(0);
+        let min_y = bounds.iter().map(|(_, b)| b.min.y).min().unwrap_or(0);
+        let max_y = bounds.iter().map(|(_, b)| b.max.y).max().unwrap_or(0);
+
+        for (id, b) in bounds {
+            let (dx, dy) = match alignment {
+                Alignment::Left => (min_x - b.min.x, 0),
+                Alignment::Right => (max_x - b.max.x, 0),
+                Alignment::Center => ((min_x + max_x) / 2 - (b.min.x + b.max.x) / 2, 0),
+                Alignment::Top => (0, min_y - b.min.y),
+                Alignment::Bottom => (0, max_y - b.max.y),
+                Alignment::Middle => (0, (min_y + max_y) / 2 - (b.min.y + b.max.y) / 2),
+            };
+            if (dx, dy) != (0, 0)
+                && let Some(element) = canvas.get_element_mut(id)
+            {
+                element.translate(dx, dy);
             }
         }
+        canvas.update_connectors_after_move(&self.selected_ids);
     }
 
     /// Delete selected elements
@@ -0,0 +1,181 @@
+use crate::state::CanvasState;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Minimum time between two swap file writes
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Swap file name prefix and extension for diagrams that haven't been saved yet. The
+/// process ID goes in between, so two unnamed sessions don't share a swap file.
+const UNNAMED_SWAP_PREFIX: &str = "unnamed-";
+const UNNAMED_SWAP_EXTENSION: &str = ".textdraw.swp";
+
+/// Periodic autosave of the canvas to a swap file, for recovery after a crash.
+///
+/// Diagrams with a file are swapped next to it (`dir/.name.textdraw.swp`), unnamed
+/// diagrams go to the textdraw state directory, one per session. The swap file is
+/// removed again once the changes are saved or deliberately discarded.
+pub struct SwapState {
+    // Swap file written by this session, if any
+    written: Option<PathBuf>,
+    // Canvas revision in the swap file
+    written_revision: Option<u64>,
+    last_write: Option<Instant>,
+}
+
+impl SwapState {
+    pub fn new() -> Self {
+        Self {
+            written: None,
+            written_revision: None,
+            last_write: None,
+        }
+    }
+
+    /// Write the canvas to the swap file if it has unsaved changes that aren't in the
+    /// swap yet and the last write was long enough ago. Removes the swap once the
+    /// canvas is clean.
+    pub fn autosave(
+        &mut self,
+        canvas: &CanvasState,
+        current_file: Option<&str>,
+        dirty: bool,
+    ) -> anyhow::Result<()> {
+        if !dirty {
+            return self.remove();
+        }
+        if self.written_revision == Some(canvas.revision()) {
+            return Ok(());
+        }
+        if self
+            .last_write
+            .is_some_and(|last| last.elapsed() < AUTOSAVE_INTERVAL)
+        {
+            return Ok(());
+        }
+        self.write(canvas, current_file)
+    }
+
+    /// Write the canvas to the swap file right away
+    pub fn write(
+        &mut self,
+        canvas: &CanvasState,
+        current_file: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let Some(path) = swap_path(current_file) else {
+            return Ok(());
+        };
+
+        // The file was renamed (e.g. `:w other`), so the old swap is stale
+        if self.written.as_ref().is_some_and(|old| *old != path) {
+            self.remove()?;
+        }
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        canvas.save_to_file(&path)?;
+
+        self.written = Some(path);
+        self.written_revision = Some(canvas.revision());
+        self.last_write = Some(Instant::now());
+        Ok(())
+    }
+
+    /// Take over a swap file left by an earlier session (after recovering from it),
+    /// so it is cleaned up like our own
+    pub fn adopt(&mut self, path: PathBuf) {
+        self.written = Some(path);
+        self.written_revision = None;
+    }
+
+    /// Remove the swap file written by this session, if any
+    pub fn remove(&mut self) -> anyhow::Result<()> {
+        self.written_revision = None;
+        if let Some(path) = self.written.take()
+            && path.exists()
+        {
+            std::fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for SwapState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Swap file for the given diagram file, or for an unnamed diagram if there is none
+pub fn swap_path(current_file: Option<&str>) -> Option<PathBuf> {
+    match current_file {
+        Some(file) => {
+            let path = Path::new(file);
+            let name = path.file_name()?.to_string_lossy();
+            Some(path.with_file_name(format!(".{}.swp", name)))
+        }
+        None => Some(state_dir()?.join(unnamed_swap_name(std::process::id()))),
+    }
+}
+
+pub(super) fn unnamed_swap_name(pid: u32) -> String {
+    format!("{}{}{}", UNNAMED_SWAP_PREFIX, pid, UNNAMED_SWAP_EXTENSION)
+}
+
+/// Swap file left behind by an earlier session that is newer than the diagram file
+/// (or whose diagram file doesn't exist), i.e. one worth offering to recover
+pub fn find_recoverable(current_file: Option<&str>) -> Option<PathBuf> {
+    let Some(current_file) = current_file else {
+        return find_unnamed_swap(&state_dir()?);
+    };
+    let swap = swap_path(Some(current_file))?;
+    let swap_modified = swap.metadata().and_then(|m| m.modified()).ok()?;
+
+    let file_modified = Path::new(current_file)
+        .metadata()
+        .and_then(|m| m.modified())
+        .ok();
+    match file_modified {
+        Some(file_modified) if file_modified >= swap_modified => None,
+        _ => Some(swap),
+    }
+}
+
+/// The newest unnamed swap file in `dir` whose session has ended. Swaps of sessions
+/// still running (including this one) are theirs to keep.
+pub(super) fn find_unnamed_swap(dir: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            let name = entry.file_name();
+            let pid = name
+                .to_str()
+                .and_then(|name| name.strip_prefix(UNNAMED_SWAP_PREFIX))
+                .and_then(|rest| rest.strip_suffix(UNNAMED_SWAP_EXTENSION))
+                .and_then(|pid| pid.parse().ok());
+            pid.is_some_and(|pid| pid != std::process::id() && !is_running(pid))
+        })
+        .filter_map(|entry| {
+            let modified = entry.metadata().and_then(|m| m.modified()).ok()?;
+            Some((modified, entry.path()))
+        })
+        .max()
+        .map(|(_, path)| path)
+}
+
+/// Whether a process is still running. Only known where `/proc` exists; elsewhere any
+/// other session's swap counts as left behind.
+fn is_running(pid: u32) -> bool {
+    Path::new("/proc").join(pid.to_string()).exists()
+}
+
+/// `$XDG_STATE_HOME/textdraw`, falling back to `~/.local/state/textdraw`
+pub(super) fn state_dir() -> Option<PathBuf> {
+    let base = match std::env::var_os("XDG_STATE_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".local/state"),
+    };
+    Some(base.join("textdraw"))
+}
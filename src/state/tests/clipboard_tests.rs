@@ -0,0 +1,50 @@
+use super::fixtures::*;
+use crate::state::AppState;
+
+fn state_with_selected_rectangle() -> (AppState, usize) {
+    let mut state = AppState::new();
+    let id = add_rectangle(&mut state, 2, 3, 4, 2);
+    state.selection_state.selected_ids = vec![id];
+    (state, id)
+}
+
+#[test]
+fn test_paste_at_cursor_with_fresh_id() {
+    let (mut state, id) = state_with_selected_rectangle();
+    state.copy_selection();
+    state.update_cursor(10, 20);
+    state.paste();
+
+    assert_eq!(state.canvas.elements().len(), 2);
+    let pasted_id = state.get_selected_element_ids()[0];
+    assert_ne!(pasted_id, id);
+
+    let pasted = state.canvas.get_element(pasted_id).unwrap();
+    assert_eq!(pasted.name(), format!("Rectangle {}", pasted_id + 1));
+    assert_eq!((pasted.bounds().min.x, pasted.bounds().min.y), (10, 20));
+}
+
+#[test]
+fn test_cut_removes_and_paste_restores() {
+    let (mut state, _) = state_with_selected_rectangle();
+    state.cut_selection();
+    assert!(state.canvas.is_empty());
+
+    state.paste();
+    assert_eq!(state.canvas.elements().len(), 1);
+}
+
+#[test]
+fn test_duplicate_offsets_copy() {
+    let (mut state, id) = state_with_selected_rectangle();
+    state.duplicate_selection();
+
+    assert_eq!(state.canvas.elements().len(), 2);
+    assert!(state.clipboard.is_empty());
+
+    let duplicate_id = state.get_selected_element_ids()[0];
+    let original = state.canvas.get_element(id).unwrap().bounds();
+    let duplicate = state.canvas.get_element(duplicate_id).unwrap().bounds();
+    assert_eq!(duplicate.min.x, original.min.x + 2);
+    assert_eq!(duplicate.min.y, original.min.y + 1);
+}
@@ -0,0 +1,279 @@
+use super::fixtures::*;
+use crate::elements::PropertyValue;
+use crate::export::ExportFormat;
+use crate::state::command::{Alignment, CommandAction, CommandState, SelectFilter};
+use crate::state::{AppState, ConfirmAction};
+use assert_fs::TempDir;
+
+fn parse(command: &str) -> CommandAction {
+    let mut state = CommandState::new();
+    state.enter_with(command);
+    state.parse()
+}
+
+/// Run a command as if typed into command mode, returning whether the app should quit
+fn run(state: &mut AppState, command: &str) -> bool {
+    state.enter_command_mode_with(command);
+    state.execute_command()
+}
+
+#[test]
+fn test_parse_quit_commands() {
+    assert_eq!(parse("q"), CommandAction::Quit);
+    assert_eq!(parse("q!"), CommandAction::ForceQuit);
+    assert_eq!(parse("wq"), CommandAction::SaveAndQuit(None));
+    assert_eq!(
+        parse("wq out"),
+        CommandAction::SaveAndQuit(Some("out.textdraw".to_string()))
+    );
+    assert_eq!(parse("x"), CommandAction::Exit(None));
+    assert_eq!(
+        parse("e! in.textdraw"),
+        CommandAction::ForceOpen("in.textdraw".to_string())
+    );
+}
+
+#[test]
+fn test_edits_mark_dirty_until_saved() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("diagram.textdraw");
+    let mut state = AppState::new();
+    assert!(!state.file.is_dirty());
+
+    add_rectangle(&mut state, 0, 0, 4, 2);
+    state.commit_history();
+    assert!(state.file.is_dirty());
+
+    run(&mut state, &format!("w {}", path.display()));
+    assert!(!state.file.is_dirty());
+
+    state.undo();
+    assert!(state.file.is_dirty());
+}
+
+#[test]
+fn test_quit_with_unsaved_changes_asks_first() {
+    let mut state = AppState::new();
+    assert!(run(&mut state, "q"), "nothing to lose, quits right away");
+
+    add_rectangle(&mut state, 0, 0, 4, 2);
+    state.commit_history();
+    assert!(!run(&mut state, "q"));
+    assert_eq!(state.confirm, Some(ConfirmAction::Quit));
+
+    state.cancel_pending_action();
+    assert!(run(&mut state, "q!"));
+}
+
+#[test]
+fn test_open_with_unsaved_changes_asks_first() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("empty.textdraw").display().to_string();
+    let mut state = AppState::new();
+    run(&mut state, &format!("w {}", path));
+    add_rectangle(&mut state, 0, 0, 4, 2);
+    state.commit_history();
+
+    run(&mut state, &format!("e {}", path));
+    assert_eq!(state.confirm, Some(ConfirmAction::Open(path)));
+    assert_eq!(state.canvas.elements().len(), 1);
+
+    assert!(!state.confirm_pending_action());
+    assert!(state.canvas.is_empty());
+    assert!(!state.file.is_dirty());
+}
+
+#[test]
+fn test_exit_saves_only_when_dirty() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("diagram.textdraw");
+    let mut state = AppState::new();
+
+    assert!(
+        run(&mut state, "x"),
+        "clean canvas quits without a filename"
+    );
+    assert!(!path.exists());
+
+    add_rectangle(&mut state, 0, 0, 4, 2);
+    state.commit_history();
+    assert!(!run(&mut state, "x"), "no filename to save to");
+    assert!(run(&mut state, &format!("x {}", path.display())));
+    assert!(path.exists());
+}
+
+#[test]
+fn test_parse_editing_commands() {
+    assert_eq!(parse("new"), CommandAction::New);
+    assert_eq!(
+        parse("export SVG out.svg"),
+        CommandAction::Export(ExportFormat::Svg, "out.svg".to_string())
+    );
+    assert_eq!(parse("goto 10 -4"), CommandAction::Goto(10, -4));
+    assert_eq!(
+        parse("select type=Rectangle"),
+        CommandAction::Select(SelectFilter::Type("Rectangle".to_string()))
+    );
+    assert_eq!(
+        parse("set border_style = Double"),
+        CommandAction::Set("border_style".to_string(), "Double".to_string())
+    );
+    assert_eq!(
+        parse("rename Load balancer"),
+        CommandAction::Rename("Load balancer".to_string())
+    );
+    assert_eq!(parse("align top"), CommandAction::Align(Alignment::Top));
+
+    assert_eq!(
+        parse("goto 10"),
+        CommandAction::Message("Usage: :goto <x> <y>".to_string())
+    );
+    assert_eq!(
+        parse("export png out.png"),
+        CommandAction::Message("Unknown export format: png".to_string())
+    );
+}
+
+#[test]
+fn test_select_set_and_rename() {
+    let mut state = AppState::new();
+    let first = add_rectangle(&mut state, 0, 0, 4, 2);
+    state.commit_history();
+    add_rectangle(&mut state, 10, 0, 4, 2);
+    state.commit_history();
+
+    run(&mut state, "select type=rectangle");
+    assert_eq!(state.get_selected_element_ids().len(), 2);
+
+    run(&mut state, "set border_style=double");
+    for element in state.canvas.elements() {
+        assert_eq!(
+            element.get_property("border_style"),
+            Some(PropertyValue::Choice("Double".to_string()))
+        );
+    }
+
+    run(&mut state, "set width=0");
+    assert_eq!(
+        state.file.status_message.as_deref(),
+        Some("Error: 0 is out of range (1 to 200)")
+    );
+
+    run(&mut state, "rename Server");
+    assert_eq!(
+        state.file.status_message.as_deref(),
+        Some("Error: Select one element to rename")
+    );
+
+    run(&mut state, &format!("select name=Rectangle {}", first + 1));
+    run(&mut state, "rename Server");
+    assert_eq!(state.canvas.get_element(first).unwrap().name(), "Server");
+
+    run(&mut state, "delete");
+    assert_eq!(state.canvas.elements().len(), 1);
+}
+
+#[test]
+fn test_align_selected_elements() {
+    let mut state = AppState::new();
+    let narrow = add_rectangle(&mut state, 3, 0, 4, 2);
+    state.commit_history();
+    let wide = add_rectangle(&mut state, 0, 5, 10, 2);
+    state.commit_history();
+    run(&mut state, "select all");
+
+    run(&mut state, "align right");
+    let right = |state: &AppState, id| state.canvas.get_element(id).unwrap().bounds().max.x;
+    assert_eq!(right(&state, narrow), right(&state, wide));
+
+    run(&mut state, "align left");
+    let left = |state: &AppState, id| state.canvas.get_element(id).unwrap().bounds().min.x;
+    assert_eq!(left(&state, narrow), 0);
+    assert_eq!(left(&state, wide), 0);
+}
+
+#[test]
+fn test_new_with_unsaved_changes_asks_first() {
+    let mut state = AppState::new();
+    add_rectangle(&mut state, 0, 0, 4, 2);
+    state.commit_history();
+
+    run(&mut state, "new");
+    assert_eq!(state.confirm, Some(ConfirmAction::New));
+    assert!(!state.confirm_pending_action());
+    assert!(state.canvas.is_empty());
+    assert!(!state.file.is_dirty());
+}
+
+#[test]
+fn test_export_command_writes_file() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("out.svg");
+    let mut state = AppState::new();
+    add_rectangle(&mut state, 0, 0, 4, 2);
+    state.commit_history();
+
+    run(&mut state, &format!("export svg {}", path.display()));
+    assert!(std::fs::read_to_string(&path).unwrap().starts_with("<svg"));
+}
+
+#[test]
+fn test_history_is_recalled_and_persisted() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("history");
+    let mut command = CommandState::new();
+    command.load_history_from(path.clone());
+
+    for typed in ["goto 1 2", "select all", "select all"] {
+        command.enter_with(typed);
+        command.finish();
+    }
+    assert_eq!(command.history, ["goto 1 2", "select all"]);
+
+    command.enter_with("al");
+    command.history_prev();
+    assert_eq!(command.buffer, "select all");
+    command.history_prev();
+    command.history_prev();
+    assert_eq!(command.buffer, "goto 1 2");
+    command.history_next();
+    command.history_next();
+    assert_eq!(command.buffer, "al", "back to what was typed");
+
+    let mut next_session = CommandState::new();
+    next_session.load_history_from(path);
+    assert_eq!(next_session.history, ["goto 1 2", "select all"]);
+}
+
+#[test]
+fn test_tab_completes_commands_and_files() {
+    let mut command = CommandState::new();
+    command.enter_with("ex");
+    command.complete();
+    assert_eq!(command.buffer, "exit ");
+    command.complete();
+    assert_eq!(command.buffer, "export ", "Tab again cycles");
+
+    command.enter_with("ren");
+    command.complete();
+    assert_eq!(command.buffer, "rename ");
+    assert!(command.completion.is_none());
+
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("diagram.textdraw"), "").unwrap();
+    std::fs::write(dir.path().join("notes.txt"), "").unwrap();
+    std::fs::create_dir(dir.path().join("docs")).unwrap();
+
+    command.enter_with(&format!("e {}/", dir.path().display()));
+    command.complete();
+    assert_eq!(
+        command.buffer,
+        format!("e {}/diagram.textdraw", dir.path().display())
+    );
+    command.complete();
+    assert_eq!(command.buffer, format!("e {}/docs/", dir.path().display()));
+    assert_eq!(
+        command.completion.as_ref().unwrap().candidates,
+        ["diagram.textdraw", "docs/"]
+    );
+}
@@ -0,0 +1,130 @@
+use super::fixtures::*;
+use crate::elements::{ArrowElement, Element, Segment, route_around};
+use crate::state::AppState;
+use crate::types::{Bounds, Coord, Direction};
+
+/// Two 4x2 boxes at (0,0) and (10,0) joined by an arrow between their facing sides.
+/// Returns (state, left box, right box, arrow).
+fn state_with_connected_boxes() -> (AppState, usize, usize, usize) {
+    let mut state = AppState::new();
+    let left = add_rectangle(&mut state, 0, 0, 4, 2);
+    let right = add_rectangle(&mut state, 10, 0, 4, 2);
+
+    let id = state.canvas.get_next_id();
+    let segment = Segment::from_coords(Coord { x: 5, y: 1 }, Coord { x: 9, y: 1 });
+    state
+        .canvas
+        .add_element(Element::Arrow(ArrowElement::new(id, vec![segment])));
+    state.canvas.attach_connector(id);
+
+    (state, left, right, id)
+}
+
+fn arrow(state: &AppState, id: usize) -> &ArrowElement {
+    match state.canvas.get_element(id) {
+        Some(Element::Arrow(arrow)) => arrow,
+        other => panic!("expected an arrow, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_connector_attaches_to_adjacent_sides() {
+    let (state, left, right, id) = state_with_connected_boxes();
+
+    let arrow = arrow(&state, id);
+    assert_eq!(arrow.start_attachment.map(|a| a.element_id), Some(left));
+    assert_eq!(arrow.end_attachment.map(|a| a.element_id), Some(right));
+}
+
+#[test]
+fn test_moving_target_reroutes_connector() {
+    let (mut state, _, right, id) = state_with_connected_boxes();
+    state.selection_state.selected_ids = vec![right];
+
+    state.move_selected_elements(0, 4);
+
+    let arrow = arrow(&state, id);
+    let points: Vec<(i32, i32)> = std::iter::once(arrow.segments[0].start)
+        .chain(arrow.segments.iter().map(Segment::end))
+        .map(|c| (c.x, c.y))
+        .collect();
+    assert_eq!(points, vec![(5, 1), (7, 1), (7, 5), (9, 5)]);
+    assert_eq!(arrow.head_end, '▶');
+}
+
+#[test]
+fn test_moving_connector_alone_detaches_it() {
+    let (mut state, _, _, id) = state_with_connected_boxes();
+    state.selection_state.selected_ids = vec![id];
+
+    state.move_selected_elements(0, 3);
+
+    let arrow = arrow(&state, id);
+    assert_eq!(arrow.start_attachment, None);
+    assert_eq!(arrow.end_attachment, None);
+    assert_eq!(arrow.segments[0].start, Coord { x: 5, y: 4 });
+}
+
+#[test]
+fn test_deleting_target_detaches_connector() {
+    let (mut state, left, right, id) = state_with_connected_boxes();
+    state.selection_state.selected_ids = vec![right];
+
+    state.delete_selected_elements();
+
+    let arrow = arrow(&state, id);
+    assert_eq!(arrow.start_attachment.map(|a| a.element_id), Some(left));
+    assert_eq!(arrow.end_attachment, None);
+    assert_eq!(arrow.segments[0].direction, Direction::Right);
+}
+
+#[test]
+fn test_duplicate_keeps_connector_attached_to_copies() {
+    let (mut state, left, right, id) = state_with_connected_boxes();
+    state.selection_state.selected_ids = vec![left, right, id];
+
+    state.duplicate_selection();
+
+    let copies = state.get_selected_element_ids().to_vec();
+    let copied_arrow = arrow(&state, copies[2]);
+    assert_eq!(
+        copied_arrow.start_attachment.map(|a| a.element_id),
+        Some(copies[0])
+    );
+    assert_eq!(
+        copied_arrow.end_attachment.map(|a| a.element_id),
+        Some(copies[1])
+    );
+}
+
+fn corners(segments: &[Segment]) -> Vec<(i32, i32)> {
+    std::iter::once(segments[0].start)
+        .chain(segments.iter().map(Segment::end))
+        .map(|c| (c.x, c.y))
+        .collect()
+}
+
+#[test]
+fn test_route_around_goes_straight_when_clear() {
+    let segments = route_around(Coord { x: 0, y: 2 }, Coord { x: 9, y: 2 }, &[]).unwrap();
+    assert_eq!(corners(&segments), vec![(0, 2), (9, 2)]);
+}
+
+#[test]
+fn test_route_around_avoids_obstacle_with_two_bends() {
+    // A box sitting across the straight path from (0,2) to (12,2)
+    let obstacle = Bounds {
+        min: Coord { x: 4, y: 0 },
+        max: Coord { x: 8, y: 4 },
+    };
+    let segments = route_around(Coord { x: 0, y: 2 }, Coord { x: 12, y: 2 }, &[obstacle]).unwrap();
+
+    // Around the top or bottom of the box: down/up, across, and back
+    let points = corners(&segments);
+    assert_eq!(points.len(), 4, "expected two bends: {:?}", points);
+    for segment in &segments {
+        let b = segment.bounds();
+        let overlaps = b.min.x <= 8 && b.max.x >= 4 && b.min.y <= 4 && b.max.y >= 0;
+        assert!(!overlaps, "segment crosses the obstacle: {:?}", segment);
+    }
+}
@@ -0,0 +1,48 @@
+//! Helpers shared by the state tests
+
+use crate::elements::{Element, RectangleElement, TextElement};
+use crate::events::KeyEvent;
+use crate::state::AppState;
+use crate::types::Coord;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// Add a rectangle to the canvas and return its ID
+pub fn add_rectangle(state: &mut AppState, x: i32, y: i32, width: u16, height: u16) -> usize {
+    let id = state.canvas.get_next_id();
+    let rect = RectangleElement::new(id, Coord { x, y }, width, height);
+    state.canvas.add_element(Element::Rectangle(rect))
+}
+
+/// Add a text element to the canvas and return its ID
+pub fn add_text(state: &mut AppState, x: i32, y: i32, text: &str) -> usize {
+    let id = state.canvas.get_next_id();
+    let element = TextElement::new(id, Coord { x, y }, text.to_string());
+    state.canvas.add_element(Element::Text(element))
+}
+
+/// Coordinates from (x, y) pairs
+pub fn points(coords: &[(i32, i32)]) -> Vec<Coord> {
+    coords.iter().map(|&(x, y)| Coord { x, y }).collect()
+}
+
+/// Key press without modifiers
+pub fn key(code: KeyCode) -> KeyEvent {
+    KeyEvent {
+        code,
+        modifiers: KeyModifiers::NONE,
+    }
+}
+
+/// Character drawn on the canvas at (x, y)
+pub fn char_at(state: &AppState, x: i32, y: i32) -> Option<char> {
+    state.canvas.build_render_map().get(&(x, y)).copied()
+}
+
+/// Character a single element draws at (x, y)
+pub fn element_char_at(element: &Element, x: i32, y: i32) -> Option<char> {
+    element
+        .render_points()
+        .into_iter()
+        .find(|&(px, py, _)| (px, py) == (x, y))
+        .map(|(_, _, ch)| ch)
+}
@@ -0,0 +1,201 @@
+use super::fixtures::*;
+use crate::state::{AppState, Member};
+use assert_fs::TempDir;
+
+/// Three rectangles side by side, at x = 0, 10 and 20
+fn state_with_rectangles() -> (AppState, Vec<usize>) {
+    let mut state = AppState::new();
+    let ids = [0, 10, 20]
+        .into_iter()
+        .map(|x| add_rectangle(&mut state, x, 0, 4, 2))
+        .collect();
+    state.commit_history();
+    (state, ids)
+}
+
+fn click(state: &mut AppState, x: i32, y: i32) {
+    state.start_selection(x, y);
+    state.finish_selection(x, y);
+}
+
+fn selected(state: &AppState) -> Vec<usize> {
+    let mut ids = state.selection_state.selected_ids.clone();
+    ids.sort();
+    ids
+}
+
+#[test]
+fn test_click_selects_whole_group() {
+    let (mut state, ids) = state_with_rectangles();
+    state.select_elements(vec![ids[0], ids[1]]);
+    assert!(state.group_selected());
+
+    click(&mut state, 10, 0);
+    assert_eq!(selected(&state), vec![ids[0], ids[1]]);
+
+    click(&mut state, 20, 0);
+    assert_eq!(selected(&state), vec![ids[2]]);
+}
+
+#[test]
+fn test_group_needs_two_units() {
+    let (mut state, ids) = state_with_rectangles();
+    state.select_elements(vec![ids[0]]);
+    assert!(!state.group_selected());
+    assert!(state.canvas.groups().is_empty());
+}
+
+#[test]
+fn test_enter_and_exit_nested_groups() {
+    let (mut state, ids) = state_with_rectangles();
+    state.select_elements(vec![ids[0], ids[1]]);
+    state.group_selected();
+    state.select_elements(ids.clone());
+    state.group_selected();
+    assert_eq!(state.canvas.groups().len(), 2);
+
+    // The outer group is picked first, then the inner group, then the element
+    click(&mut state, 0, 0);
+    assert_eq!(selected(&state), ids);
+    assert!(state.enter_selected_group());
+    click(&mut state, 0, 0);
+    assert_eq!(selected(&state), vec![ids[0], ids[1]]);
+    assert!(state.enter_selected_group());
+    click(&mut state, 0, 0);
+    assert_eq!(selected(&state), vec![ids[0]]);
+
+    // Exiting selects the group that was being edited
+    assert!(state.exit_group());
+    assert_eq!(selected(&state), vec![ids[0], ids[1]]);
+    assert!(state.exit_group());
+    assert_eq!(selected(&state), ids);
+    assert!(!state.exit_group());
+}
+
+#[test]
+fn test_click_outside_group_leaves_it() {
+    let (mut state, ids) = state_with_rectangles();
+    state.select_elements(vec![ids[0], ids[1]]);
+    state.group_selected();
+    state.enter_selected_group();
+    assert!(state.group_scope().is_some());
+
+    click(&mut state, 20, 0);
+    assert_eq!(state.group_scope(), None);
+    assert_eq!(selected(&state), vec![ids[2]]);
+}
+
+#[test]
+fn test_ungroup_keeps_nesting() {
+    let (mut state, ids) = state_with_rectangles();
+    state.select_elements(vec![ids[0], ids[1]]);
+    state.group_selected();
+    let inner = state.canvas.groups()[0].id;
+    state.select_elements(ids.clone());
+    state.group_selected();
+    let outer = state.canvas.groups()[1].id;
+
+    // The inner group's elements move up into the outer group
+    state.enter_selected_group();
+    state.select_elements(vec![ids[0]]);
+    assert!(state.ungroup_selected());
+    assert!(state.canvas.get_group(inner).is_none());
+    assert_eq!(
+        state.canvas.get_group(outer).unwrap().members,
+        vec![
+            Member::Element(ids[0]),
+            Member::Element(ids[1]),
+            Member::Element(ids[2])
+        ]
+    );
+
+    // Nothing left to ungroup when a plain element is selected
+    assert!(!state.ungroup_selected());
+}
+
+#[test]
+fn test_deleting_members_removes_empty_groups() {
+    let (mut state, ids) = state_with_rectangles();
+    state.select_elements(vec![ids[0], ids[1]]);
+    state.group_selected();
+    state.select_elements(ids.clone());
+    state.group_selected();
+
+    state.select_elements(vec![ids[0], ids[1]]);
+    state.delete_selected_elements();
+    assert_eq!(state.canvas.groups().len(), 1);
+    assert_eq!(
+        state.canvas.groups()[0].members,
+        vec![Member::Element(ids[2])]
+    );
+}
+
+#[test]
+fn test_undo_grouping() {
+    let (mut state, ids) = state_with_rectangles();
+    state.select_elements(vec![ids[0], ids[1]]);
+    state.group_selected();
+    state.commit_history();
+    assert_eq!(state.canvas.groups().len(), 1);
+
+    state.undo();
+    assert!(state.canvas.groups().is_empty());
+    state.redo();
+    assert_eq!(state.canvas.groups().len(), 1);
+}
+
+#[test]
+fn test_groups_survive_save_and_load() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("diagram.textdraw");
+    let (mut state, ids) = state_with_rectangles();
+    state.select_elements(vec![ids[0], ids[1]]);
+    state.group_selected();
+    state.select_elements(ids.clone());
+    state.group_selected();
+    state.canvas.save_to_file(&path).unwrap();
+
+    let mut loaded = AppState::new();
+    loaded.load_from_file_silent(&path).unwrap();
+    let groups = loaded.canvas.groups();
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[1].members[0], Member::Group(groups[0].id));
+    assert_eq!(groups[1].name, state.canvas.groups()[1].name);
+
+    // New elements don't reuse the groups' IDs
+    let outer = groups[1].id;
+    assert!(loaded.canvas.get_next_id() > outer);
+}
+
+#[test]
+fn test_copies_of_a_group_stay_grouped() {
+    let (mut state, ids) = state_with_rectangles();
+    state.select_elements(vec![ids[0], ids[1]]);
+    state.group_selected();
+
+    state.duplicate_selection();
+    let duplicates = selected(&state);
+    assert_eq!(duplicates.len(), 2);
+    assert!(duplicates.iter().all(|id| !ids.contains(id)));
+    assert_eq!(state.canvas.groups().len(), 2);
+
+    // Clicking one copy selects both, not the original group
+    let x = state
+        .canvas
+        .get_element(duplicates[1])
+        .unwrap()
+        .bounds()
+        .min
+        .x;
+    click(&mut state, x, 1);
+    assert_eq!(selected(&state), duplicates);
+
+    state.copy_selection();
+    state.update_cursor(0, 20);
+    state.paste();
+    let pasted = selected(&state);
+    assert_eq!(state.canvas.groups().len(), 3);
+    let group = state.canvas.unit_of(pasted[0], None).unwrap();
+    assert_eq!(state.canvas.member_elements(group).len(), 2);
+    assert!(matches!(group, Member::Group(_)));
+}
@@ -0,0 +1,95 @@
+use super::fixtures::*;
+use crate::state::AppState;
+
+fn rectangle_x(state: &AppState, id: usize) -> i32 {
+    state.canvas.get_element(id).unwrap().bounds().min.x
+}
+
+#[test]
+fn test_undo_redo_add_element() {
+    let mut state = AppState::new();
+    add_rectangle(&mut state, 0, 0, 4, 2);
+    state.commit_history();
+    assert_eq!(state.canvas.elements().len(), 1);
+
+    state.undo();
+    assert!(state.canvas.is_empty());
+
+    state.redo();
+    assert_eq!(state.canvas.elements().len(), 1);
+}
+
+#[test]
+fn test_undo_with_empty_history_is_noop() {
+    let mut state = AppState::new();
+    state.undo();
+    state.redo();
+    assert!(state.canvas.is_empty());
+    assert!(!state.history.can_undo());
+}
+
+#[test]
+fn test_consecutive_nudges_coalesce() {
+    let mut state = AppState::new();
+    let id = add_rectangle(&mut state, 5, 5, 4, 2);
+    state.commit_history();
+    state.selection_state.selected_ids = vec![id];
+
+    for _ in 0..3 {
+        state.move_selected_elements(1, 0);
+        state.commit_history();
+    }
+    assert_eq!(rectangle_x(&state, id), 8);
+
+    // All three nudges are undone together
+    state.undo();
+    assert_eq!(rectangle_x(&state, id), 5);
+
+    // Next undo removes the rectangle itself
+    state.undo();
+    assert!(state.canvas.is_empty());
+}
+
+#[test]
+fn test_new_edit_clears_redo() {
+    let mut state = AppState::new();
+    add_rectangle(&mut state, 0, 0, 4, 2);
+    state.commit_history();
+    state.undo();
+    assert!(state.history.can_redo());
+
+    add_rectangle(&mut state, 10, 10, 4, 2);
+    state.commit_history();
+    assert!(!state.history.can_redo());
+}
+
+#[test]
+fn test_undo_prunes_selection() {
+    let mut state = AppState::new();
+    let id = add_rectangle(&mut state, 0, 0, 4, 2);
+    state.commit_history();
+    state.selection_state.selected_ids = vec![id];
+
+    state.undo();
+    assert!(state.get_selected_element_ids().is_empty());
+}
+
+#[test]
+fn test_edit_that_changes_nothing_is_not_recorded() {
+    let mut state = AppState::new();
+    let id = add_rectangle(&mut state, 0, 0, 4, 2);
+    state.commit_history();
+    state.file.dirty = false;
+    let name = state.canvas.get_element(id).unwrap().name().to_string();
+
+    state.rename_element(id, &name).unwrap();
+    state.commit_history();
+    state.select_elements(vec![id]);
+    assert!(state.set_selected_property("width", "0").is_err());
+    state.commit_history();
+
+    assert!(!state.file.is_dirty());
+    // Only the rectangle itself can be undone
+    state.undo();
+    assert!(state.canvas.is_empty());
+}
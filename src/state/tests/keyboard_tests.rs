@@ -0,0 +1,110 @@
+use crate::components::CanvasComponent;
+use crate::elements::Element;
+use crate::events::{EventHandler, EventResult, KeyEvent};
+use crate::state::AppState;
+use crate::tools::Tool;
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::layout::Rect;
+
+fn press(canvas: &mut CanvasComponent, state: &mut AppState, code: KeyCode) -> EventResult {
+    let key_event = KeyEvent {
+        code,
+        modifiers: KeyModifiers::NONE,
+    };
+    canvas.handle_key_event(state, &key_event)
+}
+
+fn press_n(canvas: &mut CanvasComponent, state: &mut AppState, code: KeyCode, n: usize) {
+    for _ in 0..n {
+        press(canvas, state, code);
+    }
+}
+
+#[test]
+fn test_draw_rectangle_with_keyboard() {
+    let mut state = AppState::new();
+    let mut canvas = CanvasComponent::new();
+    state.layout.canvas = Rect::new(0, 0, 40, 20);
+    state.select_tool(Tool::Rectangle);
+
+    press(&mut canvas, &mut state, KeyCode::Enter);
+    assert!(state.is_drawing());
+    press_n(&mut canvas, &mut state, KeyCode::Char('l'), 4);
+    press_n(&mut canvas, &mut state, KeyCode::Char('j'), 3);
+    press(&mut canvas, &mut state, KeyCode::Enter);
+
+    let Some(Element::Rectangle(rect)) = state.canvas.elements().first() else {
+        panic!("expected a rectangle");
+    };
+    assert_eq!((rect.start.x, rect.start.y), (0, 0));
+    assert_eq!((rect.width, rect.height), (4, 3));
+    // Back to Select once the rectangle is done
+    assert_eq!(state.tool.selected_tool, Tool::Select);
+}
+
+#[test]
+fn test_draw_line_with_bend_from_keyboard() {
+    let mut state = AppState::new();
+    let mut canvas = CanvasComponent::new();
+    state.layout.canvas = Rect::new(0, 0, 40, 20);
+    state.select_tool(Tool::Line);
+
+    press(&mut canvas, &mut state, KeyCode::Enter);
+    press_n(&mut canvas, &mut state, KeyCode::Right, 5);
+    press(&mut canvas, &mut state, KeyCode::Char(' '));
+    assert!(state.is_drawing(), "Space adds a bend and keeps drawing");
+    press_n(&mut canvas, &mut state, KeyCode::Down, 2);
+    press(&mut canvas, &mut state, KeyCode::Enter);
+
+    assert!(!state.is_drawing());
+    let Some(Element::Line(line)) = state.canvas.elements().first() else {
+        panic!("expected a line");
+    };
+    assert_eq!(line.segments.len(), 2);
+}
+
+#[test]
+fn test_global_keys_win_when_not_drawing() {
+    let mut state = AppState::new();
+    let mut canvas = CanvasComponent::new();
+    state.layout.canvas = Rect::new(0, 0, 40, 20);
+
+    // `l` selects the Line tool, so the canvas leaves it alone
+    let result = press(&mut canvas, &mut state, KeyCode::Char('l'));
+    assert_eq!(result, EventResult::Ignored);
+    assert_eq!((state.cursor_x, state.cursor_y), (0, 0));
+
+    let result = press(&mut canvas, &mut state, KeyCode::Char('j'));
+    assert_eq!(result, EventResult::Consumed);
+    assert_eq!((state.cursor_x, state.cursor_y), (0, 1));
+}
+
+#[test]
+fn test_select_element_under_cursor() {
+    let mut state = AppState::new();
+    let mut canvas = CanvasComponent::new();
+    state.layout.canvas = Rect::new(0, 0, 40, 20);
+    state.select_tool(Tool::Rectangle);
+    press(&mut canvas, &mut state, KeyCode::Enter);
+    press_n(&mut canvas, &mut state, KeyCode::Down, 3);
+    press_n(&mut canvas, &mut state, KeyCode::Right, 3);
+    press(&mut canvas, &mut state, KeyCode::Enter);
+
+    // Cursor is on the bottom-right corner, drawing switched back to Select
+    press(&mut canvas, &mut state, KeyCode::Enter);
+    assert_eq!(state.get_selected_element_ids().len(), 1);
+}
+
+#[test]
+fn test_move_cursor_scrolls_viewport() {
+    let mut state = AppState::new();
+    // 10x5 inside the border
+    state.layout.canvas = Rect::new(0, 0, 12, 7);
+
+    state.move_cursor(12, 0);
+    assert_eq!(state.viewport_x, 3);
+
+    state.move_cursor(-12, 6);
+    assert_eq!((state.viewport_x, state.viewport_y), (0, 2));
+    assert_eq!((state.cursor_x, state.cursor_y), (0, 6));
+}
@@ -0,0 +1,80 @@
+use super::fixtures::*;
+use crate::components::label_editor;
+use crate::elements::{Element, HasProperties, PropertyValue, RectangleElement};
+use crate::state::AppState;
+use crate::types::Coord;
+use crossterm::event::KeyCode;
+
+fn labelled_rectangle(label: &str, width: u16, height: u16) -> RectangleElement {
+    let mut rect = RectangleElement::new(0, Coord { x: 0, y: 0 }, width, height);
+    rect.set_property("label", PropertyValue::Text(label.to_string()))
+        .unwrap();
+    rect
+}
+
+fn placed_lines(rect: &RectangleElement) -> Vec<(i32, i32, String)> {
+    rect.label_lines()
+        .into_iter()
+        .map(|(pos, line)| (pos.x, pos.y, line.text))
+        .collect()
+}
+
+#[test]
+fn test_label_wraps_and_centers() {
+    // Inner width 10 minus 1 column of padding on each side leaves 8 columns
+    let rect = labelled_rectangle("load balancer", 11, 4);
+
+    assert_eq!(
+        placed_lines(&rect),
+        vec![(4, 1, "load".to_string()), (2, 2, "balancer".to_string())]
+    );
+}
+
+#[test]
+fn test_label_alignment() {
+    let mut rect = labelled_rectangle("db", 9, 4);
+    rect.set_property("label_align", PropertyValue::Choice("Right".to_string()))
+        .unwrap();
+    rect.set_property("label_valign", PropertyValue::Choice("Bottom".to_string()))
+        .unwrap();
+
+    assert_eq!(placed_lines(&rect), vec![(6, 3, "db".to_string())]);
+}
+
+#[test]
+fn test_auto_grow_fits_label() {
+    let mut rect = labelled_rectangle("one two three four", 8, 2);
+    assert_eq!(placed_lines(&rect).len(), 1);
+
+    rect.set_property("auto_grow", PropertyValue::Boolean(true))
+        .unwrap();
+
+    assert_eq!(rect.height, 5);
+    assert_eq!(placed_lines(&rect).len(), 4);
+}
+
+#[test]
+fn test_typing_label_is_one_undo_step() {
+    let mut state = AppState::new();
+    let id = add_rectangle(&mut state, 0, 0, 10, 2);
+    state.commit_history();
+    state.selection_state.selected_ids = vec![id];
+
+    label_editor::try_enter_edit_label_mode(&mut state);
+    assert!(state.is_editing_label());
+    for c in "api".chars() {
+        label_editor::handle_edit_label_key(&mut state, &key(KeyCode::Char(c)));
+        state.commit_history();
+    }
+    label_editor::handle_edit_label_key(&mut state, &key(KeyCode::Enter));
+    assert!(!state.is_editing_label());
+
+    let label = |state: &AppState| match state.canvas.get_element(id) {
+        Some(Element::Rectangle(rect)) => rect.label.clone(),
+        other => panic!("expected a rectangle, got {:?}", other),
+    };
+    assert_eq!(label(&state), "api");
+
+    state.undo();
+    assert_eq!(label(&state), "");
+}
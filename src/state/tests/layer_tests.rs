@@ -0,0 +1,159 @@
+use super::fixtures::*;
+use crate::state::AppState;
+use assert_fs::TempDir;
+
+#[test]
+fn test_new_elements_go_on_the_active_layer() {
+    let mut state = AppState::new();
+    let base = state.canvas.active_layer();
+    let below = add_text(&mut state, 0, 0, "a");
+
+    let overlay = state.add_layer(Some(base));
+    let above = add_text(&mut state, 0, 0, "b");
+    assert_eq!(state.canvas.get_element(below).unwrap().layer(), base);
+    assert_eq!(state.canvas.get_element(above).unwrap().layer(), overlay);
+    assert_eq!(char_at(&state, 0, 0), Some('b'));
+
+    // Layers are drawn in their own order, whatever the order of the elements
+    state.move_layer(overlay, false);
+    assert_eq!(char_at(&state, 0, 0), Some('a'));
+    assert_eq!(state.canvas.find_element_at(0, 0), Some(below));
+}
+
+#[test]
+fn test_hidden_layer_is_not_drawn_or_selectable() {
+    let mut state = AppState::new();
+    let base = state.canvas.active_layer();
+    let id = add_text(&mut state, 0, 0, "note");
+    state.select_elements(vec![id]);
+
+    state.toggle_layer_visible(base);
+    assert_eq!(char_at(&state, 0, 0), None);
+    assert_eq!(state.canvas.find_element_at(0, 0), None);
+    assert!(state.selection_state.selected_ids.is_empty());
+
+    state.toggle_layer_visible(base);
+    assert_eq!(char_at(&state, 0, 0), Some('n'));
+}
+
+#[test]
+fn test_locked_layer_is_drawn_but_not_selectable() {
+    let mut state = AppState::new();
+    let base = state.canvas.active_layer();
+    add_rectangle(&mut state, 0, 0, 4, 2);
+    state.toggle_layer_locked(base);
+
+    assert_eq!(char_at(&state, 0, 0), Some('┌'));
+    assert_eq!(state.canvas.find_element_at(0, 0), None);
+    assert!(
+        state
+            .canvas
+            .find_elements_fully_inside_rect(-1, -1, 10, 10)
+            .is_empty()
+    );
+    assert_eq!(state.select_matching(&crate::state::SelectFilter::All), 0);
+}
+
+#[test]
+fn test_move_selection_to_another_layer() {
+    let mut state = AppState::new();
+    let base = state.canvas.active_layer();
+    let id = add_text(&mut state, 0, 0, "note");
+    let overlay = state.add_layer(Some(base));
+    state.toggle_layer_visible(overlay);
+
+    state.select_elements(vec![id]);
+    state.move_selected_to_layer(overlay);
+    assert_eq!(state.canvas.get_element(id).unwrap().layer(), overlay);
+    // Moved onto a hidden layer, so it is no longer selected
+    assert!(state.selection_state.selected_ids.is_empty());
+}
+
+#[test]
+fn test_layer_names_must_be_unique() {
+    let mut state = AppState::new();
+    let base = state.canvas.active_layer();
+    let overlay = state.add_layer(Some(base));
+
+    assert!(
+        state
+            .canvas
+            .rename_layer(overlay, " Notes ".to_string())
+            .is_ok()
+    );
+    assert_eq!(state.canvas.get_layer(overlay).unwrap().name, "Notes");
+    assert!(
+        state
+            .canvas
+            .rename_layer(base, "Notes".to_string())
+            .is_err()
+    );
+    assert!(state.canvas.rename_layer(base, "".to_string()).is_err());
+}
+
+#[test]
+fn test_layers_survive_save_and_load() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("diagram.textdraw");
+    let mut state = AppState::new();
+    let base = state.canvas.active_layer();
+    let overlay = state.add_layer(Some(base));
+    state
+        .canvas
+        .rename_layer(overlay, "Notes".to_string())
+        .unwrap();
+    let id = add_text(&mut state, 0, 0, "note");
+    state.toggle_layer_locked(overlay);
+    state.canvas.save_to_file(&path).unwrap();
+
+    let mut loaded = AppState::new();
+    loaded.load_from_file_silent(&path).unwrap();
+    let layers = loaded.canvas.layers();
+    assert_eq!(layers.len(), 2);
+    assert_eq!(layers[1].name, "Notes");
+    assert!(layers[1].locked);
+    assert_eq!(loaded.canvas.get_element(id).unwrap().layer(), overlay);
+}
+
+#[test]
+fn test_show_only_layers() {
+    let mut state = AppState::new();
+    let base = state.canvas.active_layer();
+    add_text(&mut state, 0, 0, "a");
+    let overlay = state.add_layer(Some(base));
+    add_text(&mut state, 0, 1, "b");
+    state.toggle_layer_visible(overlay);
+
+    // Hidden layers can still be picked explicitly
+    let notes = state.canvas.get_layer(overlay).unwrap().name.clone();
+    state.canvas.show_only_layers(&[notes]).unwrap();
+    assert_eq!(char_at(&state, 0, 0), None);
+    assert_eq!(char_at(&state, 0, 1), Some('b'));
+
+    let error = state
+        .canvas
+        .show_only_layers(&["Missing".to_string()])
+        .unwrap_err();
+    assert_eq!(error.to_string(), "Unknown layer: Missing");
+}
+
+#[test]
+fn test_group_members_on_locked_layer_are_not_selected() {
+    let mut state = AppState::new();
+    let base = state.canvas.active_layer();
+    let free = add_text(&mut state, 0, 0, "a");
+    let overlay = state.add_layer(Some(base));
+    let locked = add_text(&mut state, 10, 0, "b");
+    state.select_elements(vec![free, locked]);
+    assert!(state.group_selected());
+    state.toggle_layer_locked(overlay);
+
+    state.start_selection(0, 0);
+    state.finish_selection(0, 0);
+    assert_eq!(state.selection_state.selected_ids, vec![free]);
+
+    state.move_selected_elements(5, 0);
+    let start = |id| state.canvas.get_element(id).unwrap().bounds().min.x;
+    assert_eq!(start(free), 5);
+    assert_eq!(start(locked), 10);
+}
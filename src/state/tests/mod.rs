@@ -0,0 +1,16 @@
+mod clipboard_tests;
+mod command_tests;
+mod connector_tests;
+mod fixtures;
+mod group_tests;
+mod history_tests;
+mod keyboard_tests;
+mod label_tests;
+mod layer_tests;
+mod order_tests;
+mod outline_tests;
+mod properties_tests;
+mod selection_tests;
+mod swap_tests;
+mod vertex_tests;
+mod viewport_tests;
@@ -0,0 +1,73 @@
+use super::fixtures::*;
+use crate::elements::{Element, PropertyValue};
+use crate::state::AppState;
+use crate::types::ZOrder;
+
+/// Four overlapping rectangles, drawn in the order they were added
+fn state_with_stack() -> (AppState, Vec<usize>) {
+    let mut state = AppState::new();
+    let ids = (0..4)
+        .map(|i| add_rectangle(&mut state, i, i, 6, 4))
+        .collect();
+    (state, ids)
+}
+
+fn order(state: &AppState) -> Vec<usize> {
+    state.canvas.elements().iter().map(Element::id).collect()
+}
+
+#[test]
+fn test_raise_and_lower_one_step() {
+    let (mut state, ids) = state_with_stack();
+    state.selection_state.selected_ids = vec![ids[0], ids[1]];
+
+    state.reorder_selected(ZOrder::Raise);
+    assert_eq!(order(&state), vec![ids[2], ids[0], ids[1], ids[3]]);
+
+    state.reorder_selected(ZOrder::Lower);
+    assert_eq!(order(&state), vec![ids[0], ids[1], ids[2], ids[3]]);
+}
+
+#[test]
+fn test_bring_to_front_and_send_to_back() {
+    let (mut state, ids) = state_with_stack();
+    state.selection_state.selected_ids = vec![ids[2], ids[0]];
+
+    state.reorder_selected(ZOrder::Front);
+    assert_eq!(order(&state), vec![ids[1], ids[3], ids[0], ids[2]]);
+    assert_eq!(state.canvas.find_element_at(3, 3), Some(ids[2]));
+
+    state.reorder_selected(ZOrder::Back);
+    assert_eq!(order(&state), vec![ids[0], ids[2], ids[1], ids[3]]);
+    assert_eq!(state.canvas.find_element_at(3, 3), Some(ids[3]));
+}
+
+#[test]
+fn test_reorder_at_the_top_changes_nothing() {
+    let (mut state, ids) = state_with_stack();
+    let revision = state.canvas.revision();
+
+    assert!(!state.canvas.reorder(&[ids[3]], ZOrder::Raise));
+    assert!(!state.canvas.reorder(&[ids[3]], ZOrder::Front));
+    assert!(!state.canvas.reorder(&[ids[0]], ZOrder::Back));
+    assert_eq!(state.canvas.revision(), revision);
+}
+
+#[test]
+fn test_filled_rectangle_hides_what_is_below() {
+    let mut state = AppState::new();
+    add_text(&mut state, 2, 1, "hidden");
+    let id = add_rectangle(&mut state, 0, 0, 10, 2);
+
+    // Without a fill, the text shows through the box
+    assert_eq!(char_at(&state, 2, 1), Some('h'));
+
+    let rect = state.canvas.get_element_mut(id).unwrap();
+    rect.set_property("fill", PropertyValue::Boolean(true))
+        .unwrap();
+    assert_eq!(char_at(&state, 2, 1), Some(' '));
+
+    // Sent to the back, the box no longer covers the text
+    state.canvas.reorder(&[id], ZOrder::Back);
+    assert_eq!(char_at(&state, 2, 1), Some('h'));
+}
@@ -0,0 +1,60 @@
+use super::fixtures::*;
+use crate::elements::MAX_NAME_LENGTH;
+use crate::state::AppState;
+
+#[test]
+fn test_rename_element() {
+    let mut state = AppState::new();
+    let id = add_rectangle(&mut state, 0, 0, 8, 4);
+
+    state.rename_element(id, "  Server ").unwrap();
+    assert_eq!(state.canvas.get_element(id).unwrap().name(), "Server");
+
+    assert!(state.rename_element(id, "   ").is_err());
+    assert!(
+        state
+            .rename_element(id, &"x".repeat(MAX_NAME_LENGTH + 1))
+            .is_err()
+    );
+    assert_eq!(state.canvas.get_element(id).unwrap().name(), "Server");
+}
+
+#[test]
+fn test_select_and_jump_to_centers_the_view() {
+    let mut state = AppState::new();
+    let first = add_rectangle(&mut state, 0, 0, 8, 4);
+    let far = add_rectangle(&mut state, 200, 80, 8, 4);
+    state.select_elements(vec![first]);
+
+    state.select_and_jump_to(far);
+
+    assert_eq!(state.selection_state.selected_ids, vec![far]);
+    assert_eq!((state.cursor_x, state.cursor_y), (204, 82));
+    assert!(state.viewport_x > 0 && state.viewport_y > 0);
+}
+
+#[test]
+fn test_select_and_jump_to_locked_element_only_jumps() {
+    let mut state = AppState::new();
+    let id = add_rectangle(&mut state, 50, 50, 8, 4);
+    state.toggle_layer_locked(state.canvas.active_layer());
+
+    state.select_and_jump_to(id);
+
+    assert!(state.selection_state.selected_ids.is_empty());
+    assert_eq!((state.cursor_x, state.cursor_y), (54, 52));
+}
+
+#[test]
+fn test_toggle_element_selection() {
+    let mut state = AppState::new();
+    let a = add_rectangle(&mut state, 0, 0, 8, 4);
+    let b = add_rectangle(&mut state, 20, 0, 8, 4);
+
+    state.toggle_element_selection(a);
+    state.toggle_element_selection(b);
+    assert_eq!(state.selection_state.selected_ids, vec![a, b]);
+
+    state.toggle_element_selection(a);
+    assert_eq!(state.selection_state.selected_ids, vec![b]);
+}
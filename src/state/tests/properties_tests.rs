@@ -0,0 +1,127 @@
+use super::fixtures::*;
+use crate::elements::{ArrowElement, Element, LineElement, PropertyValue, Segment};
+use crate::state::AppState;
+
+fn choice(value: &str) -> PropertyValue {
+    PropertyValue::Choice(value.to_string())
+}
+
+#[test]
+fn test_arrow_head_styles_follow_the_path() {
+    // Right from (0,0), then down to (4,3)
+    let segments = Segment::path(&points(&[(0, 0), (4, 0), (4, 3)]));
+    let mut element = Element::Arrow(ArrowElement::new(0, segments));
+
+    element.set_property("head_end", choice("ASCII")).unwrap();
+    element
+        .set_property("head_start", choice("Diamond"))
+        .unwrap();
+    assert_eq!(element_char_at(&element, 4, 3), Some('v'));
+    assert_eq!(element_char_at(&element, 0, 0), Some('◆'));
+    assert_eq!(element.get_property("head_start"), Some(choice("Diamond")));
+
+    // Reshaping keeps the style and turns the head with the last segment
+    element.set_vertices(&points(&[(0, 0), (4, 0), (4, -3)]));
+    assert_eq!(element_char_at(&element, 4, -3), Some('^'));
+
+    element.set_property("head_end", choice("None")).unwrap();
+    assert_eq!(element_char_at(&element, 4, -3), Some('│'));
+    assert!(element.set_property("head_end", choice("Star")).is_err());
+}
+
+#[test]
+fn test_line_style_restyles_path() {
+    let segments = Segment::path(&points(&[(0, 0), (4, 0), (4, 3)]));
+    let mut element = Element::Line(LineElement::new(0, segments));
+
+    element
+        .set_property("line_style", choice("Double"))
+        .unwrap();
+    assert_eq!(element_char_at(&element, 2, 0), Some('═'));
+    assert_eq!(element_char_at(&element, 4, 0), Some('╗'));
+
+    // Dashed has no corners of its own
+    element
+        .set_property("line_style", choice("Dashed"))
+        .unwrap();
+    assert_eq!(element_char_at(&element, 2, 0), Some('╌'));
+    assert_eq!(element_char_at(&element, 4, 0), Some('┐'));
+    assert_eq!(element_char_at(&element, 4, 2), Some('╎'));
+}
+
+#[test]
+fn test_text_content_and_position() {
+    let mut state = AppState::new();
+    let id = add_text(&mut state, 2, 1, "hi");
+    state.select_elements(vec![id]);
+
+    state.set_selected_property("text", "héllo").unwrap();
+    state.set_selected_property("x", "5").unwrap();
+
+    let element = state.canvas.get_element(id).unwrap();
+    assert_eq!(element_char_at(element, 5, 1), Some('h'));
+    assert_eq!(element_char_at(element, 9, 1), Some('o'));
+    let bounds = element.bounds();
+    assert_eq!((bounds.min.x, bounds.max.x), (5, 10));
+
+    assert!(state.set_selected_property("text", "").is_err());
+}
+
+#[test]
+fn test_every_element_has_an_editable_name() {
+    let mut state = AppState::new();
+    let id = state.canvas.get_next_id();
+    let segments = Segment::path(&points(&[(0, 0), (4, 0)]));
+    state
+        .canvas
+        .add_element(Element::Line(LineElement::new(id, segments)));
+    state.select_elements(vec![id]);
+
+    let element = state.canvas.get_element(id).unwrap();
+    assert_eq!(element.properties_spec().all_fields()[0].name, "name");
+
+    state.set_selected_property("name", " uplink ").unwrap();
+    let element = state.canvas.get_element(id).unwrap();
+    assert_eq!(element.name(), "uplink");
+    assert!(state.set_selected_property("name", " ").is_err());
+}
+
+#[test]
+fn test_multi_selection_shares_common_properties() {
+    let mut state = AppState::new();
+    let mut ids = Vec::new();
+    for (x, style) in [(0, "Single"), (10, "Single"), (20, "Double")] {
+        let id = add_rectangle(&mut state, x, 0, 6, 3);
+        state
+            .canvas
+            .get_element_mut(id)
+            .unwrap()
+            .set_property("border_style", choice(style))
+            .unwrap();
+        ids.push(id);
+    }
+    let id = add_text(&mut state, 0, 5, "note");
+
+    // Rectangles only: everything but the name, with mixed values left open
+    state.select_elements(ids.clone());
+    let spec = state.selected_properties_spec().unwrap();
+    assert!(spec.get_field("name").is_none());
+    assert!(spec.get_field("border_style").is_some());
+    assert_eq!(state.selected_property_value("border_style"), None);
+    assert_eq!(
+        state.selected_property_value("width"),
+        Some(PropertyValue::Numeric(6))
+    );
+
+    state.apply_selected_property("border_style", choice("Rounded"));
+    assert_eq!(
+        state.selected_property_value("border_style"),
+        Some(choice("Rounded"))
+    );
+
+    // With the text, only the position is shared
+    state.select_elements(vec![ids[0], id]);
+    let spec = state.selected_properties_spec().unwrap();
+    let fields: Vec<_> = spec.all_fields().iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(fields, vec!["x", "y"]);
+}
@@ -0,0 +1,117 @@
+use super::fixtures::*;
+use crate::elements::{Element, RectangleElement, TableElement};
+use crate::state::AppState;
+use crate::types::{Coord, ResizeHandle, SelectionMode};
+
+fn select_only(state: &mut AppState, element: Element) -> usize {
+    let id = element.id();
+    state.canvas.add_element(element);
+    state.selection_state.selected_ids = vec![id];
+    state.selection_state.mode = SelectionMode::Selected;
+    id
+}
+
+#[test]
+fn test_move_selection_past_origin_keeps_layout() {
+    let mut state = AppState::new();
+    for (x, y) in [(0, 0), (5, 2)] {
+        let id = add_rectangle(&mut state, x, y, 2, 2);
+        state.selection_state.selected_ids.push(id);
+    }
+
+    state.move_selected_elements(-3, -1);
+
+    let starts: Vec<_> = state
+        .canvas
+        .elements()
+        .iter()
+        .map(|e| (e.bounds().min.x, e.bounds().min.y))
+        .collect();
+    assert_eq!(starts, vec![(-3, -1), (2, 1)]);
+}
+
+#[test]
+fn test_resize_rectangle_with_handles() {
+    let mut state = AppState::new();
+    let rect = RectangleElement::new(0, Coord { x: 2, y: 2 }, 6, 4);
+    let id = select_only(&mut state, Element::Rectangle(rect));
+
+    assert_eq!(
+        state.resize_handle_at(8, 6),
+        Some(ResizeHandle::BottomRight)
+    );
+    assert_eq!(state.resize_handle_at(5, 2), Some(ResizeHandle::Top));
+    assert_eq!(state.resize_handle_at(4, 4), None);
+
+    // The preview follows the drag, the element only changes when it ends
+    state.start_resize(ResizeHandle::BottomRight, 8, 6);
+    state.update_resize(12, 9);
+    let preview = state.get_resize_preview().unwrap();
+    assert_eq!(preview.bounds().max, Coord { x: 12, y: 9 });
+    assert_eq!(
+        state.canvas.get_element(id).unwrap().bounds().max,
+        Coord { x: 8, y: 6 }
+    );
+
+    state.finish_resize();
+    let Some(Element::Rectangle(rect)) = state.canvas.get_element(id) else {
+        panic!("expected a rectangle");
+    };
+    assert_eq!(
+        (rect.start.x, rect.start.y, rect.width, rect.height),
+        (2, 2, 10, 7)
+    );
+    assert_eq!(state.selection_state.mode, SelectionMode::Selected);
+
+    // Dragging the left edge past the right one stops one cell short of it
+    state.start_resize(ResizeHandle::Left, 2, 5);
+    state.update_resize(20, 5);
+    state.finish_resize();
+    let bounds = state.canvas.get_element(id).unwrap().bounds();
+    assert_eq!((bounds.min.x, bounds.max.x), (11, 12));
+}
+
+#[test]
+fn test_resize_table_recomputes_rows_and_cols() {
+    let mut state = AppState::new();
+    let table = TableElement::new(0, Coord { x: 0, y: 0 }, 2, 2);
+    let id = select_only(&mut state, Element::Table(table));
+    let (width, height) = TableElement::calculate_size(2, 2);
+
+    state.start_resize(ResizeHandle::BottomRight, width as i32, height as i32);
+    state.update_resize(40, 12);
+    state.finish_resize();
+
+    let Some(Element::Table(table)) = state.canvas.get_element(id) else {
+        panic!("expected a table");
+    };
+    assert_eq!((table.rows, table.cols), (3, 3));
+    assert_eq!(table.cells.len(), 3);
+    assert!(table.cells.iter().all(|row| row.len() == 3));
+    assert_eq!(table.cells[0][1], "Header 2");
+
+    // Shrinking from the top-left keeps the bottom-right corner in place
+    let max = table.bounds.max;
+    state.start_resize(ResizeHandle::TopLeft, 0, 0);
+    state.update_resize(max.x - 14, max.y - 4);
+    state.finish_resize();
+
+    let Some(Element::Table(table)) = state.canvas.get_element(id) else {
+        panic!("expected a table");
+    };
+    assert_eq!((table.rows, table.cols), (1, 1));
+    assert_eq!(table.bounds.max, max);
+}
+
+#[test]
+fn test_no_resize_handles_for_multiple_selection() {
+    let mut state = AppState::new();
+    let first = RectangleElement::new(0, Coord { x: 0, y: 0 }, 4, 4);
+    select_only(&mut state, Element::Rectangle(first));
+    let second = RectangleElement::new(1, Coord { x: 10, y: 0 }, 4, 4);
+    state.canvas.add_element(Element::Rectangle(second));
+    state.selection_state.selected_ids.push(1);
+
+    assert!(state.get_resize_handles().is_empty());
+    assert_eq!(state.resize_handle_at(0, 0), None);
+}
@@ -0,0 +1,115 @@
+use super::fixtures::*;
+use crate::state::{AppState, ConfirmAction, swap};
+use assert_fs::TempDir;
+use std::path::PathBuf;
+
+/// A saved, empty diagram in a temp dir, with one unsaved rectangle added on top.
+/// Returns (state, diagram path, where its swap file goes).
+fn state_with_unsaved_changes(dir: &TempDir) -> (AppState, String, PathBuf) {
+    let path = dir.path().join("diagram.textdraw");
+    let swap = dir.path().join(".diagram.textdraw.swp");
+    let path = path.display().to_string();
+
+    let mut state = AppState::new();
+    state.file.save_to_file(&state.canvas, &path).unwrap();
+
+    add_rectangle(&mut state, 0, 0, 4, 2);
+    state.commit_history();
+
+    (state, path, swap)
+}
+
+#[test]
+fn test_autosave_writes_swap_next_to_file() {
+    let dir = TempDir::new().unwrap();
+    let (mut state, path, swap) = state_with_unsaved_changes(&dir);
+
+    state.autosave();
+    assert!(swap.exists());
+
+    // Saving makes the swap file redundant
+    state.file.save_to_file(&state.canvas, &path).unwrap();
+    state.autosave();
+    assert!(!swap.exists());
+}
+
+#[test]
+fn test_recover_from_swap_after_crash() {
+    let dir = TempDir::new().unwrap();
+    let (mut crashed, path, swap) = state_with_unsaved_changes(&dir);
+    crashed.autosave();
+    drop(crashed);
+
+    let mut state = AppState::new();
+    state.load_from_file_silent(&path).unwrap();
+    state.offer_recovery();
+    assert_eq!(state.confirm, Some(ConfirmAction::Recover(swap.clone())));
+
+    state.confirm_pending_action();
+    assert_eq!(state.canvas.elements().len(), 1);
+    assert!(state.file.is_dirty());
+    assert_eq!(state.file.current_file.as_deref(), Some(path.as_str()));
+
+    // The recovered swap is cleaned up on exit like our own
+    state.remove_swap().unwrap();
+    assert!(!swap.exists());
+}
+
+#[test]
+fn test_declining_recovery_keeps_swap() {
+    let dir = TempDir::new().unwrap();
+    let (mut crashed, path, swap) = state_with_unsaved_changes(&dir);
+    crashed.autosave();
+
+    let mut state = AppState::new();
+    state.load_from_file_silent(&path).unwrap();
+    state.offer_recovery();
+    state.cancel_pending_action();
+
+    assert!(state.canvas.is_empty());
+    assert!(swap.exists());
+    assert!(!state.file.is_dirty());
+}
+
+#[test]
+fn test_swap_older_than_file_is_not_offered() {
+    let dir = TempDir::new().unwrap();
+    let (mut state, path, swap) = state_with_unsaved_changes(&dir);
+    state.autosave();
+
+    // Saved elsewhere after the swap was written, e.g. by another session
+    state.canvas.save_to_file(&path).unwrap();
+    assert!(swap.exists());
+
+    let mut reopened = AppState::new();
+    reopened.load_from_file_silent(&path).unwrap();
+    reopened.offer_recovery();
+    assert_eq!(reopened.confirm, None);
+}
+
+#[test]
+fn test_reopening_discards_own_swap() {
+    let dir = TempDir::new().unwrap();
+    let (mut state, path, swap) = state_with_unsaved_changes(&dir);
+    state.autosave();
+    assert!(swap.exists());
+
+    // `:e!` on the same file throws the changes away instead of offering them back
+    state.open_file(&path);
+    assert_eq!(state.confirm, None);
+    assert!(state.canvas.is_empty());
+    assert!(!swap.exists());
+}
+
+#[test]
+fn test_unnamed_swaps_are_per_session() {
+    let dir = TempDir::new().unwrap();
+    let own = dir.path().join(swap::unnamed_swap_name(std::process::id()));
+    std::fs::write(&own, "{}").unwrap();
+    assert_eq!(swap::find_unnamed_swap(dir.path()), None);
+
+    // No process has this ID, so its session has ended
+    let ended = dir.path().join(swap::unnamed_swap_name(u32::MAX));
+    std::fs::write(&ended, "{}").unwrap();
+    assert_eq!(swap::find_unnamed_swap(dir.path()), Some(ended));
+}
@@ -0,0 +1,168 @@
+use super::fixtures::*;
+use crate::components::vertex_editor;
+use crate::elements::{
+    ArrowElement, Element, LineElement, Segment, insert_vertex, move_vertex, remove_vertex,
+};
+use crate::events::EventResult;
+use crate::state::AppState;
+use crate::types::{Coord, SelectionMode};
+use crossterm::event::KeyCode;
+
+fn coords(element: &Element) -> Vec<(i32, i32)> {
+    element
+        .vertices()
+        .unwrap()
+        .into_iter()
+        .map(|c| (c.x, c.y))
+        .collect()
+}
+
+/// An L-shaped arrow from (0,0) right to (6,0), then down to (6,4), in Edit Vertices mode
+fn state_editing_arrow() -> (AppState, usize) {
+    let mut state = AppState::new();
+    let id = state.canvas.get_next_id();
+    let arrow = ArrowElement::new(id, Segment::path(&points(&[(0, 0), (6, 0), (6, 4)])));
+    state.canvas.add_element(Element::Arrow(arrow));
+    state.selection_state.selected_ids = vec![id];
+    state.selection_state.mode = SelectionMode::Selected;
+    assert_eq!(
+        vertex_editor::try_enter_edit_vertices_mode(&mut state),
+        EventResult::Consumed
+    );
+    (state, id)
+}
+
+#[test]
+fn test_moving_bend_slides_neighbouring_bends() {
+    // Z shape: the middle run moves sideways with the dragged bend
+    let mut path = points(&[(0, 0), (4, 0), (4, 3), (8, 3)]);
+    let index = move_vertex(&mut path, 1, Coord { x: 6, y: 0 });
+
+    assert_eq!(index, 1);
+    assert_eq!(path, points(&[(0, 0), (6, 0), (6, 3), (8, 3)]));
+}
+
+#[test]
+fn test_moving_end_adds_corner() {
+    let mut path = points(&[(0, 0), (6, 0)]);
+    let index = move_vertex(&mut path, 1, Coord { x: 6, y: 3 });
+
+    assert_eq!(index, 2);
+    assert_eq!(path, points(&[(0, 0), (6, 0), (6, 3)]));
+}
+
+#[test]
+fn test_pulling_inserted_bend_offsets_the_run() {
+    let mut path = points(&[(0, 0), (8, 0)]);
+    let index = insert_vertex(&mut path, 0, Coord { x: 4, y: 0 }).unwrap();
+    let index = move_vertex(&mut path, index, Coord { x: 4, y: 2 });
+
+    assert_eq!(path, points(&[(0, 0), (0, 2), (4, 2), (8, 2), (8, 0)]));
+    assert_eq!(path[index], Coord { x: 4, y: 2 });
+
+    // Only strictly inside a segment
+    assert_eq!(insert_vertex(&mut path, 0, Coord { x: 0, y: 0 }), None);
+}
+
+#[test]
+fn test_removing_bend() {
+    // A straight-through vertex just goes away
+    let mut path = points(&[(0, 0), (4, 0), (8, 0)]);
+    assert_eq!(remove_vertex(&mut path, 1), Some(0));
+    assert_eq!(path, points(&[(0, 0), (8, 0)]));
+
+    // The corner of an L flips to the other side
+    let mut path = points(&[(0, 0), (6, 0), (6, 4)]);
+    remove_vertex(&mut path, 1);
+    assert_eq!(path, points(&[(0, 0), (0, 4), (6, 4)]));
+
+    // Ends can't be removed
+    assert_eq!(remove_vertex(&mut path, 0), None);
+}
+
+#[test]
+fn test_edit_arrow_with_keys_updates_arrowhead() {
+    let (mut state, id) = state_editing_arrow();
+
+    // The end is selected first. Moving it left slides the bend along until it meets
+    // the start, after which the corner turns at the start instead.
+    for _ in 0..8 {
+        vertex_editor::handle_edit_vertices_key(&mut state, &key(KeyCode::Left));
+    }
+    let element = state.canvas.get_element(id).unwrap();
+    assert_eq!(coords(element), vec![(0, 0), (0, 4), (-2, 4)]);
+    let Element::Arrow(arrow) = element else {
+        panic!("expected an arrow");
+    };
+    assert_eq!(arrow.head_end, '◀');
+
+    // Esc restores the original path
+    vertex_editor::handle_edit_vertices_key(&mut state, &key(KeyCode::Esc));
+    assert!(!state.is_editing_vertices());
+    let element = state.canvas.get_element(id).unwrap();
+    assert_eq!(coords(element), vec![(0, 0), (6, 0), (6, 4)]);
+}
+
+#[test]
+fn test_reverse_arrow() {
+    let (mut state, id) = state_editing_arrow();
+
+    vertex_editor::handle_edit_vertices_key(&mut state, &key(KeyCode::Char('r')));
+    vertex_editor::handle_edit_vertices_key(&mut state, &key(KeyCode::Enter));
+
+    let element = state.canvas.get_element(id).unwrap();
+    assert_eq!(coords(element), vec![(6, 4), (6, 0), (0, 0)]);
+    let Element::Arrow(arrow) = element else {
+        panic!("expected an arrow");
+    };
+    assert_eq!(arrow.head_end, '◀');
+    assert_eq!(arrow.head_start, ' ');
+    assert!(!state.is_editing_vertices());
+}
+
+#[test]
+fn test_drag_bend_with_mouse_is_one_undo_step() {
+    let (mut state, id) = state_editing_arrow();
+    state.commit_history();
+
+    // Click the middle of the first segment to add a bend there, then drag it up
+    assert!(vertex_editor::handle_edit_vertices_mouse_down(
+        &mut state, 3, 0
+    ));
+    state.commit_history();
+    for y in [-1, -2] {
+        assert!(vertex_editor::drag_vertex(&mut state, 3, y));
+        state.commit_history();
+    }
+    assert!(vertex_editor::release_vertex(&mut state));
+    assert_eq!(
+        coords(state.canvas.get_element(id).unwrap()),
+        vec![(0, 0), (0, -2), (3, -2), (6, -2), (6, 4)]
+    );
+
+    state.undo();
+    assert_eq!(
+        coords(state.canvas.get_element(id).unwrap()),
+        vec![(0, 0), (6, 0), (6, 4)]
+    );
+}
+
+#[test]
+fn test_moving_end_detaches_and_reattaches() {
+    let mut state = AppState::new();
+    let rect_id = add_rectangle(&mut state, 10, 0, 4, 4);
+    let id = state.canvas.get_next_id();
+    let line = LineElement::new(id, Segment::path(&points(&[(0, 2), (9, 2)])));
+    state.canvas.add_element(Element::Line(line));
+    state.canvas.attach_connector(id);
+    state.selection_state.selected_ids = vec![id];
+    state.selection_state.mode = SelectionMode::Selected;
+    vertex_editor::try_enter_edit_vertices_mode(&mut state);
+
+    vertex_editor::handle_edit_vertices_key(&mut state, &key(KeyCode::Left));
+    let attached = |state: &AppState| state.canvas.get_element(id).unwrap().attachments()[1];
+    assert!(attached(&state).is_none());
+
+    vertex_editor::handle_edit_vertices_key(&mut state, &key(KeyCode::Right));
+    assert_eq!(attached(&state).map(|a| a.element_id), Some(rect_id));
+}
@@ -0,0 +1,23 @@
+use crate::state::AppState;
+
+#[test]
+fn test_pan_viewport_moves_cursor_along() {
+    let mut state = AppState::new();
+    state.update_cursor(5, 5);
+
+    state.pan_viewport(10, 3);
+
+    assert_eq!((state.viewport_x, state.viewport_y), (10, 3));
+    assert_eq!((state.cursor_x, state.cursor_y), (15, 8));
+}
+
+#[test]
+fn test_pan_viewport_past_origin() {
+    let mut state = AppState::new();
+    state.update_cursor(6, 6);
+
+    state.pan_viewport(-10, -1);
+
+    assert_eq!((state.viewport_x, state.viewport_y), (-10, -1));
+    assert_eq!((state.cursor_x, state.cursor_y), (-4, 5));
+}
@@ -1,9 +1,13 @@
-use crate::tools::{ArrowTool, DrawingTool, LineTool, RectangleTool, TableTool, TextTool, Tool};
+use crate::config::Defaults;
+use crate::tools::{
+    ArrowTool, ConnectorTool, DrawingTool, LineTool, RectangleTool, TableTool, TextTool, Tool,
+};
 
 pub struct ToolState {
     pub selected_tool: Tool,
-    pub tool_index: usize, // For arrow key navigation
-    pub tool_locked: bool, // If true, tool stays active after drawing
+    pub tool_index: usize,  // For arrow key navigation
+    pub tool_locked: bool,  // If true, tool stays active after drawing
+    pub defaults: Defaults, // Border styles for new elements, from the config file
     active_tool: Option<Box<dyn DrawingTool>>,
 }
 
@@ -13,6 +17,7 @@ impl ToolState {
             selected_tool: Tool::Select,
             tool_index: 0,
             tool_locked: false,
+            defaults: Defaults::default(),
             active_tool: None, // No active tool when in Select mode
         }
     }
@@ -27,10 +32,15 @@ impl ToolState {
         self.active_tool = match tool {
             Tool::Select => None, // Selection is handled by selection_state, not as a tool
             Tool::Line => Some(Box::new(LineTool::new())),
-            Tool::Rectangle => Some(Box::new(RectangleTool::new())),
+            Tool::Rectangle => Some(Box::new(
+                RectangleTool::new().with_border_style(self.defaults.rectangle_border),
+            )),
             Tool::Arrow => Some(Box::new(ArrowTool::new())),
+            Tool::Connector => Some(Box::new(ConnectorTool::new())),
             Tool::Text => Some(Box::new(TextTool::new())),
-            Tool::Table => Some(Box::new(TableTool::new())),
+            Tool::Table => Some(Box::new(
+                TableTool::new().with_border_style(self.defaults.table_border),
+            )),
         };
 
         // Return true if we switched away from Select tool (caller should deselect)
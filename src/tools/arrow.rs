@@ -88,6 +88,7 @@ impl EventHandler for ArrowTool {
                     let id = state.get_next_id();
                     let arrow = ArrowElement::new(id, self.segments.clone());
                     state.add_element(Element::Arrow(arrow));
+                    state.attach_connector(id);
                 }
 
                 self.reset();
@@ -160,6 +161,7 @@ impl EventHandler for ArrowTool {
             let id = state.get_next_id();
             let arrow = ArrowElement::new(id, self.segments.clone());
             state.add_element(Element::Arrow(arrow));
+            state.attach_connector(id);
         }
 
         self.reset();
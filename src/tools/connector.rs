@@ -0,0 +1,175 @@
+use crate::elements::{ArrowElement, Element, Segment, route, route_around};
+use crate::events::{ActionType, EventHandler, EventResult, MouseEvent};
+use crate::state::CanvasState;
+use crate::tools::DrawingTool;
+use crate::types::{Bounds, Coord};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DrawingState {
+    Idle,
+    Anchored, // First click done, waiting for second click (click-move-click)
+    Dragging, // User is actively dragging (drag-and-drop)
+}
+
+/// Draws arrows from just a start and end point, routing the path around other
+/// elements with as few bends as possible
+pub struct ConnectorTool {
+    start: Option<Coord>,
+    route: Vec<Segment>, // Route to the current cursor position, for the preview
+    state: DrawingState,
+}
+
+impl ConnectorTool {
+    pub fn new() -> Self {
+        Self {
+            start: None,
+            route: Vec::new(),
+            state: DrawingState::Idle,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.start = None;
+        self.route.clear();
+        self.state = DrawingState::Idle;
+    }
+
+    fn update_route(&mut self, canvas: &CanvasState, end: Coord) {
+        if let Some(start) = self.start {
+            self.route = find_route(canvas, start, end);
+        }
+    }
+
+    /// Add the routed arrow to the canvas, if there is one
+    fn finish_arrow(&mut self, canvas: &mut CanvasState, end: Coord) -> EventResult {
+        self.update_route(canvas, end);
+        if self.route.is_empty() {
+            self.reset();
+            return EventResult::Consumed;
+        }
+
+        let id = canvas.get_next_id();
+        let arrow = ArrowElement::new(id, std::mem::take(&mut self.route));
+        canvas.add_element(Element::Arrow(arrow));
+        canvas.attach_connector(id);
+
+        self.reset();
+        EventResult::Action(ActionType::FinishedDrawing)
+    }
+}
+
+/// Route around rectangles, tables and text. Lines and arrows may be crossed, since
+/// their bounding boxes would otherwise wall off large parts of the canvas.
+fn find_route(canvas: &CanvasState, start: Coord, end: Coord) -> Vec<Segment> {
+    if start == end {
+        return Vec::new();
+    }
+
+    let obstacles: Vec<Bounds> = canvas
+        .draw_order()
+        .into_iter()
+        .filter(|e| !matches!(e, Element::Line(_) | Element::Arrow(_)))
+        .map(Element::bounds)
+        .collect();
+
+    route_around(start, end, &obstacles).unwrap_or_else(|| route(start, None, end, None))
+}
+
+impl EventHandler for ConnectorTool {
+    type State = CanvasState;
+
+    fn handle_mouse_down(
+        &mut self,
+        state: &mut CanvasState,
+        mouse_event: &MouseEvent,
+    ) -> EventResult {
+        match self.state {
+            DrawingState::Idle => {
+                // First click - anchor the start point
+                self.start = Some(mouse_event.get_coord());
+                self.route.clear();
+                self.state = DrawingState::Anchored;
+                EventResult::Consumed
+            }
+            DrawingState::Anchored => {
+                // Second click - finalize unless it's on the start point
+                if self.start == Some(mouse_event.get_coord()) {
+                    return EventResult::Consumed;
+                }
+                self.finish_arrow(state, mouse_event.get_coord())
+            }
+            DrawingState::Dragging => {
+                // Shouldn't happen, but reset just in case
+                self.reset();
+                EventResult::Consumed
+            }
+        }
+    }
+
+    fn handle_mouse_moved(
+        &mut self,
+        state: &mut CanvasState,
+        mouse_event: &MouseEvent,
+    ) -> EventResult {
+        // Update preview when in click-move-click mode
+        if self.state == DrawingState::Anchored {
+            self.update_route(state, mouse_event.get_coord());
+            EventResult::Consumed
+        } else {
+            EventResult::Ignored
+        }
+    }
+
+    fn handle_mouse_drag(
+        &mut self,
+        state: &mut CanvasState,
+        mouse_event: &MouseEvent,
+    ) -> EventResult {
+        // Switch to dragging mode and update preview
+        if self.state == DrawingState::Anchored {
+            self.state = DrawingState::Dragging;
+        }
+        self.update_route(state, mouse_event.get_coord());
+        EventResult::Consumed
+    }
+
+    fn handle_mouse_up(
+        &mut self,
+        state: &mut CanvasState,
+        mouse_event: &MouseEvent,
+    ) -> EventResult {
+        // Only finalize on mouse_up if we're in Dragging mode (drag-and-drop)
+        if self.state != DrawingState::Dragging {
+            return EventResult::Consumed;
+        }
+        self.finish_arrow(state, mouse_event.get_coord())
+    }
+}
+
+impl DrawingTool for ConnectorTool {
+    fn preview_points(&self) -> Vec<(i32, i32, char)> {
+        if self.route.is_empty() {
+            return vec![];
+        }
+        ArrowElement::new(0, self.route.clone()).render_points()
+    }
+
+    fn finish(&mut self, _state: &mut CanvasState) {
+        // Element creation only happens on click/mouse up
+        self.reset();
+    }
+
+    fn cancel(&mut self) {
+        self.reset();
+    }
+
+    fn is_drawing(&self) -> bool {
+        self.start.is_some()
+    }
+}
+
+impl Default for ConnectorTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
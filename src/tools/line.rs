@@ -89,6 +89,7 @@ impl EventHandler for LineTool {
                     let id = state.get_next_id();
                     let line = LineElement::new(id, self.segments.clone());
                     state.add_element(Element::Line(line));
+                    state.attach_connector(id);
                 }
 
                 self.reset();
@@ -161,6 +162,7 @@ impl EventHandler for LineTool {
             let id = state.get_next_id();
             let line = LineElement::new(id, self.segments.clone());
             state.add_element(Element::Line(line));
+            state.attach_connector(id);
         }
 
         self.reset();
@@ -1,4 +1,5 @@
 mod arrow;
+mod connector;
 mod line;
 mod rectangle;
 mod table;
@@ -6,6 +7,7 @@ mod text;
 
 // Re-export tool implementations
 pub use arrow::ArrowTool;
+pub use connector::ConnectorTool;
 pub use line::LineTool;
 pub use rectangle::RectangleTool;
 pub use table::TableTool;
@@ -14,15 +16,16 @@ pub use text::TextTool;
 use crate::events::EventHandler;
 use crate::state::CanvasState;
 
-/// Macro to define the Tool enum with associated names and keyboard shortcuts.
+/// Macro to define the Tool enum with associated names.
 ///
 /// This generates:
 /// - The Tool enum with all variants
 /// - `all()` - returns all tools as a Vec
 /// - `name()` - returns the display name for the tool
-/// - `key()` - returns the keyboard shortcut for the tool
+///
+/// Keyboard shortcuts live in the keymap (`Action::SelectTool`).
 macro_rules! define_tools_enum {
-    ( $( $variant:ident => ($name:expr, $key:expr) ),* $(,)? ) => {
+    ( $( $variant:ident => $name:expr ),* $(,)? ) => {
         #[derive(Debug, Clone, Copy, PartialEq, Eq)]
         pub enum Tool {
             $($variant),*
@@ -39,19 +42,6 @@ macro_rules! define_tools_enum {
                 }
             }
 
-            pub fn key(&self) -> char {
-                match self {
-                    $(Tool::$variant => $key),*
-                }
-            }
-
-            pub fn from_key(c: char) -> Option<Self> {
-                match c {
-                    $($key => Some(Tool::$variant),)*
-                    _ => None,
-                }
-            }
-
             /// Get the maximum length of all tool names (for UI alignment)
             pub const fn max_name_len() -> usize {
                 let mut max = 0;
@@ -67,14 +57,15 @@ macro_rules! define_tools_enum {
     };
 }
 
-// Define all tools as enum with their names and keys
+// Define all tools as enum with their names
 define_tools_enum! {
-    Select    => ("Select", 's'),
-    Line      => ("Line", 'l'),
-    Rectangle => ("Rectangle", 'r'),
-    Arrow     => ("Arrow", 'a'),
-    Text      => ("Text", 't'),
-    Table     => ("Table", '\0'),
+    Select    => "Select",
+    Line      => "Line",
+    Rectangle => "Rectangle",
+    Arrow     => "Arrow",
+    Connector => "Connector",
+    Text      => "Text",
+    Table     => "Table",
 }
 
 /// Trait for all drawing tools - extends EventHandler for event routing
@@ -1,4 +1,4 @@
-use crate::elements::{Element, RectangleElement};
+use crate::elements::{BorderStyle, Element, RectangleElement};
 use crate::events::{ActionType, EventHandler, EventResult, MouseEvent};
 use crate::state::CanvasState;
 use crate::tools::DrawingTool;
@@ -12,9 +12,10 @@ enum DrawingState {
 }
 
 pub struct RectangleTool {
-    start: Option<(u16, u16)>,
-    current: Option<(u16, u16)>,
+    start: Option<(i32, i32)>,
+    current: Option<(i32, i32)>,
     state: DrawingState,
+    border_style: BorderStyle,
 }
 
 impl RectangleTool {
@@ -23,14 +24,34 @@ impl RectangleTool {
             start: None,
             current: None,
             state: DrawingState::Idle,
+            border_style: BorderStyle::default(),
         }
     }
 
+    /// Use the given border style for new rectangles
+    pub fn with_border_style(mut self, border_style: BorderStyle) -> Self {
+        self.border_style = border_style;
+        self
+    }
+
     fn reset(&mut self) {
         self.start = None;
         self.current = None;
         self.state = DrawingState::Idle;
     }
+
+    /// Rectangle spanning the two corners
+    fn rectangle(&self, id: usize, (sx, sy): (i32, i32), (x, y): (i32, i32)) -> RectangleElement {
+        let left = sx.min(x);
+        let top = sy.min(y);
+        let width = sx.abs_diff(x);
+        let height = sy.abs_diff(y);
+
+        let mut rect =
+            RectangleElement::new(id, Coord { x: left, y: top }, width as u16, height as u16);
+        rect.border_style = self.border_style;
+        rect
+    }
 }
 
 impl EventHandler for RectangleTool {
@@ -65,17 +86,7 @@ impl EventHandler for RectangleTool {
                 }
 
                 let id = state.get_next_id();
-                let left = sx.min(x);
-                let top = sy.min(y);
-                let width = sx.abs_diff(x);
-                let height = sy.abs_diff(y);
-
-                let rect = RectangleElement::new(
-                    id,
-                    Coord { x: left, y: top },
-                    width as u16,
-                    height as u16,
-                );
+                let rect = self.rectangle(id, (sx, sy), (x, y));
                 state.add_element(Element::Rectangle(rect));
 
                 self.reset();
@@ -141,13 +152,7 @@ impl EventHandler for RectangleTool {
         }
 
         let id = state.get_next_id();
-        let left = sx.min(x);
-        let top = sy.min(y);
-        let width = sx.abs_diff(x);
-        let height = sy.abs_diff(y);
-
-        let rect =
-            RectangleElement::new(id, Coord { x: left, y: top }, width as u16, height as u16);
+        let rect = self.rectangle(id, (sx, sy), (x, y));
         state.add_element(Element::Rectangle(rect));
 
         self.reset();
@@ -157,13 +162,8 @@ impl EventHandler for RectangleTool {
 
 impl DrawingTool for RectangleTool {
     fn preview_points(&self) -> Vec<(i32, i32, char)> {
-        if let (Some((sx, sy)), Some((cx, cy))) = (self.start, self.current) {
-            let left = sx.min(cx);
-            let top = sy.min(cy);
-            let width = sx.abs_diff(cx);
-            let height = sy.abs_diff(cy);
-            let temp_rect =
-                RectangleElement::new(0, Coord { x: left, y: top }, width as u16, height as u16);
+        if let (Some(start), Some(current)) = (self.start, self.current) {
+            let temp_rect = self.rectangle(0, start, current);
             temp_rect.render_points()
         } else {
             vec![]
@@ -1,4 +1,4 @@
-use crate::elements::{Element, TableElement};
+use crate::elements::{BorderStyle, Element, TableElement};
 use crate::events::{ActionType, EventHandler, EventResult, MouseEvent};
 use crate::state::CanvasState;
 use crate::tools::DrawingTool;
@@ -12,9 +12,10 @@ enum DrawingState {
 }
 
 pub struct TableTool {
-    start: Option<(u16, u16)>,
-    current: Option<(u16, u16)>,
+    start: Option<(i32, i32)>,
+    current: Option<(i32, i32)>,
     state: DrawingState,
+    border_style: BorderStyle,
 }
 
 impl TableTool {
@@ -23,14 +24,35 @@ impl TableTool {
             start: None,
             current: None,
             state: DrawingState::Idle,
+            border_style: BorderStyle::default(),
         }
     }
 
+    /// Use the given border style for new tables
+    pub fn with_border_style(mut self, border_style: BorderStyle) -> Self {
+        self.border_style = border_style;
+        self
+    }
+
     fn reset(&mut self) {
         self.start = None;
         self.current = None;
         self.state = DrawingState::Idle;
     }
+
+    /// Table filling the area between the two corners
+    fn table(&self, id: usize, (sx, sy): (i32, i32), (x, y): (i32, i32)) -> TableElement {
+        let left = sx.min(x);
+        let top = sy.min(y);
+        let width = sx.abs_diff(x) as u16;
+        let height = sy.abs_diff(y) as u16;
+
+        // Rows/cols are auto-calculated from the dragged area
+        let mut table = TableElement::new_from_drag(id, Coord { x: left, y: top }, width, height);
+        table.header_border = self.border_style;
+        table.body_border = self.border_style;
+        table
+    }
 }
 
 impl EventHandler for TableTool {
@@ -66,14 +88,7 @@ impl EventHandler for TableTool {
                 }
 
                 let id = state.get_next_id();
-                let left = sx.min(x);
-                let top = sy.min(y);
-                let width = sx.abs_diff(x);
-                let height = sy.abs_diff(y);
-
-                // Create table with auto-calculated rows/cols based on dragged area
-                let table =
-                    TableElement::new_from_drag(id, Coord { x: left, y: top }, width, height);
+                let table = self.table(id, (sx, sy), (x, y));
                 state.add_element(Element::Table(table));
 
                 self.reset();
@@ -139,13 +154,7 @@ impl EventHandler for TableTool {
         }
 
         let id = state.get_next_id();
-        let left = sx.min(x);
-        let top = sy.min(y);
-        let width = sx.abs_diff(x);
-        let height = sy.abs_diff(y);
-
-        // Create table with auto-calculated rows/cols based on dragged area
-        let table = TableElement::new_from_drag(id, Coord { x: left, y: top }, width, height);
+        let table = self.table(id, (sx, sy), (x, y));
         state.add_element(Element::Table(table));
 
         self.reset();
@@ -155,15 +164,9 @@ impl EventHandler for TableTool {
 
 impl DrawingTool for TableTool {
     fn preview_points(&self) -> Vec<(i32, i32, char)> {
-        if let (Some((sx, sy)), Some((cx, cy))) = (self.start, self.current) {
-            let left = sx.min(cx);
-            let top = sy.min(cy);
-            let width = sx.abs_diff(cx);
-            let height = sy.abs_diff(cy);
-
+        if let (Some(start), Some(current)) = (self.start, self.current) {
             // Show preview with auto-calculated rows/cols based on dragged area
-            let temp_table =
-                TableElement::new_from_drag(0, Coord { x: left, y: top }, width, height);
+            let temp_table = self.table(0, start, current);
             temp_table.render_points()
         } else {
             vec![]
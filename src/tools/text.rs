@@ -6,7 +6,7 @@ use crate::types::Coord;
 use crossterm::event::KeyCode;
 
 pub struct TextTool {
-    position: Option<(u16, u16)>,
+    position: Option<(i32, i32)>,
     text: String,
 }
 
@@ -103,10 +103,10 @@ impl DrawingTool for TextTool {
         if let Some((px, py)) = self.position {
             let mut points = vec![];
             for (i, ch) in self.text.chars().enumerate() {
-                points.push((px as i32 + i as i32, py as i32, ch));
+                points.push((px + i as i32, py, ch));
             }
             // Add cursor
-            points.push((px as i32 + self.text.len() as i32, py as i32, '█'));
+            points.push((px + self.text.len() as i32, py, '█'));
             points
         } else {
             vec![]
@@ -12,25 +12,25 @@ pub type RenderMap = HashMap<(i32, i32), char>;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Coord {
-    pub x: u16,
-    pub y: u16,
+    pub x: i32,
+    pub y: i32,
 }
 
 impl Coord {
-    pub fn translate(&mut self, dx: i16, dy: i16) {
-        self.x = self.x.saturating_add_signed(dx);
-        self.y = self.y.saturating_add_signed(dy);
+    pub fn translate(&mut self, dx: i32, dy: i32) {
+        self.x += dx;
+        self.y += dy;
     }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Bounds {
     pub min: Coord,
     pub max: Coord,
 }
 
 impl Bounds {
-    pub fn translate(&mut self, dx: i16, dy: i16) {
+    pub fn translate(&mut self, dx: i32, dy: i32) {
         self.min.translate(dx, dy);
         self.max.translate(dx, dy);
     }
@@ -44,10 +44,23 @@ pub enum Direction {
     Right,
 }
 
+impl Direction {
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Panel {
     Canvas,
     Properties,
+    Layers,
+    Outline,
     Tools,
 }
 
@@ -57,4 +70,103 @@ pub enum SelectionMode {
     Selecting,
     Selected,
     Moving,
+    Resizing,
+}
+
+/// Handle on the bounding box of a selected rectangle or table, dragged to resize it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResizeHandle {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl ResizeHandle {
+    /// Corners first, so they win over edge handles on boxes too small to tell apart
+    pub fn all() -> &'static [ResizeHandle] {
+        use ResizeHandle::*;
+        &[
+            TopLeft,
+            TopRight,
+            BottomLeft,
+            BottomRight,
+            Top,
+            Bottom,
+            Left,
+            Right,
+        ]
+    }
+
+    pub fn moves_left(self) -> bool {
+        matches!(self, Self::TopLeft | Self::BottomLeft | Self::Left)
+    }
+
+    pub fn moves_right(self) -> bool {
+        matches!(self, Self::TopRight | Self::BottomRight | Self::Right)
+    }
+
+    pub fn moves_top(self) -> bool {
+        matches!(self, Self::TopLeft | Self::TopRight | Self::Top)
+    }
+
+    pub fn moves_bottom(self) -> bool {
+        matches!(self, Self::BottomLeft | Self::BottomRight | Self::Bottom)
+    }
+
+    /// Where the handle sits on the given bounds
+    pub fn position(self, bounds: &Bounds) -> Coord {
+        let (min, max) = (bounds.min, bounds.max);
+        let x = if self.moves_left() {
+            min.x
+        } else if self.moves_right() {
+            max.x
+        } else {
+            (min.x + max.x) / 2
+        };
+        let y = if self.moves_top() {
+            min.y
+        } else if self.moves_bottom() {
+            max.y
+        } else {
+            (min.y + max.y) / 2
+        };
+        Coord { x, y }
+    }
+
+    /// Bounds after dragging this handle to (x, y). The opposite edges stay put and
+    /// the dragged edges can't cross them.
+    pub fn drag(self, bounds: &Bounds, x: i32, y: i32) -> Bounds {
+        let mut result = *bounds;
+        if self.moves_left() {
+            result.min.x = x.min(bounds.max.x - 1);
+        }
+        if self.moves_right() {
+            result.max.x = x.max(bounds.min.x + 1);
+        }
+        if self.moves_top() {
+            result.min.y = y.min(bounds.max.y - 1);
+        }
+        if self.moves_bottom() {
+            result.max.y = y.max(bounds.min.y + 1);
+        }
+        result
+    }
+}
+
+/// Where `CanvasState::reorder` moves elements in the drawing order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZOrder {
+    /// One step up, past the next element drawn after them
+    Raise,
+    /// One step down, past the previous element drawn before them
+    Lower,
+    /// Above everything else
+    Front,
+    /// Below everything else
+    Back,
 }
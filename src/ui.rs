@@ -5,7 +5,8 @@ pub use styles::*;
 pub use widgets::*;
 
 use crate::components::{
-    CanvasComponent, Component, HelpModal, PropertiesPanel, StatusBar, ToolsPanel,
+    CanvasComponent, Component, ConfirmModal, HelpModal, LayersPanel, OutlinePanel,
+    PropertiesPanel, StatusBar, ToolsPanel,
 };
 use crate::events::EventHandler;
 use crate::state::AppState;
@@ -19,9 +20,12 @@ use ratatui::{
 pub struct UI {
     tools_panel: ToolsPanel,
     properties_panel: PropertiesPanel,
+    layers_panel: LayersPanel,
+    outline_panel: OutlinePanel,
     canvas: CanvasComponent,
     statusbar: StatusBar,
     help_modal: HelpModal,
+    confirm_modal: ConfirmModal,
 }
 
 impl UI {
@@ -30,9 +34,12 @@ impl UI {
         Self {
             tools_panel: ToolsPanel::new(),
             properties_panel: PropertiesPanel::new(),
+            layers_panel: LayersPanel::new(),
+            outline_panel: OutlinePanel::new(),
             canvas: CanvasComponent::new(),
             statusbar: StatusBar::new(),
             help_modal: HelpModal::new(),
+            confirm_modal: ConfirmModal::new(),
         }
     }
 
@@ -41,17 +48,23 @@ impl UI {
         self.canvas.draw(state, frame);
         self.statusbar.draw(state, frame);
         self.properties_panel.draw(state, frame); // Render after canvas as floating overlay
+        self.layers_panel.draw(state, frame);
+        self.outline_panel.draw(state, frame);
         self.tools_panel.draw(state, frame); // Render as floating modal
         self.help_modal.draw(state, frame);
+        self.confirm_modal.draw(state, frame); // Topmost: blocks everything until answered
     }
 
     /// Get event handlers in priority order for event dispatching.
     /// Returns mutable references to components for event handling.
     pub fn component_event_handlers(&mut self) -> Vec<&mut dyn EventHandler<State = AppState>> {
         vec![
+            &mut self.confirm_modal,
             &mut self.help_modal,
             &mut self.tools_panel,
             &mut self.properties_panel,
+            &mut self.layers_panel,
+            &mut self.outline_panel,
             &mut self.canvas,
             &mut self.statusbar,
         ]
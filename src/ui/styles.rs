@@ -1,29 +1,65 @@
 use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
+use std::sync::OnceLock;
 
 // ============================================================================
 // Design System - Color Palette
 // ============================================================================
 
-/// Primary accent color used for borders, highlights, and active elements
-pub const COLOR_PRIMARY: Color = Color::Cyan;
-
-/// Color for labels and important text
-pub const COLOR_LABEL: Color = Color::Yellow;
-
-/// Color for selected/focused background
-pub const COLOR_SELECTED_BG: Color = Color::DarkGray;
+/// Color palette used across the UI. Every color can be overridden in the `[theme]`
+/// section of the config file, using names like "cyan" or hex values like "#00afff".
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Theme {
+    /// Primary accent color used for borders, highlights, and active elements
+    pub primary: Color,
+    /// Color for labels and important text
+    pub label: Color,
+    /// Color for selected/focused background
+    pub selected_bg: Color,
+    /// Color for muted/disabled text
+    pub muted: Color,
+    /// Color for error messages
+    pub error: Color,
+    /// Color for success messages
+    pub success: Color,
+    /// Color for keybinding hints
+    pub hint: Color,
+    /// Statusbar mode badge colors
+    pub mode_select: Color,
+    pub mode_draw: Color,
+    pub mode_edit: Color,
+}
 
-/// Color for muted/disabled text
-pub const COLOR_MUTED: Color = Color::DarkGray;
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            primary: Color::Cyan,
+            label: Color::Yellow,
+            selected_bg: Color::DarkGray,
+            muted: Color::DarkGray,
+            error: Color::Red,
+            success: Color::Green,
+            hint: Color::Cyan,
+            mode_select: Color::Yellow,
+            mode_draw: Color::Blue,
+            mode_edit: Color::Magenta,
+        }
+    }
+}
 
-/// Color for error messages
-pub const COLOR_ERROR: Color = Color::Red;
+static THEME: OnceLock<Theme> = OnceLock::new();
 
-/// Color for success messages
-pub const COLOR_SUCCESS: Color = Color::Green;
+/// Install the theme from the config file. Only the first call has an effect, so this
+/// must happen before the first frame is drawn.
+pub fn set_theme(theme: Theme) {
+    let _ = THEME.set(theme);
+}
 
-/// Color for keybinding hints
-pub const COLOR_HINT: Color = Color::Cyan;
+/// The active theme (the default palette unless `set_theme` was called)
+pub fn theme() -> &'static Theme {
+    THEME.get_or_init(Theme::default)
+}
 
 // ============================================================================
 // Common Spacing
@@ -58,25 +94,26 @@ pub struct InputStyles {
 
 /// Get styles for property inputs based on state
 pub fn input_styles(is_editing: bool, is_focused: bool, panel_active: bool) -> InputStyles {
+    let theme = theme();
     // Only apply background highlight when panel is active
     if panel_active && is_editing {
         // Editing: dark gray background with cyan text
         InputStyles {
-            label: Style::default().fg(COLOR_LABEL).bg(COLOR_SELECTED_BG),
-            value: Style::default().fg(COLOR_PRIMARY).bg(COLOR_SELECTED_BG),
-            background: Style::default().bg(COLOR_SELECTED_BG),
+            label: Style::default().fg(theme.label).bg(theme.selected_bg),
+            value: Style::default().fg(theme.primary).bg(theme.selected_bg),
+            background: Style::default().bg(theme.selected_bg),
         }
     } else if panel_active && is_focused {
         // Focused: dark gray background
         InputStyles {
-            label: Style::default().fg(COLOR_LABEL).bg(COLOR_SELECTED_BG),
-            value: Style::default().fg(Color::White).bg(COLOR_SELECTED_BG),
-            background: Style::default().bg(COLOR_SELECTED_BG),
+            label: Style::default().fg(theme.label).bg(theme.selected_bg),
+            value: Style::default().fg(Color::White).bg(theme.selected_bg),
+            background: Style::default().bg(theme.selected_bg),
         }
     } else {
         // Normal: yellow label, default value
         InputStyles {
-            label: Style::default().fg(COLOR_LABEL),
+            label: Style::default().fg(theme.label),
             value: Style::default(),
             background: Style::default(),
         }
@@ -89,12 +126,12 @@ pub fn input_styles(is_editing: bool, is_focused: bool, panel_active: bool) -> I
 
 /// Style for labels (yellow)
 pub fn label_style() -> Style {
-    Style::default().fg(COLOR_LABEL)
+    Style::default().fg(theme().label)
 }
 
 /// Style for muted/helper text (dark gray)
 pub fn muted_style() -> Style {
-    Style::default().fg(COLOR_MUTED)
+    Style::default().fg(theme().muted)
 }
 
 /// Style for section headers (bold)
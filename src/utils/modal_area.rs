@@ -24,6 +24,30 @@ fn bottom_left_rect(canvas_area: Rect, width: u16, height: u16) -> Rect {
     }
 }
 
+/// Calculate rect for a modal at the bottom-right corner
+///
+/// Uses standard margins: 2px from right, 1px from bottom
+fn bottom_right_rect(canvas_area: Rect, width: u16, height: u16) -> Rect {
+    Rect {
+        x: canvas_area
+            .x
+            .saturating_add(canvas_area.width)
+            .saturating_sub(width)
+            .saturating_sub(2),
+        ..bottom_left_rect(canvas_area, width, height)
+    }
+}
+
+/// Calculate rect for a modal at the top-right corner
+///
+/// Uses standard margins: 2px from right, 1px from top
+fn top_right_rect(canvas_area: Rect, width: u16, height: u16) -> Rect {
+    Rect {
+        y: canvas_area.y + 1,
+        ..bottom_right_rect(canvas_area, width, height)
+    }
+}
+
 /// Calculate rect for a centered modal with percentage-based sizing
 fn centered_percent_rect(area: Rect, percent_x: u16, percent_y: u16) -> Rect {
     let popup_layout = Layout::default()
@@ -69,6 +93,30 @@ impl ModalArea {
         Self::new(bottom_left_rect(canvas_area, width, height))
     }
 
+    /// Position a modal at the bottom-right corner
+    ///
+    /// Uses standard margins: 2px from right, 1px from bottom
+    ///
+    /// # Arguments
+    /// * `canvas_area` - The canvas area to position relative to
+    /// * `width` - Width of the modal
+    /// * `height` - Height of the modal (including borders)
+    pub fn bottom_right(canvas_area: Rect, width: u16, height: u16) -> Self {
+        Self::new(bottom_right_rect(canvas_area, width, height))
+    }
+
+    /// Position a modal at the top-right corner
+    ///
+    /// Uses standard margins: 2px from right, 1px from top
+    ///
+    /// # Arguments
+    /// * `canvas_area` - The canvas area to position relative to
+    /// * `width` - Width of the modal
+    /// * `height` - Height of the modal (including borders)
+    pub fn top_right(canvas_area: Rect, width: u16, height: u16) -> Self {
+        Self::new(top_right_rect(canvas_area, width, height))
+    }
+
     /// Position a modal at the center with percentage-based sizing
     ///
     /// # Arguments
@@ -85,11 +133,11 @@ impl ModalArea {
     }
 
     /// Check if a point (x, y) is inside the modal area
-    pub fn contains(&self, x: u16, y: u16) -> bool {
-        x >= self.rect.x
-            && x < self.rect.x + self.rect.width
-            && y >= self.rect.y
-            && y < self.rect.y + self.rect.height
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.rect.x as i32
+            && x < (self.rect.x + self.rect.width) as i32
+            && y >= self.rect.y as i32
+            && y < (self.rect.y + self.rect.height) as i32
     }
 
     /// Calculate relative Y position from the top of the modal content area
@@ -99,8 +147,8 @@ impl ModalArea {
     ///
     /// # Arguments
     /// * `y` - Absolute Y coordinate
-    pub fn content_relative_y(&self, y: u16) -> u16 {
-        y.saturating_sub(self.rect.y + BORDER + PADDING)
+    pub fn content_relative_y(&self, y: i32) -> u16 {
+        (y - (self.rect.y + BORDER + PADDING) as i32).max(0) as u16
     }
 
     /// Clear the modal area
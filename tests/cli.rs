@@ -38,13 +38,20 @@ fn test_help() {
 An interactive terminal ASCII diagram editor
 
 Usage: textdraw [OPTIONS] [FILE]
+       textdraw <COMMAND>
+
+Commands:
+  export  Export a diagram to another format
+  import  Import plain-text ASCII art as an editable diagram
+  help    Print this message or the help of the given subcommand(s)
 
 Arguments:
   [FILE]  File to open (or render with --render flag)
 
 Options:
-  -r, --render  Render the file to the terminal without entering TUI mode
-  -h, --help    Print help
+  -r, --render          Render the file to the terminal without entering TUI mode
+      --layers <NAMES>  Render only these layers (comma-separated names) instead of the visible ones
+  -h, --help            Print help
 ";
 
     // Test --help
@@ -105,6 +112,149 @@ fn test_render() {
         .stdout(expected);
 }
 
+/// Test rendering elements placed at negative coordinates
+#[test]
+fn test_render_negative_coordinates() {
+    let diagram_file = TempDiagramFile::new(
+        "negative.textdraw",
+        r#"{
+  "version": "0.1.0",
+  "format": 2,
+  "elements": [
+    {
+      "Rectangle": {
+        "id": 0,
+        "name": "Rectangle 1",
+        "start": {"x": -3, "y": -2},
+        "width": 3,
+        "height": 2,
+        "bounds": {"min": {"x": -3, "y": -2}, "max": {"x": 0, "y": 0}}
+      }
+    },
+    {
+      "Text": {
+        "id": 1,
+        "name": "Text 2",
+        "position": {"x": 1, "y": 0},
+        "text": "hi",
+        "bounds": {"min": {"x": 1, "y": 0}, "max": {"x": 2, "y": 0}}
+      }
+    }
+  ],
+  "next_id": 2
+}"#,
+    );
+
+    let expected = "\
+┌──┐  
+│  │  
+└──┘hi
+";
+
+    textdraw_cmd()
+        .arg("--render")
+        .arg(diagram_file.path())
+        .assert()
+        .success()
+        .stdout(expected);
+}
+
+/// Test that a diagram saved in a newer format is rejected
+#[test]
+fn test_render_newer_format() {
+    let diagram_file = TempDiagramFile::new(
+        "future.textdraw",
+        r#"{
+  "version": "9.0.0",
+  "format": 99,
+  "elements": [],
+  "next_id": 0
+}"#,
+    );
+
+    textdraw_cmd()
+        .arg("--render")
+        .arg(diagram_file.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Diagram uses format 99"));
+}
+
+/// Diagram with a text on each of three layers; "Notes" is hidden in the editor
+const LAYERED_DIAGRAM: &str = r#"{
+  "version": "0.1.0",
+  "format": 4,
+  "elements": [
+    {
+      "Text": {
+        "id": 0,
+        "name": "Text 1",
+        "layer": 0,
+        "position": {"x": 0, "y": 0},
+        "text": "base",
+        "bounds": {"min": {"x": 0, "y": 0}, "max": {"x": 3, "y": 0}}
+      }
+    },
+    {
+      "Text": {
+        "id": 1,
+        "name": "Text 2",
+        "layer": 1,
+        "position": {"x": 0, "y": 1},
+        "text": "notes",
+        "bounds": {"min": {"x": 0, "y": 1}, "max": {"x": 4, "y": 1}}
+      }
+    },
+    {
+      "Text": {
+        "id": 2,
+        "name": "Text 3",
+        "layer": 2,
+        "position": {"x": 0, "y": 2},
+        "text": "draft",
+        "bounds": {"min": {"x": 0, "y": 2}, "max": {"x": 4, "y": 2}}
+      }
+    }
+  ],
+  "layers": [
+    {"id": 0, "name": "Base"},
+    {"id": 1, "name": "Notes", "visible": false},
+    {"id": 2, "name": "Draft"}
+  ],
+  "next_id": 3
+}"#;
+
+/// Test that hidden layers are left out, and --layers picks layers by name
+#[test]
+fn test_render_layers() {
+    let diagram_file = TempDiagramFile::new("layers.textdraw", LAYERED_DIAGRAM);
+
+    textdraw_cmd()
+        .arg("--render")
+        .arg(diagram_file.path())
+        .assert()
+        .success()
+        .stdout("base \n     \ndraft\n");
+
+    textdraw_cmd()
+        .arg("--render")
+        .arg("--layers")
+        .arg("Base,Notes")
+        .arg(diagram_file.path())
+        .assert()
+        .success()
+        .stdout("base \nnotes\n");
+
+    textdraw_cmd()
+        .arg("export")
+        .arg("--layers")
+        .arg("Missing")
+        .arg(diagram_file.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown layer: Missing"));
+}
+
 /// Test rendering an empty diagram
 #[test]
 fn test_render_empty_file() {
@@ -163,3 +313,200 @@ fn test_render_invalid_json() {
         .failure()
         .stderr(predicate::str::contains("Failed to parse diagram file"));
 }
+
+/// Diagram with a rectangle, an arrow and a text label, used by the export tests
+const EXPORT_DIAGRAM: &str = r#"{
+  "version": "0.1.0",
+  "format": 2,
+  "elements": [
+    {
+      "Rectangle": {
+        "id": 0,
+        "name": "Rectangle 1",
+        "start": {"x": 0, "y": 0},
+        "width": 8,
+        "height": 2,
+        "bounds": {"min": {"x": 0, "y": 0}, "max": {"x": 8, "y": 2}}
+      }
+    },
+    {
+      "Arrow": {
+        "id": 1,
+        "name": "Arrow 2",
+        "segments": [{"start": {"x": 8, "y": 1}, "length": 4, "direction": "Right"}],
+        "bounds": {"min": {"x": 8, "y": 1}, "max": {"x": 12, "y": 1}},
+        "head_start": " ",
+        "head_end": "▶"
+      }
+    },
+    {
+      "Text": {
+        "id": 2,
+        "name": "Text 3",
+        "position": {"x": 2, "y": 1},
+        "text": "a<b",
+        "bounds": {"min": {"x": 2, "y": 1}, "max": {"x": 4, "y": 1}}
+      }
+    }
+  ],
+  "next_id": 3
+}"#;
+
+/// Test exporting a diagram as SVG to stdout
+#[test]
+fn test_export_svg() {
+    let diagram_file = TempDiagramFile::new("export.textdraw", EXPORT_DIAGRAM);
+
+    textdraw_cmd()
+        .arg("export")
+        .arg("--format")
+        .arg("svg")
+        .arg(diagram_file.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("<svg"))
+        .stdout(predicate::str::contains(
+            r#"<rect x="20.2" y="24.4" width="67.2" height="33.6""#,
+        ))
+        .stdout(predicate::str::contains(r#"marker-end="url(#arrowhead)""#))
+        .stdout(predicate::str::contains(">a&lt;b</text>"));
+}
+
+/// Test that each arrowhead style gets its own SVG marker, and that a double arrow is
+/// drawn as two strokes
+#[test]
+fn test_export_svg_head_styles() {
+    let diagram = r#"{
+  "version": "0.1.0",
+  "format": 2,
+  "elements": [
+    {
+      "Arrow": {
+        "id": 0,
+        "name": "Arrow 1",
+        "segments": [{"start": {"x": 0, "y": 0}, "length": 4, "direction": "Right"}],
+        "bounds": {"min": {"x": 0, "y": 0}, "max": {"x": 4, "y": 0}},
+        "head_start": "●",
+        "head_end": "◆",
+        "line_style": "Double"
+      }
+    }
+  ],
+  "next_id": 1
+}"#;
+    let diagram_file = TempDiagramFile::new("heads.textdraw", diagram);
+
+    textdraw_cmd()
+        .arg("export")
+        .arg(diagram_file.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r#"marker-start="url(#arrowhead-dot)" marker-end="url(#arrowhead-diamond)""#,
+        ))
+        .stdout(predicate::str::contains(r#"<circle cx="5" cy="5" r="4""#))
+        .stdout(predicate::str::contains(
+            r#"<polyline points="20.2,22.9 53.8,22.9" stroke-width="1""#,
+        ))
+        .stdout(predicate::str::contains(
+            r#"<polyline points="20.2,25.9 53.8,25.9" stroke-width="1""#,
+        ));
+}
+
+/// Test exporting to a file with custom font size and padding
+#[test]
+fn test_export_svg_to_file() {
+    let diagram_file = TempDiagramFile::new("export.textdraw", EXPORT_DIAGRAM);
+    let temp = TempDir::new().unwrap();
+    let output = temp.child("out.svg");
+
+    textdraw_cmd()
+        .arg("export")
+        .arg(diagram_file.path())
+        .arg("-o")
+        .arg(output.path())
+        .arg("--font-size")
+        .arg("10")
+        .arg("--padding")
+        .arg("0")
+        .assert()
+        .success()
+        .stdout("");
+
+    // 13 columns x 3 rows of 6x12 pixel cells
+    output.assert(predicate::str::contains(r#"width="78" height="36""#));
+}
+
+/// Test exporting a diagram as HTML with a span per element
+#[test]
+fn test_export_html() {
+    let diagram_file = TempDiagramFile::new("export.textdraw", EXPORT_DIAGRAM);
+
+    textdraw_cmd()
+        .arg("export")
+        .arg("--format")
+        .arg("html")
+        .arg(diagram_file.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("<!DOCTYPE html>"))
+        .stdout(predicate::str::contains(
+            r#"<pre class="textdraw"><span class="element rectangle" data-element-id="0" title="Rectangle 1">┌───────┐</span>"#,
+        ))
+        .stdout(predicate::str::contains(
+            r#"<span class="element text" data-element-id="2" title="Text 3">a&lt;b</span>"#,
+        ))
+        .stdout(predicate::str::contains(
+            r#"<span class="element arrow" data-element-id="1" title="Arrow 2">────▶</span>"#,
+        ));
+}
+
+/// Test that importing ASCII art and rendering it back gives the same picture
+#[test]
+fn test_import_round_trip() {
+    let art = "\
+┌──────┐    ┌──────┐
+│ App  │───▶│  DB  │
+└──────┘    └──────┘
+";
+    let temp = TempDir::new().unwrap();
+    let input = temp.child("diagram.txt");
+    input.write_str(art).unwrap();
+    let output = temp.child("diagram.textdraw");
+
+    textdraw_cmd()
+        .arg("import")
+        .arg(input.path())
+        .arg("-o")
+        .arg(output.path())
+        .assert()
+        .success()
+        .stdout(format!(
+            "Imported 5 elements into {}\n",
+            output.path().display()
+        ));
+
+    textdraw_cmd()
+        .arg("--render")
+        .arg(output.path())
+        .assert()
+        .success()
+        .stdout(art);
+}
+
+/// Test that import writes next to the input file when no output is given
+#[test]
+fn test_import_default_output() {
+    let temp = TempDir::new().unwrap();
+    let input = temp.child("sketch.txt");
+    input.write_str("+--+\n|  |\n+--+\n").unwrap();
+
+    textdraw_cmd()
+        .arg("import")
+        .arg(input.path())
+        .assert()
+        .success();
+
+    temp.child("sketch.textdraw")
+        .assert(predicate::str::contains("\"Rectangle\""));
+}